@@ -2,15 +2,27 @@
 //!
 //! Security guarantees:
 //! - Audit entries are persisted locally as JSON in the app data directory.
-//! - On Unix, the audit file has `0o600` permissions (owner-only read/write).
-//! - Sensitive data in `details` is redacted before storage via keyword detection.
+//!   If it is not writable, a per-user temp directory (`0o700`, refused if
+//!   another user owns it or it is group/world accessible) is used instead
+//!   and the fallback is reported through `AuditLogger::file_status`.
+//! - On Unix, the audit file has `0o600` permissions (owner-only read/write);
+//!   broader permissions found at startup are tightened and reported.
+//! - Sensitive data in `details` is redacted before storage: keywords redact
+//!   the whole field, while JWT-shaped, connection-string, and high-entropy
+//!   values are replaced in place.
 //! - The in-memory log is bounded to 1000 entries to prevent unbounded growth.
 //! - Exported data goes through an additional sanitisation pass.
+//! - An optional append-only JSONL sink (`0o600`, never truncated) mirrors
+//!   each entry for log shippers; see `AuditLogger::with_jsonl_sink`.
+//! - The runtime `AuditLevel` can suppress routine reads, but secret value
+//!   access, secret writes, deletes, and purges are always recorded.
 
-use crate::models::AuditEntry;
+use crate::models::{AuditEntry, AuditFileStatus};
+use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -20,24 +32,281 @@ const MAX_ENTRIES: usize = 1000;
 /// Maximum character length for individual detail fields before truncation.
 const MAX_DETAIL_LEN: usize = 512;
 
+/// Minimum length of an unbroken base64/alphanumeric run checked for entropy.
+const MIN_TOKEN_RUN: usize = 20;
+
+/// Share of the highest entropy a run of its length and alphabet could reach
+/// (`log2(min(len, alphabet))`) at which it is treated as a key.
+const MIN_TOKEN_ENTROPY_RATIO: f64 = 0.8;
+
+/// Runs whose lowercase words (4+ letters) cover more than this share are
+/// treated as camelCase names or paths, not keys.
+const MAX_WORD_COVERAGE: f64 = 0.5;
+
+/// Keywords that mark a detail (or a JSON key's value) as sensitive.
+const SENSITIVE_KEYWORDS: [&str; 8] = [
+    "secret",
+    "token",
+    "password",
+    "access_key",
+    "connection_string",
+    "credential",
+    "private_key",
+    "bearer",
+];
+
+/// Actions that read or write secret values or tokens. Their details are
+/// always replaced with `[REDACTED]`, both when logged and when exported.
+const SENSITIVE_DATA_ACTIONS: [&str; 12] = [
+    "get_secret_value",
+    "reveal_secret_value",
+    "get_certificate_as_secret",
+    "reveal_certificate_as_secret",
+    "find_duplicate_secret_values",
+    "vault_value_size",
+    "export_secret_value",
+    "export_secret_values",
+    "inspect_token",
+    "set_secret",
+    "ensure_secret",
+    "rotate_secret",
+];
+
+/// Which actions `log_action` persists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditLevel {
+    /// Every action, including metadata reads (default).
+    All,
+    /// Everything except metadata reads.
+    MutationsOnly,
+    /// Only secret value/token access, secret writes, deletes, and purges.
+    SensitiveOnly,
+}
+
+impl AuditLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::MutationsOnly,
+            2 => Self::SensitiveOnly,
+            _ => Self::All,
+        }
+    }
+
+    /// Returns `true` when `action` is recorded at this level.
+    fn records(self, action: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::MutationsOnly => is_sensitive_action(action) || !is_read_action(action),
+            Self::SensitiveOnly => is_sensitive_action(action),
+        }
+    }
+}
+
+/// Actions recorded at every audit level.
+fn is_sensitive_action(action: &str) -> bool {
+    touches_sensitive_data(action)
+        || action == "set_audit_level"
+        || action.starts_with("delete_")
+        || action.starts_with("purge_")
+}
+
+/// `true` for actions listed in `SENSITIVE_DATA_ACTIONS`.
+fn touches_sensitive_data(action: &str) -> bool {
+    SENSITIVE_DATA_ACTIONS.contains(&action)
+}
+
+/// Details as stored and exported for `action`: fully redacted for
+/// sensitive-data actions, keyword-sanitized otherwise.
+fn redact_details(action: &str, details: &str) -> String {
+    if touches_sensitive_data(action) {
+        "[REDACTED]".to_string()
+    } else {
+        AuditLogger::sanitize_details(details)
+    }
+}
+
+/// Actions that only read metadata.
+fn is_read_action(action: &str) -> bool {
+    [
+        "list_",
+        "get_",
+        "check_",
+        "compare_",
+        "resolve_",
+        "expiring_",
+        "not_yet_active_",
+    ]
+    .iter()
+    .any(|prefix| action.starts_with(prefix))
+}
+
 /// Manages in-memory and persisted audit log entries.
 pub struct AuditLogger {
     entries: Arc<RwLock<Vec<AuditEntry>>>,
     log_dir: PathBuf,
+    file_status: AuditFileStatus,
+    /// Append-only JSONL mirror of every entry, if configured.
+    jsonl_sink: Option<PathBuf>,
+    /// Current `AuditLevel`, stored as its discriminant.
+    level: AtomicU8,
+    /// Sequence number for the next entry (never reused, even after `clear`:
+    /// the high-water mark is persisted to `audit.seq` when clearing).
+    next_seq: AtomicU64,
 }
 
 impl AuditLogger {
-    /// Initialises the logger, creating the audit directory and loading
-    /// any previously persisted entries from disk.
+    /// Initialises the logger, creating the audit directory (falling back to
+    /// the system temp directory if the app data directory is not writable),
+    /// repairing the audit file's permissions if needed, and loading any
+    /// previously persisted entries from disk.
     pub fn new(app_data_dir: PathBuf) -> Self {
-        let log_dir = app_data_dir.join("audit_logs");
-        std::fs::create_dir_all(&log_dir).ok();
+        Self::open(&app_data_dir, &std::env::temp_dir())
+    }
+
+    /// `new` with the fallback root made explicit for tests.
+    fn open(app_data_dir: &Path, fallback_root: &Path) -> Self {
+        let (log_dir, directory_warning) = Self::resolve_log_dir(app_data_dir, fallback_root);
+        if let Some(warning) = &directory_warning {
+            log::warn!("{}", warning);
+        }
 
-        let entries = Self::load_entries(&log_dir).unwrap_or_default();
+        let mut file_status = Self::check_permissions(&Self::log_file(&log_dir));
+        file_status.directory = log_dir.display().to_string();
+        file_status.directory_warning = directory_warning;
+
+        let mut entries = Self::load_entries(&log_dir).unwrap_or_default();
+        let mut next_seq = number_entries(&mut entries).max(Self::load_seq_mark(&log_dir));
+        if let Some(warning) = &file_status.warning {
+            entries.push(AuditEntry {
+                seq: next_seq,
+                timestamp: now_timestamp(),
+                vault_name: "system".to_string(),
+                action: "repair_audit_permissions".to_string(),
+                item_type: "audit".to_string(),
+                item_name: "audit.json".to_string(),
+                result: if file_status.repaired {
+                    "warning"
+                } else {
+                    "error"
+                }
+                .to_string(),
+                details: Some(Self::sanitize_details(warning)),
+                request_id: None,
+            });
+            next_seq += 1;
+            log::warn!("{}", warning);
+            Self::save_entries(&log_dir, &entries);
+        }
 
         Self {
             entries: Arc::new(RwLock::new(entries)),
             log_dir,
+            file_status,
+            jsonl_sink: None,
+            level: AtomicU8::new(AuditLevel::All as u8),
+            next_seq: AtomicU64::new(next_seq),
+        }
+    }
+
+    /// Returns the current audit level.
+    pub fn level(&self) -> AuditLevel {
+        AuditLevel::from_u8(self.level.load(Ordering::Relaxed))
+    }
+
+    /// Changes which actions are recorded from now on.
+    pub fn set_level(&self, level: AuditLevel) {
+        self.level.store(level as u8, Ordering::Relaxed);
+    }
+
+    /// Also appends each new entry as one compact JSON line to `path`.
+    /// The file is only ever appended to, so a log shipper can tail it.
+    pub fn with_jsonl_sink(mut self, path: PathBuf) -> Self {
+        self.jsonl_sink = Some(path);
+        self
+    }
+
+    /// Returns the audit file location and permission state detected at
+    /// startup.
+    pub fn file_status(&self) -> &AuditFileStatus {
+        &self.file_status
+    }
+
+    /// Returns the directory the audit log is written to.
+    pub fn log_dir(&self) -> &Path {
+        &self.log_dir
+    }
+
+    /// Picks `<app data>/audit_logs` when it is writable, else
+    /// `<fallback_root>/azvault-<user>/audit_logs` if that folder is private
+    /// to the current user. Returns a warning whenever the preferred
+    /// directory could not be used.
+    fn resolve_log_dir(app_data_dir: &Path, fallback_root: &Path) -> (PathBuf, Option<String>) {
+        let preferred = app_data_dir.join("audit_logs");
+        if is_writable_dir(&preferred) {
+            return (preferred, None);
+        }
+
+        let base = fallback_dir(fallback_root);
+        let fallback = base.join("audit_logs");
+        let reason = match ensure_private_dir(&base) {
+            Ok(()) if is_writable_dir(&fallback) => {
+                let warning = format!(
+                    "Audit directory {} is not writable; audit log is kept in {} instead.",
+                    preferred.display(),
+                    fallback.display()
+                );
+                return (fallback, Some(warning));
+            }
+            Ok(()) => format!("{} is not writable", fallback.display()),
+            Err(e) => e,
+        };
+
+        let warning = format!(
+            "Audit directory {} is not writable and no fallback is available ({}); audit entries will not be persisted.",
+            preferred.display(),
+            reason
+        );
+        (preferred, Some(warning))
+    }
+
+    /// Stats the audit file and tightens any group/other permission bits
+    /// to `0o600`.
+    #[cfg(unix)]
+    fn check_permissions(path: &Path) -> AuditFileStatus {
+        use std::os::unix::fs::PermissionsExt;
+
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return AuditFileStatus::default();
+        };
+        let mode = metadata.permissions().mode() & 0o777;
+        let mut status = AuditFileStatus {
+            exists: true,
+            mode: Some(format!("{:o}", mode)),
+            ..AuditFileStatus::default()
+        };
+
+        if mode & 0o077 != 0 {
+            let result = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+            status.repaired = result.is_ok();
+            status.warning = Some(match result {
+                Ok(()) => format!("Audit log permissions were {:o}; tightened to 600.", mode),
+                Err(e) => format!(
+                    "Audit log permissions are {:o} and could not be tightened: {}",
+                    mode, e
+                ),
+            });
+        }
+
+        status
+    }
+
+    /// Non-Unix platforms have no mode bits to check.
+    #[cfg(not(unix))]
+    fn check_permissions(path: &Path) -> AuditFileStatus {
+        AuditFileStatus {
+            exists: path.exists(),
+            ..AuditFileStatus::default()
         }
     }
 
@@ -53,6 +322,19 @@ impl AuditLogger {
         serde_json::from_str(&content).ok()
     }
 
+    /// Returns the path to the file holding the sequence high-water mark.
+    fn seq_file(log_dir: &Path) -> PathBuf {
+        log_dir.join("audit.seq")
+    }
+
+    /// Loads the next sequence number saved by `clear` (0 if none).
+    fn load_seq_mark(log_dir: &Path) -> u64 {
+        std::fs::read_to_string(Self::seq_file(log_dir))
+            .ok()
+            .and_then(|mark| mark.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
     /// Atomically writes all entries to the audit file.
     /// On Unix, restricts file permissions to owner-only (0o600).
     fn save_entries(log_dir: &PathBuf, entries: &[AuditEntry]) {
@@ -75,7 +357,25 @@ impl AuditLogger {
         }
     }
 
+    /// Appends one entry to the JSONL sink, creating it with `0o600`.
+    fn append_jsonl(path: &Path, entry: &AuditEntry) {
+        let Ok(line) = serde_json::to_string(entry) else {
+            return;
+        };
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        if let Ok(mut file) = options.open(path) {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+
     /// Records a new audit entry, sanitising details before persistence.
+    /// Actions filtered out by the current `AuditLevel` are dropped.
     pub async fn log_action(
         &self,
         vault_name: &str,
@@ -85,17 +385,28 @@ impl AuditLogger {
         result: &str,
         details: Option<&str>,
     ) {
+        if !self.level().records(action) {
+            return;
+        }
+
+        // The sequence is taken under the write lock so it matches push order.
+        let mut entries = self.entries.write().await;
         let entry = AuditEntry {
-            timestamp: chrono::Utc::now().to_rfc3339(),
+            seq: self.next_seq.fetch_add(1, Ordering::Relaxed),
+            timestamp: now_timestamp(),
             vault_name: vault_name.to_string(),
             action: action.to_string(),
             item_type: item_type.to_string(),
             item_name: item_name.to_string(),
             result: result.to_string(),
-            details: details.map(Self::sanitize_details),
+            details: details.map(|details| redact_details(action, details)),
+            request_id: crate::correlation::current(),
         };
 
-        let mut entries = self.entries.write().await;
+        if let Some(path) = &self.jsonl_sink {
+            Self::append_jsonl(path, &entry);
+        }
+
         entries.push(entry);
 
         // Enforce bounded log size
@@ -107,39 +418,88 @@ impl AuditLogger {
         Self::save_entries(&self.log_dir, &entries);
     }
 
-    /// Returns the most recent `limit` entries (default 100).
-    pub async fn get_entries(&self, limit: Option<usize>) -> Vec<AuditEntry> {
+    /// Returns the most recent `limit` entries (default 100), newest first
+    /// by sequence number. With `before_seq`, only older entries are
+    /// returned, so pages stay stable while new entries arrive.
+    pub async fn get_entries(
+        &self,
+        limit: Option<usize>,
+        before_seq: Option<u64>,
+    ) -> Vec<AuditEntry> {
+        self.query_entries(limit, before_seq, None).await
+    }
+
+    /// Like `get_entries`, restricted to entries whose vault name equals
+    /// `vault_name` (ignoring ASCII case; never a substring match).
+    pub async fn get_vault_entries(
+        &self,
+        vault_name: &str,
+        limit: Option<usize>,
+        before_seq: Option<u64>,
+    ) -> Vec<AuditEntry> {
+        self.query_entries(limit, before_seq, Some(vault_name))
+            .await
+    }
+
+    /// Shared query behind `get_entries` and `get_vault_entries`.
+    async fn query_entries(
+        &self,
+        limit: Option<usize>,
+        before_seq: Option<u64>,
+        vault_name: Option<&str>,
+    ) -> Vec<AuditEntry> {
         let entries = self.entries.read().await;
-        let limit = limit.unwrap_or(100).min(entries.len());
-        entries[entries.len() - limit..].to_vec()
+        let mut page: Vec<AuditEntry> = entries
+            .iter()
+            .filter(|e| match before_seq {
+                Some(before) => e.seq < before,
+                None => true,
+            })
+            .filter(|e| match vault_name {
+                Some(name) => e.vault_name.eq_ignore_ascii_case(name),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        page.sort_by(|a, b| b.seq.cmp(&a.seq));
+        page.truncate(limit.unwrap_or(100));
+        page
     }
 
     /// Produces a sanitised JSON export where sensitive actions have
-    /// their details replaced with `[REDACTED]`.
-    pub async fn get_sanitized_export(&self) -> String {
+    /// their details replaced with `[REDACTED]`. With `compact`, entries are
+    /// emitted as newline-delimited compact JSON (one entry per line).
+    pub async fn get_sanitized_export(&self, compact: bool) -> String {
         let entries = self.entries.read().await;
         let sanitized: Vec<_> = entries
             .iter()
             .map(|e| {
                 let mut entry = e.clone();
-                if entry.action.contains("secret")
-                    || entry.action.contains("token")
-                    || entry.action.contains("value")
-                {
-                    entry.details = Some("[REDACTED]".to_string());
-                } else if let Some(details) = &entry.details {
-                    entry.details = Some(Self::sanitize_details(details));
-                }
+                entry.details = entry
+                    .details
+                    .map(|details| redact_details(&entry.action, &details));
                 entry
             })
             .collect();
 
+        if compact {
+            return sanitized
+                .iter()
+                .filter_map(|entry| serde_json::to_string(entry).ok())
+                .map(|line| line + "\n")
+                .collect();
+        }
         serde_json::to_string_pretty(&sanitized).unwrap_or_default()
     }
 
-    /// Clears all in-memory and persisted audit entries.
+    /// Clears all in-memory and persisted audit entries. The next sequence
+    /// number is saved first so numbering continues after a restart.
     pub async fn clear(&self) {
         let mut entries = self.entries.write().await;
+        let mark = self.next_seq.load(Ordering::Relaxed).to_string();
+        if let Err(e) = std::fs::write(Self::seq_file(&self.log_dir), mark) {
+            log::warn!("Failed to save audit sequence mark: {}", e);
+        }
         entries.clear();
         Self::save_entries(&self.log_dir, &entries);
     }
@@ -147,25 +507,251 @@ impl AuditLogger {
     /// Redacts details that contain sensitive keywords (secret, token,
     /// password, access_key, connection_string, etc.) and truncates
     /// remaining text to `MAX_DETAIL_LEN` characters.
+    ///
+    /// JSON object/array details keep their structure: only the values of
+    /// keyword-matching keys (and sensitive string values) are redacted.
     pub(crate) fn sanitize_details(details: &str) -> String {
-        let lower = details.to_lowercase();
-        let sensitive_keywords = [
-            "secret",
-            "token",
-            "password",
-            "access_key",
-            "connection_string",
-            "credential",
-            "private_key",
-            "bearer",
-        ];
-        for keyword in &sensitive_keywords {
-            if lower.contains(keyword) {
-                return "[REDACTED]".to_string();
+        let sanitized = match serde_json::from_str::<serde_json::Value>(details) {
+            Ok(mut value) if value.is_object() || value.is_array() => {
+                redact_json(&mut value);
+                value.to_string()
             }
+            _ => redact_text(details),
+        };
+        sanitized.chars().take(MAX_DETAIL_LEN).collect()
+    }
+}
+
+/// `true` when `text` mentions any of `SENSITIVE_KEYWORDS`.
+fn has_sensitive_keyword(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    SENSITIVE_KEYWORDS
+        .iter()
+        .any(|keyword| lower.contains(keyword))
+}
+
+/// Whole-string redaction for plain-text details.
+fn redact_text(details: &str) -> String {
+    if has_sensitive_keyword(details) {
+        "[REDACTED]".to_string()
+    } else {
+        redact_patterns(details)
+    }
+}
+
+/// Redacts a JSON detail in place: values under keyword-matching keys are
+/// replaced outright; other strings get the plain-text treatment.
+fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if has_sensitive_keyword(key) {
+                    *field = serde_json::Value::String("[REDACTED]".to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        serde_json::Value::String(text) => *text = redact_text(text),
+        _ => {}
+    }
+}
+
+/// Per-user fallback folder under `fallback_root`, named after the login so
+/// users sharing a temp directory do not collide.
+fn fallback_dir(fallback_root: &Path) -> PathBuf {
+    let user: String = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    let user = if user.is_empty() { "user" } else { &user };
+    fallback_root.join(format!("azvault-{}", user))
+}
+
+/// Creates `dir` as `0o700` if missing, then checks that it is a real
+/// directory owned by the current user without group/other access, so a
+/// shared temp directory cannot be pre-seeded by someone else.
+#[cfg(unix)]
+fn ensure_private_dir(dir: &Path) -> Result<(), String> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(format!("cannot create {}: {}", dir.display(), e)),
+    }
+    let metadata = std::fs::symlink_metadata(dir)
+        .map_err(|e| format!("cannot inspect {}: {}", dir.display(), e))?;
+    if !metadata.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(format!(
+            "{} has permissions {:o}; expected 700",
+            dir.display(),
+            mode
+        ));
+    }
+
+    // A file we create is owned by our effective uid, so compare against it.
+    let probe = dir.join(format!(".owner-probe-{}", uuid::Uuid::new_v4()));
+    let owner = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .and_then(|file| file.metadata())
+        .map(|probe| probe.uid());
+    let _ = std::fs::remove_file(&probe);
+    match owner {
+        Ok(uid) if uid == metadata.uid() => Ok(()),
+        Ok(_) => Err(format!("{} is owned by another user", dir.display())),
+        Err(e) => Err(format!("{} is not writable: {}", dir.display(), e)),
+    }
+}
+
+/// Non-Unix temp directories are already per-user.
+#[cfg(not(unix))]
+fn ensure_private_dir(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))
+}
+
+/// Creates `dir` if needed and checks that a new file can be written in it.
+fn is_writable_dir(dir: &Path) -> bool {
+    if std::fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(format!(".write-probe-{}", uuid::Uuid::new_v4()));
+    let writable = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .is_ok();
+    if writable {
+        let _ = std::fs::remove_file(&probe);
+    }
+    writable
+}
+
+/// Current UTC time as RFC 3339 with millisecond precision.
+fn now_timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+/// Gives loaded entries strictly increasing sequence numbers (entries from
+/// before sequencing have `seq == 0` and keep their file order) and returns
+/// the next free number.
+fn number_entries(entries: &mut [AuditEntry]) -> u64 {
+    let mut last = 0;
+    for entry in entries.iter_mut() {
+        if entry.seq <= last {
+            entry.seq = last + 1;
         }
-        details.chars().take(MAX_DETAIL_LEN).collect()
+        last = entry.seq;
     }
+    last + 1
+}
+
+/// Replaces words that look like credentials with `[REDACTED]`, keeping the
+/// surrounding text and whitespace.
+fn redact_patterns(details: &str) -> String {
+    details
+        .split_inclusive(char::is_whitespace)
+        .map(|chunk| {
+            let word = chunk.trim_end();
+            let core = word.trim_matches(|c: char| "\"'()[]{}<>,.;:!?".contains(c));
+            if !core.is_empty() && looks_like_credential(core) {
+                chunk.replacen(core, "[REDACTED]", 1)
+            } else {
+                chunk.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Detects JWTs, connection-string keys/signatures, and high-entropy runs.
+fn looks_like_credential(word: &str) -> bool {
+    let lower = word.to_ascii_lowercase();
+    let is_jwt = word.starts_with("eyJ")
+        && word.split('.').count() == 3
+        && word
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(is_base64url_char));
+    let is_connection_string = ["accountkey=", "sharedaccesskey=", "sig="]
+        .iter()
+        .any(|marker| lower.contains(marker));
+
+    is_jwt
+        || is_connection_string
+        || word
+            .split(|c: char| !(c.is_ascii_alphanumeric() || "+/=".contains(c)))
+            .any(is_high_entropy_run)
+}
+
+fn is_base64url_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// `true` for long runs with mixed character classes and high entropy.
+/// Hyphenated names and GUIDs split into short runs and are not matched.
+///
+/// The entropy bar scales with the run's length and alphabet (hex or
+/// base64), since short runs cannot reach the entropy of long ones. Runs
+/// made mostly of lowercase words (`MyConnectionString2024`,
+/// `resourceGroups/Prod/providers`) are never matched.
+fn is_high_entropy_run(run: &str) -> bool {
+    if run.len() < MIN_TOKEN_RUN {
+        return false;
+    }
+    let classes = [
+        run.chars().any(|c| c.is_ascii_uppercase()),
+        run.chars().any(|c| c.is_ascii_lowercase()),
+        run.chars().any(|c| c.is_ascii_digit()),
+    ];
+    if classes.iter().filter(|present| **present).count() < 2 || is_word_like(run) {
+        return false;
+    }
+
+    let mut counts = std::collections::HashMap::new();
+    for c in run.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    let len = run.len() as f64;
+    let entropy: f64 = counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum();
+
+    let alphabet = if run.chars().all(|c| c.is_ascii_hexdigit()) {
+        16.0
+    } else {
+        64.0
+    };
+    entropy >= MIN_TOKEN_ENTROPY_RATIO * len.min(alphabet).log2()
+}
+
+/// `true` when lowercase words of 4+ letters cover most of `run`, as in
+/// camelCase identifiers and ARM paths; random keys rarely have them.
+fn is_word_like(run: &str) -> bool {
+    let mut covered = 0;
+    let mut current = 0;
+    for c in run.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_lowercase() {
+            current += 1;
+        } else {
+            if current >= 4 {
+                covered += current;
+            }
+            current = 0;
+        }
+    }
+    covered as f64 > MAX_WORD_COVERAGE * run.len() as f64
 }
 
 // ── Tests ──
@@ -240,6 +826,292 @@ mod tests {
         );
     }
 
+    #[test]
+    fn redacts_jwt_shaped_values_in_place() {
+        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        use base64::Engine;
+
+        let jwt = format!(
+            "{}.{}.{}",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#),
+            URL_SAFE_NO_PAD.encode(r#"{"sub":"user"}"#),
+            URL_SAFE_NO_PAD.encode("sig")
+        );
+        assert_eq!(
+            AuditLogger::sanitize_details(&format!("Got {} from the CLI", jwt)),
+            "Got [REDACTED] from the CLI"
+        );
+    }
+
+    #[test]
+    fn redacts_random_tokens_but_keeps_names() {
+        assert_eq!(
+            AuditLogger::sanitize_details("The new value is Xk9Qm2Vt7Lp4Rw8Zc3Nb."),
+            "The new value is [REDACTED]."
+        );
+
+        let safe = "Updated app-settings-2024-westeurope (id 00000000-0000-0000-0000-000000000001)";
+        assert_eq!(AuditLogger::sanitize_details(safe), safe);
+    }
+
+    #[test]
+    fn keeps_camel_case_names_and_arm_paths() {
+        for safe in [
+            "Read MyApplicationConnectionString2024 from kv-a",
+            "Scope resourceGroups/ProductionEastUS2/providers/Microsoft.KeyVault",
+            "Listed StorageAccountConnectionStringForProductionWestEurope2024",
+        ] {
+            assert_eq!(AuditLogger::sanitize_details(safe), safe);
+        }
+    }
+
+    #[test]
+    fn redacts_only_sensitive_json_fields() {
+        let details =
+            r#"{"vault":"kv-a","password":"hunter2","nested":{"access_key":"abc","count":3}}"#;
+        assert_eq!(
+            AuditLogger::sanitize_details(details),
+            r#"{"nested":{"access_key":"[REDACTED]","count":3},"password":"[REDACTED]","vault":"kv-a"}"#
+        );
+    }
+
+    #[test]
+    fn redacts_sensitive_json_string_values() {
+        assert_eq!(
+            AuditLogger::sanitize_details(r#"["kv-a","my secret value"]"#),
+            r#"["kv-a","[REDACTED]"]"#
+        );
+    }
+
+    #[test]
+    fn non_json_details_redact_whole_string() {
+        assert_eq!(
+            AuditLogger::sanitize_details("{password: hunter2}"),
+            "[REDACTED]"
+        );
+        assert_eq!(AuditLogger::sanitize_details("\"token\""), "[REDACTED]");
+    }
+
+    #[test]
+    fn redacts_connection_string_keys() {
+        assert_eq!(
+            AuditLogger::sanitize_details("Using Endpoint=sb://bus;SharedAccessKey=abc now"),
+            "Using [REDACTED] now"
+        );
+    }
+
+    #[test]
+    fn audit_levels_filter_actions() {
+        assert!(AuditLevel::All.records("list_secrets"));
+        assert!(!AuditLevel::MutationsOnly.records("list_secrets"));
+        assert!(!AuditLevel::MutationsOnly.records("get_secret_metadata"));
+        assert!(!AuditLevel::MutationsOnly.records("not_yet_active_secrets"));
+        assert!(AuditLevel::MutationsOnly.records("get_secret_value"));
+        assert!(AuditLevel::SensitiveOnly.records("reveal_secret_value"));
+        assert!(AuditLevel::SensitiveOnly.records("get_certificate_as_secret"));
+        assert!(AuditLevel::SensitiveOnly.records("reveal_certificate_as_secret"));
+        assert!(AuditLevel::MutationsOnly.records("recover_secret"));
+        assert!(!AuditLevel::SensitiveOnly.records("recover_secret"));
+        assert!(AuditLevel::SensitiveOnly.records("delete_secret"));
+        assert!(AuditLevel::SensitiveOnly.records("purge_all_deleted_secrets"));
+        assert!(AuditLevel::SensitiveOnly.records("set_secret"));
+    }
+
+    #[tokio::test]
+    async fn vault_entries_match_exact_vault_name() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+        for vault in ["prod", "prod-backup", "PROD", "dev"] {
+            logger
+                .log_action(vault, "list_secrets", "secret", "*", "success", None)
+                .await;
+        }
+
+        let entries = logger.get_vault_entries("prod", None, None).await;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].vault_name, "PROD");
+        assert!(entries[0].seq > entries[1].seq);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn entries_are_sequenced_and_paged_by_seq() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+        for name in ["a", "b", "c"] {
+            logger
+                .log_action("vault", "delete_secret", "secret", name, "success", None)
+                .await;
+        }
+
+        let newest = logger.get_entries(Some(2), None).await;
+        assert_eq!(newest[0].item_name, "c");
+        assert!(newest[0].seq > newest[1].seq);
+        let older = logger.get_entries(None, Some(newest[1].seq)).await;
+        assert_eq!(older.len(), 1);
+        assert_eq!(older[0].item_name, "a");
+
+        // Millisecond precision, UTC
+        assert!(newest[0].timestamp.ends_with('Z'));
+        assert_eq!(newest[0].timestamp.split('.').nth(1).map(str::len), Some(4));
+
+        // Sequence survives a reload
+        let reloaded = AuditLogger::new(dir.clone());
+        reloaded
+            .log_action("vault", "delete_secret", "secret", "d", "success", None)
+            .await;
+        assert!(reloaded.get_entries(Some(1), None).await[0].seq > newest[0].seq);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn sequence_is_not_reused_after_clear_and_reload() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+        for name in ["a", "b"] {
+            logger
+                .log_action("vault", "delete_secret", "secret", name, "success", None)
+                .await;
+        }
+        let last = logger.get_entries(Some(1), None).await[0].seq;
+        logger.clear().await;
+
+        let reloaded = AuditLogger::new(dir.clone());
+        reloaded
+            .log_action("vault", "delete_secret", "secret", "c", "success", None)
+            .await;
+        assert!(reloaded.get_entries(Some(1), None).await[0].seq > last);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn number_entries_fills_legacy_sequence() {
+        let entry = |seq| AuditEntry {
+            seq,
+            timestamp: String::new(),
+            vault_name: String::new(),
+            action: String::new(),
+            item_type: String::new(),
+            item_name: String::new(),
+            result: String::new(),
+            details: None,
+            request_id: None,
+        };
+        let mut entries = vec![entry(0), entry(0), entry(7)];
+        assert_eq!(number_entries(&mut entries), 8);
+        let seqs: Vec<u64> = entries.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![1, 2, 7]);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_temp_dir_when_app_data_is_unwritable() {
+        let root =
+            std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        // A plain file cannot hold the audit directory.
+        let app_data = root.join("app-data");
+        std::fs::write(&app_data, b"").unwrap();
+
+        let logger = AuditLogger::open(&app_data, &root);
+        let status = logger.file_status();
+        assert_eq!(logger.log_dir(), fallback_dir(&root).join("audit_logs"));
+        assert_eq!(status.directory, logger.log_dir().display().to_string());
+        assert!(status.directory_warning.is_some());
+
+        logger
+            .log_action("vault", "delete_secret", "secret", "db", "success", None)
+            .await;
+        assert!(AuditLogger::log_file(&logger.log_dir).exists());
+
+        let writable = AuditLogger::open(&root, &root);
+        assert_eq!(writable.log_dir(), root.join("audit_logs"));
+        assert!(writable.file_status().directory_warning.is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fallback_dir_is_private_and_loose_ones_are_refused() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root =
+            std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let base = fallback_dir(&root);
+
+        ensure_private_dir(&base).unwrap();
+        let mode = std::fs::metadata(&base).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o700);
+
+        std::fs::set_permissions(&base, std::fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(ensure_private_dir(&base).is_err());
+
+        let app_data = root.join("app-data");
+        std::fs::write(&app_data, b"").unwrap();
+        let logger = AuditLogger::open(&app_data, &root);
+        assert_eq!(logger.log_dir(), app_data.join("audit_logs"));
+        assert!(logger
+            .file_status()
+            .directory_warning
+            .as_deref()
+            .is_some_and(|w| w.contains("expected 700")));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn log_action_respects_level() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+        logger.set_level(AuditLevel::SensitiveOnly);
+        assert_eq!(logger.level(), AuditLevel::SensitiveOnly);
+
+        logger
+            .log_action("vault", "list_secrets", "secret", "*", "success", None)
+            .await;
+        logger
+            .log_action("vault", "delete_secret", "secret", "db", "success", None)
+            .await;
+
+        let entries = logger.get_entries(None, None).await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, "delete_secret");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn log_action_records_operation_request_id() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+
+        logger
+            .log_action("vault", "sign_out", "auth", "user", "success", None)
+            .await;
+        let request_id = crate::correlation::scope(async {
+            logger
+                .log_action("vault", "delete_secret", "secret", "db", "success", None)
+                .await;
+            crate::correlation::current()
+        })
+        .await;
+
+        let entries = logger.get_entries(None, None).await;
+        assert_eq!(entries[0].request_id, request_id);
+        assert!(entries[0].request_id.is_some());
+        assert!(entries[1].request_id.is_none());
+        assert!(logger
+            .get_sanitized_export(true)
+            .await
+            .contains(request_id.as_deref().unwrap()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[tokio::test]
     async fn keeps_entries_bounded_at_max() {
         let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
@@ -259,7 +1131,7 @@ mod tests {
                 .await;
         }
 
-        let all_entries = logger.get_entries(Some(2000)).await;
+        let all_entries = logger.get_entries(Some(2000), None).await;
         assert!(
             all_entries.len() <= MAX_ENTRIES,
             "Should not exceed {} entries, got {}",
@@ -282,15 +1154,44 @@ mod tests {
                 .await;
         }
 
-        let entries = logger.get_entries(Some(10)).await;
+        let entries = logger.get_entries(Some(10), None).await;
         assert_eq!(entries.len(), 10);
 
-        let entries = logger.get_entries(None).await;
+        let entries = logger.get_entries(None, None).await;
         assert_eq!(entries.len(), 50); // default limit is 100, but only 50 exist
 
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[tokio::test]
+    async fn get_entries_returns_most_recent_newest_first() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+
+        for i in 0..5 {
+            logger
+                .log_action(
+                    "vault",
+                    &format!("action-{}", i),
+                    "secret",
+                    "item",
+                    "success",
+                    None,
+                )
+                .await;
+        }
+
+        let actions: Vec<String> = logger
+            .get_entries(Some(3), None)
+            .await
+            .into_iter()
+            .map(|e| e.action)
+            .collect();
+        assert_eq!(actions, vec!["action-4", "action-3", "action-2"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[tokio::test]
     async fn clear_removes_all_entries() {
         let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
@@ -299,10 +1200,10 @@ mod tests {
         logger
             .log_action("vault", "action", "secret", "item", "success", None)
             .await;
-        assert_eq!(logger.get_entries(None).await.len(), 1);
+        assert_eq!(logger.get_entries(None, None).await.len(), 1);
 
         logger.clear().await;
-        assert_eq!(logger.get_entries(None).await.len(), 0);
+        assert_eq!(logger.get_entries(None, None).await.len(), 0);
 
         let _ = std::fs::remove_dir_all(&dir);
     }
@@ -323,13 +1224,74 @@ mod tests {
             )
             .await;
 
-        let export = logger.get_sanitized_export().await;
+        let export = logger.get_sanitized_export(false).await;
         assert!(export.contains("[REDACTED]"));
         assert!(!export.contains("actual value here"));
 
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn redaction_follows_action_set_not_substrings() {
+        // Metadata-only actions mentioning "secret" keep their details
+        assert_eq!(
+            redact_details("set_secrets_tags", "3 items updated"),
+            "3 items updated"
+        );
+        assert_eq!(redact_details("rotate_secret", "new version"), "[REDACTED]");
+        assert_eq!(redact_details("inspect_token", "scope=vault"), "[REDACTED]");
+    }
+
+    #[tokio::test]
+    async fn export_keeps_details_of_metadata_actions() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+
+        logger
+            .log_action(
+                "vault",
+                "list_secrets",
+                "secret",
+                "*",
+                "success",
+                Some("12 items"),
+            )
+            .await;
+
+        let export = logger.get_sanitized_export(false).await;
+        assert!(export.contains("12 items"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn compact_export_is_one_redacted_entry_per_line() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+
+        logger
+            .log_action("vault", "list_keys", "key", "*", "success", None)
+            .await;
+        logger
+            .log_action(
+                "vault",
+                "get_secret_value",
+                "secret",
+                "db",
+                "success",
+                Some("actual value here"),
+            )
+            .await;
+
+        let export = logger.get_sanitized_export(true).await;
+        let lines: Vec<&str> = export.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let second: AuditEntry = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second.details.as_deref(), Some("[REDACTED]"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[tokio::test]
     async fn persists_and_loads_entries() {
         let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
@@ -345,11 +1307,80 @@ mod tests {
         // Load from disk in a new instance
         {
             let logger = AuditLogger::new(dir.clone());
-            let entries = logger.get_entries(None).await;
+            let entries = logger.get_entries(None, None).await;
             assert_eq!(entries.len(), 1);
             assert_eq!(entries[0].action, "test_persist");
         }
 
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    #[tokio::test]
+    async fn jsonl_sink_appends_one_line_per_entry() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let sink = dir.join("audit.log.jsonl");
+        let logger = AuditLogger::new(dir.clone()).with_jsonl_sink(sink.clone());
+
+        logger
+            .log_action("vault", "first", "secret", "item", "success", None)
+            .await;
+        logger.clear().await;
+        logger
+            .log_action("vault", "second", "secret", "item", "success", None)
+            .await;
+
+        let content = std::fs::read_to_string(&sink).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2, "clear must not truncate the sink");
+        let first: AuditEntry = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first.action, "first");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&sink).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode, 0o600);
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_audit_file_reports_not_existing() {
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let logger = AuditLogger::new(dir.clone());
+        assert!(!logger.file_status().exists);
+        assert!(!logger.file_status().repaired);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn tightens_overly_broad_audit_file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("azvault-audit-test-{}", uuid::Uuid::new_v4()));
+        let log_dir = dir.join("audit_logs");
+        std::fs::create_dir_all(&log_dir).unwrap();
+        let path = log_dir.join("audit.json");
+        std::fs::write(&path, "[]").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let logger = AuditLogger::new(dir.clone());
+        let status = logger.file_status();
+        assert!(status.exists);
+        assert_eq!(status.mode.as_deref(), Some("644"));
+        assert!(status.repaired);
+        assert!(status.warning.is_some());
+        assert_eq!(
+            logger.get_entries(None, None).await[0].action,
+            "repair_audit_permissions"
+        );
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
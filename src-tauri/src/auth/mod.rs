@@ -6,11 +6,16 @@
 //!   on every request and held only in memory.
 //! - Token requests are restricted to an allow-list of Azure resource scopes.
 //! - Tenant preference is app-local and only influences the `--tenant` flag.
+//! - Token scopes follow the configured national cloud (see `crate::cloud`).
 //!
 //! This module intentionally avoids MSAL/browser-based flows to keep the
 //! attack surface minimal for a desktop developer tool.
 
+use crate::cloud::{Cloud, CloudConfig};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
 use serde_json::Value;
+use std::fmt;
 use std::process::Command;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -18,17 +23,121 @@ use tokio::sync::RwLock;
 /// Default tenant value used by Azure CLI when no explicit tenant is specified.
 const TENANT_DEFAULT: &str = "organizations";
 
+/// Azure CLI failure modes, distinguished so callers and the UI can give
+/// targeted guidance. Raw CLI output is never included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    /// `az` is not installed or not on `PATH`.
+    CliNotInstalled,
+    /// Azure CLI has no valid session for the configured cloud.
+    CliNotLoggedIn(Cloud),
+    /// The session cannot access the selected tenant or subscription.
+    CliWrongTenant,
+    /// Any other failure, already phrased for the user.
+    Other(String),
+}
+
+impl CliError {
+    /// Classifies the stderr of a failed `az` call. Tenant signatures are
+    /// checked first because those messages often also suggest `az login`.
+    fn classify(stderr: &str, cloud: Cloud) -> Self {
+        let stderr = stderr.to_ascii_lowercase();
+        let has = |signatures: &[&str]| signatures.iter().any(|s| stderr.contains(s));
+
+        if has(&[
+            "command not found",
+            "is not recognized as an internal or external command",
+        ]) {
+            Self::CliNotInstalled
+        } else if has(&[
+            "aadsts90002",
+            "aadsts50020",
+            "subscription not found",
+            "doesn't exist in cloud",
+            "no subscriptions found",
+        ]) || (stderr.contains("tenant") && stderr.contains("not found"))
+        {
+            Self::CliWrongTenant
+        } else if has(&[
+            "az login",
+            "not logged in",
+            "aadsts700082",
+            "aadsts70043",
+            "interactive authentication is needed",
+        ]) {
+            Self::CliNotLoggedIn(cloud)
+        } else {
+            Self::Other(format!(
+                "Azure CLI token acquisition failed. Run 'az cloud set --name {}' and 'az login', then retry.",
+                cloud.cli_name()
+            ))
+        }
+    }
+
+    /// User-facing description of the failure.
+    pub fn message(&self) -> String {
+        match self {
+            Self::CliNotInstalled => "Azure CLI is not installed or not on PATH.".to_string(),
+            Self::CliNotLoggedIn(_) => "Azure CLI is not signed in.".to_string(),
+            Self::CliWrongTenant => {
+                "Azure CLI session has no access to the selected tenant or subscription."
+                    .to_string()
+            }
+            Self::Other(message) => message.clone(),
+        }
+    }
+
+    /// Next step for the user, if one is known.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::CliNotInstalled => Some(
+                "Install the Azure CLI (https://learn.microsoft.com/cli/azure/install-azure-cli) and restart AzVault."
+                    .to_string(),
+            ),
+            Self::CliNotLoggedIn(cloud) => Some(format!(
+                "Run 'az cloud set --name {}' and 'az login', then retry.",
+                cloud.cli_name()
+            )),
+            Self::CliWrongTenant => Some(
+                "Run 'az login --tenant <tenant-id>' or select a tenant this account can access."
+                    .to_string(),
+            ),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+/// Uses the `"message | Hint: hint"` format understood by `CommandError`.
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.hint() {
+            Some(hint) => write!(f, "{} | Hint: {}", self.message(), hint),
+            None => f.write_str(&self.message()),
+        }
+    }
+}
+
+impl From<CliError> for String {
+    fn from(error: CliError) -> Self {
+        error.to_string()
+    }
+}
+
 /// Manages Azure CLI-based authentication for the app.
 pub struct AuthManager {
     /// The currently preferred tenant ID (set by the user in the sidebar).
     tenant_id: Arc<RwLock<String>>,
+    /// The national cloud whose token scopes are requested.
+    cloud: CloudConfig,
 }
 
 impl AuthManager {
-    /// Creates a new CLI-backed auth manager with the default tenant.
-    pub fn new() -> Self {
+    /// Creates a new CLI-backed auth manager for the given cloud with the
+    /// default tenant.
+    pub fn new(cloud: CloudConfig) -> Self {
         Self {
             tenant_id: Arc::new(RwLock::new(TENANT_DEFAULT.to_string())),
+            cloud,
         }
     }
 
@@ -39,21 +148,48 @@ impl AuthManager {
         *tid = sanitized;
     }
 
+    /// Returns the authority (login) origin in use.
+    pub fn authority(&self) -> &str {
+        self.cloud.cloud.authority_host()
+    }
+
     /// Returns the currently preferred tenant ID.
     pub async fn get_tenant(&self) -> String {
         self.tenant_id.read().await.clone()
     }
 
     /// Requests an ARM management-plane token from Azure CLI.
-    pub async fn get_management_token(&self) -> Result<String, String> {
+    pub async fn get_management_token(&self) -> Result<String, CliError> {
         let tenant = self.get_tenant().await;
-        self.get_az_cli_token("https://management.azure.com/", Some(&tenant))
+        let cloud = self.cloud.cloud;
+        Self::get_az_cli_token(cloud, cloud.management_scope(), Some(&tenant))
     }
 
     /// Requests a Key Vault data-plane token from Azure CLI.
-    pub async fn get_vault_token(&self) -> Result<String, String> {
+    pub async fn get_vault_token(&self) -> Result<String, CliError> {
+        let tenant = self.get_tenant().await;
+        let cloud = self.cloud.cloud;
+        Self::get_az_cli_token(cloud, cloud.vault_scope(), Some(&tenant))
+    }
+
+    /// Requests the management and vault tokens in parallel so Azure CLI
+    /// has both refreshed before the first vault operation.
+    pub async fn warm_tokens(&self) -> Result<(), String> {
         let tenant = self.get_tenant().await;
-        self.get_az_cli_token("https://vault.azure.net", Some(&tenant))
+        let cloud = self.cloud.cloud;
+        let fetch = |resource: &'static str| {
+            let tenant = tenant.clone();
+            tokio::task::spawn_blocking(move || {
+                Self::get_az_cli_token(cloud, resource, Some(&tenant))
+            })
+        };
+
+        let (management, vault) =
+            tokio::join!(fetch(cloud.management_scope()), fetch(cloud.vault_scope()));
+        for result in [management, vault] {
+            result.map_err(|e| format!("Token prefetch failed: {}", e))??;
+        }
+        Ok(())
     }
 
     /// Resets the tenant preference (app-level sign-out).
@@ -68,14 +204,50 @@ impl AuthManager {
         self.get_management_token().await.is_ok()
     }
 
+    /// Decodes the header and claims of a JWT access token **without**
+    /// verifying its signature (diagnostics only, never for trust decisions).
+    ///
+    /// The signature segment is discarded and hash claims (`at_hash`,
+    /// `c_hash`, ...) are stripped from the returned claims.
+    pub fn decode_token(token: &str) -> Result<(Value, Value), String> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(_signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err("Access token is not a well-formed JWT.".to_string());
+        };
+
+        let header = Self::decode_jwt_segment(header)?;
+        let mut claims = Self::decode_jwt_segment(payload)?;
+        if let Some(obj) = claims.as_object_mut() {
+            obj.retain(|key, _| !key.ends_with("_hash"));
+        }
+        Ok((header, claims))
+    }
+
+    /// Decodes a single base64url-encoded JWT segment into JSON.
+    fn decode_jwt_segment(segment: &str) -> Result<Value, String> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(segment.trim_end_matches('='))
+            .map_err(|_| "Access token segment is not valid base64url.".to_string())?;
+        serde_json::from_slice(&bytes)
+            .map_err(|_| "Access token segment is not valid JSON.".to_string())
+    }
+
     /// Calls `az account get-access-token` for an allow-listed resource scope.
     ///
     /// # Security
     /// - Only resources in `is_allowed_cli_resource` can be requested.
     /// - The tenant ID is sanitised to prevent command injection.
-    fn get_az_cli_token(&self, resource: &str, tenant: Option<&str>) -> Result<String, String> {
+    fn get_az_cli_token(
+        cloud: Cloud,
+        resource: &str,
+        tenant: Option<&str>,
+    ) -> Result<String, CliError> {
         if !Self::is_allowed_cli_resource(resource) {
-            return Err("Unsupported Azure CLI resource scope.".to_string());
+            return Err(CliError::Other(
+                "Unsupported Azure CLI resource scope.".to_string(),
+            ));
         }
 
         let mut args = vec![
@@ -94,26 +266,30 @@ impl AuthManager {
             }
         }
 
-        let output = Command::new("az")
-            .args(args)
-            .output()
-            .map_err(|e| format!("Azure CLI not available: {}", e))?;
+        let output = Command::new("az").args(args).output().map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                CliError::CliNotInstalled
+            } else {
+                CliError::Other(format!("Azure CLI could not be started: {}", e))
+            }
+        })?;
 
         if !output.status.success() {
-            return Err(
-                "Azure CLI token acquisition failed. Run 'az login' and retry.".to_string(),
-            );
+            return Err(CliError::classify(
+                &String::from_utf8_lossy(&output.stderr),
+                cloud,
+            ));
         }
 
-        Self::parse_cli_access_token(&output.stdout)
+        Self::parse_cli_access_token(&output.stdout).map_err(CliError::Other)
     }
 
-    /// Allow-list of token resource scopes that AzVault is permitted to request.
+    /// Allow-list of token resource scopes that AzVault is permitted to request
+    /// (the management and vault scopes of the known clouds).
     fn is_allowed_cli_resource(resource: &str) -> bool {
-        matches!(
-            resource,
-            "https://management.azure.com/" | "https://vault.azure.net"
-        )
+        Cloud::ALL
+            .iter()
+            .any(|cloud| resource == cloud.management_scope() || resource == cloud.vault_scope())
     }
 
     /// Parses the JSON output of `az account get-access-token` and extracts
@@ -170,6 +346,83 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn cli_resource_scope_allows_sovereign_clouds() {
+        assert!(AuthManager::is_allowed_cli_resource(
+            "https://management.usgovcloudapi.net/"
+        ));
+        assert!(AuthManager::is_allowed_cli_resource(
+            "https://vault.azure.cn"
+        ));
+    }
+
+    #[test]
+    fn uses_scopes_of_configured_cloud() {
+        let auth = AuthManager::new(CloudConfig::new(Cloud::China));
+        assert_eq!(auth.cloud.cloud, Cloud::China);
+        assert_eq!(
+            auth.cloud.cloud.management_scope(),
+            "https://management.chinacloudapi.cn/"
+        );
+    }
+
+    // ── CLI failure classification ──
+
+    #[test]
+    fn classifies_missing_cli() {
+        for stderr in [
+            "/bin/sh: 1: az: command not found",
+            "'az' is not recognized as an internal or external command,",
+        ] {
+            assert_eq!(
+                CliError::classify(stderr, Cloud::Public),
+                CliError::CliNotInstalled
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_missing_session() {
+        for stderr in [
+            "ERROR: Please run 'az login' to setup account.",
+            "AADSTS700082: The refresh token has expired due to inactivity.",
+        ] {
+            assert_eq!(
+                CliError::classify(stderr, Cloud::China),
+                CliError::CliNotLoggedIn(Cloud::China)
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_wrong_tenant_before_login_prompt() {
+        for stderr in [
+            "ERROR: AADSTS90002: Tenant 'contoso' not found. Please run 'az login'.",
+            "ERROR: Subscription not found.",
+            "ERROR: The subscription of 'dev' doesn't exist in cloud 'AzureCloud'.",
+        ] {
+            assert_eq!(
+                CliError::classify(stderr, Cloud::Public),
+                CliError::CliWrongTenant
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_cli_failure_keeps_generic_guidance_without_stderr() {
+        let error = CliError::classify("ERROR: something unexpected", Cloud::UsGov);
+        let message = error.to_string();
+        assert!(message.contains(Cloud::UsGov.cli_name()));
+        assert!(!message.contains("unexpected"));
+    }
+
+    #[test]
+    fn cli_error_display_carries_hint() {
+        let message = String::from(CliError::CliNotLoggedIn(Cloud::Public));
+        assert!(message.starts_with("Azure CLI is not signed in. | Hint: "));
+        assert!(message.contains("az login"));
+    }
+
     #[test]
     fn parses_cli_access_token_payload() {
         let payload = br#"{"accessToken":"eyJ0eXAi...","expiresOn":"2024-01-01"}"#;
@@ -221,7 +474,7 @@ mod tests {
 
     #[tokio::test]
     async fn set_and_get_tenant() {
-        let auth = AuthManager::new();
+        let auth = AuthManager::new(CloudConfig::default());
         assert_eq!(auth.get_tenant().await, "organizations");
 
         auth.set_tenant("12345678-abcd-ef01-2345-6789abcdef01")
@@ -234,7 +487,7 @@ mod tests {
 
     #[tokio::test]
     async fn sign_out_resets_tenant() {
-        let auth = AuthManager::new();
+        let auth = AuthManager::new(CloudConfig::default());
         auth.set_tenant("custom-tenant").await;
         assert_ne!(auth.get_tenant().await, "organizations");
 
@@ -242,6 +495,31 @@ mod tests {
         assert_eq!(auth.get_tenant().await, "organizations");
     }
 
+    #[test]
+    fn decodes_token_claims_and_strips_hashes() {
+        let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"RS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD
+            .encode(br#"{"oid":"obj-1","tid":"tid-1","at_hash":"abc","roles":["Reader"]}"#);
+        let token = format!("{}.{}.signature", header, payload);
+
+        let (header, claims) = AuthManager::decode_token(&token).expect("should decode");
+        assert_eq!(header["alg"], "RS256");
+        assert_eq!(claims["oid"], "obj-1");
+        assert_eq!(claims["roles"][0], "Reader");
+        assert!(
+            claims.get("at_hash").is_none(),
+            "hash claims must be stripped"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        assert!(AuthManager::decode_token("").is_err());
+        assert!(AuthManager::decode_token("only.two").is_err());
+        assert!(AuthManager::decode_token("a.b.c.d").is_err());
+        assert!(AuthManager::decode_token("!!!.???.sig").is_err());
+    }
+
     #[test]
     fn rejects_non_azure_resource_scopes() {
         let unsafe_scopes = [
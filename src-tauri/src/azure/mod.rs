@@ -3,50 +3,512 @@
 //! Design principles:
 //! - Minimal surface area: only the APIs AzVault needs are implemented.
 //! - Every outbound request is validated against an HTTPS-only host allowlist.
-//! - Retry logic with exponential backoff + Retry-After header support.
-//! - Pagination support for list endpoints (follows `nextLink`).
+//! - The ARM base URL follows the configured national cloud.
+//! - Retry logic with exponential backoff + Retry-After header support; an
+//!   optional `RetryHook` is told about each scheduled retry.
+//! - Every request carries an `x-ms-client-request-id`: the current
+//!   operation's correlation id (see `crate::correlation`), else a fresh one,
+//!   plus any static headers from `AzureClientConfig` (never `Authorization`).
+//! - A per-host circuit breaker fast-fails requests to a host that keeps
+//!   failing (5xx/429/network) until a cool-down has elapsed.
+//! - Pagination support for list endpoints (follows `nextLink`); a failed
+//!   later page yields the partial listing flagged as `truncated`.
+//! - Throttling headers (`x-ms-ratelimit-*`, `x-ms-keyvault-*`) of the
+//!   last response per host are kept for `last_rate_limit_status`.
+//! - List pages are revalidated with `If-None-Match`; a `304` reuses the
+//!   cached page and the listing is flagged `from_cache`.
 //!
 //! This client does NOT cache tokens or store any credentials.
 
+use crate::cloud::Cloud;
 use crate::models::*;
-use reqwest::{Client, Method};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Method, StatusCode};
 use serde_json::Value;
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 use url::Url;
+use zeroize::{Zeroize, Zeroizing};
 
 // ── API version constants ──
 
-const ARM_BASE: &str = "https://management.azure.com";
 const API_VERSION_TENANTS: &str = "2022-12-01";
 const API_VERSION_SUBSCRIPTIONS: &str = "2022-12-01";
 const API_VERSION_RESOURCES: &str = "2021-04-01";
+/// Default `Microsoft.KeyVault` ARM version (`AZVAULT_ARM_KV_API_VERSION`).
 const API_VERSION_KEYVAULT_MGMT: &str = "2023-07-01";
+/// Default data-plane version (`AZVAULT_KV_API_VERSION`).
 const API_VERSION_KEYVAULT_DATA: &str = "7.5";
 
+/// Oldest overrides accepted: the response shapes parsed here are stable
+/// from these versions on. Data-plane overrides must stay on major `7`.
+const MIN_KEYVAULT_DATA_MINOR: u32 = 4;
+const MIN_KEYVAULT_MGMT_DATE: &str = "2022-07-01";
+
 /// Maximum number of retries for transient failures (429/5xx).
-const MAX_RETRIES: usize = 3;
+pub const MAX_RETRIES: usize = 3;
+
+/// TCP connect timeout for outbound requests.
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Total timeout for a single outbound request.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Consecutive host failures (within `BREAKER_WINDOW`) that open the breaker.
+const BREAKER_THRESHOLD: u32 = 5;
+
+/// Window in which consecutive failures are counted.
+const BREAKER_WINDOW: Duration = Duration::from_secs(60);
+
+/// How long an open breaker fast-fails requests before allowing a probe.
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Default upper bound on pages followed by a single listing.
+pub const DEFAULT_MAX_PAGES: usize = 1000;
+
+/// Default upper bound on requests in flight across all commands.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 16;
+
+/// Maximum number of list pages kept for ETag revalidation.
+const MAX_ETAG_ENTRIES: usize = 256;
+
+/// Maximum number of hosts whose last throttling headers are kept.
+const MAX_RATE_LIMIT_HOSTS: usize = 64;
+
+/// A list page body remembered together with the ETag it was served with.
+#[derive(Clone)]
+struct CachedPage {
+    etag: String,
+    body: Value,
+}
+
+/// Failure tracking for a single host.
+#[derive(Default)]
+struct HostHealth {
+    consecutive_failures: u32,
+    window_start: Option<Instant>,
+    open_until: Option<Instant>,
+}
+
+/// Per-host circuit breaker shared by all requests of an `AzureClient`.
+#[derive(Default)]
+struct CircuitBreaker {
+    hosts: Mutex<HashMap<String, HostHealth>>,
+}
+
+impl CircuitBreaker {
+    /// Fails fast while the breaker for `host` is open.
+    fn check(&self, host: &str, now: Instant) -> Result<(), String> {
+        let Ok(hosts) = self.hosts.lock() else {
+            return Ok(());
+        };
+        match hosts.get(host).and_then(|h| h.open_until) {
+            Some(until) if now < until => Err(format!(
+                "{} is temporarily unavailable, backing off for {}s after repeated failures.",
+                host,
+                (until - now).as_secs().max(1)
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a request outcome. A success resets the host; a failure
+    /// after a probe (or the threshold within the window) opens the breaker.
+    fn record(&self, host: &str, failed: bool, now: Instant) {
+        let Ok(mut hosts) = self.hosts.lock() else {
+            return;
+        };
+        if !failed {
+            hosts.remove(host);
+            return;
+        }
+
+        let health = hosts.entry(host.to_string()).or_default();
+        let probing = health.open_until.is_some();
+        let window_expired = match health.window_start {
+            Some(start) => now.duration_since(start) > BREAKER_WINDOW,
+            None => true,
+        };
+        if window_expired && !probing {
+            health.consecutive_failures = 0;
+            health.window_start = Some(now);
+        }
+        health.consecutive_failures += 1;
+        if probing || health.consecutive_failures >= BREAKER_THRESHOLD {
+            health.open_until = Some(now + BREAKER_COOLDOWN);
+        }
+    }
+}
+
+/// A failed send; `host_failure` marks failures that count toward the
+/// circuit breaker (network errors, 429, and 5xx).
+struct SendError {
+    message: String,
+    host_failure: bool,
+}
+
+/// Status, ETag, and parsed body of a response accepted by `send`.
+struct SentResponse {
+    status: StatusCode,
+    etag: Option<String>,
+    body: Value,
+}
+
+/// Header used to correlate a request with Azure's service-side logs.
+const CLIENT_REQUEST_ID: &str = "x-ms-client-request-id";
+
+/// Outbound settings for `AzureClient`.
+#[derive(Debug, Clone)]
+pub struct AzureClientConfig {
+    pub cloud: Cloud,
+    /// Static headers applied to every outbound request.
+    pub headers: HeaderMap,
+    /// Pages followed per listing before it is returned as truncated.
+    pub max_pages: usize,
+    /// Requests in flight at once; further requests queue for a permit.
+    pub max_concurrent_requests: usize,
+    /// Key Vault data-plane `api-version`.
+    pub keyvault_data_api_version: String,
+    /// `Microsoft.KeyVault` ARM `api-version`.
+    pub keyvault_mgmt_api_version: String,
+}
+
+impl AzureClientConfig {
+    /// Creates a configuration for `cloud` without extra headers, with
+    /// `DEFAULT_MAX_PAGES`, `DEFAULT_MAX_CONCURRENT_REQUESTS`, and the pinned
+    /// Key Vault API versions.
+    pub fn new(cloud: Cloud) -> Self {
+        Self {
+            cloud,
+            headers: HeaderMap::new(),
+            max_pages: DEFAULT_MAX_PAGES,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            keyvault_data_api_version: API_VERSION_KEYVAULT_DATA.to_string(),
+            keyvault_mgmt_api_version: API_VERSION_KEYVAULT_MGMT.to_string(),
+        }
+    }
+
+    /// Reads static headers from `AZVAULT_EXTRA_HEADERS`
+    /// (`Name: value` pairs separated by `;`), the page limit from
+    /// `AZVAULT_MAX_PAGES`, the concurrency limit from
+    /// `AZVAULT_MAX_CONCURRENCY`, and Key Vault API versions from
+    /// `AZVAULT_KV_API_VERSION` (data plane) and `AZVAULT_ARM_KV_API_VERSION`
+    /// (ARM). Invalid entries are skipped with a warning.
+    pub fn from_env(cloud: Cloud) -> Self {
+        let mut config = Self::new(cloud);
+        if let Ok(raw) = std::env::var("AZVAULT_MAX_PAGES") {
+            match raw.trim().parse::<usize>() {
+                Ok(pages) if pages > 0 => config.max_pages = pages,
+                _ => log::warn!("Ignoring invalid AZVAULT_MAX_PAGES='{}'", raw),
+            }
+        }
+        if let Ok(raw) = std::env::var("AZVAULT_MAX_CONCURRENCY") {
+            match raw.trim().parse::<usize>() {
+                Ok(limit) if limit > 0 => config.max_concurrent_requests = limit,
+                _ => log::warn!("Ignoring invalid AZVAULT_MAX_CONCURRENCY='{}'", raw),
+            }
+        }
+        if let Ok(raw) = std::env::var("AZVAULT_KV_API_VERSION") {
+            match check_data_api_version(raw.trim()) {
+                Ok(()) => config.keyvault_data_api_version = raw.trim().to_string(),
+                Err(e) => log::warn!("Ignoring AZVAULT_KV_API_VERSION: {}", e),
+            }
+        }
+        if let Ok(raw) = std::env::var("AZVAULT_ARM_KV_API_VERSION") {
+            match check_mgmt_api_version(raw.trim()) {
+                Ok(()) => config.keyvault_mgmt_api_version = raw.trim().to_string(),
+                Err(e) => log::warn!("Ignoring AZVAULT_ARM_KV_API_VERSION: {}", e),
+            }
+        }
+        if let Ok(raw) = std::env::var("AZVAULT_EXTRA_HEADERS") {
+            for entry in raw.split(';').filter(|e| !e.trim().is_empty()) {
+                let added = entry
+                    .split_once(':')
+                    .ok_or_else(|| "expected 'Name: value'".to_string())
+                    .and_then(|(name, value)| config.add_header(name.trim(), value.trim()));
+                if let Err(e) = added {
+                    log::warn!("Ignoring AZVAULT_EXTRA_HEADERS entry: {}", e);
+                }
+            }
+        }
+        config
+    }
+
+    /// Adds a static header. `Authorization` and the generated
+    /// `x-ms-client-request-id` cannot be overridden.
+    pub fn add_header(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| format!("invalid header name '{}'", name))?;
+        if name == reqwest::header::AUTHORIZATION || name.as_str() == CLIENT_REQUEST_ID {
+            return Err(format!("header '{}' cannot be overridden", name));
+        }
+        let value = HeaderValue::from_str(value)
+            .map_err(|_| format!("invalid value for header '{}'", name))?;
+        self.headers.insert(name, value);
+        Ok(())
+    }
+}
+
+/// Accepts `7.N` or `7.N-preview` with `N >= MIN_KEYVAULT_DATA_MINOR`.
+fn check_data_api_version(version: &str) -> Result<(), String> {
+    let base = version.strip_suffix("-preview").unwrap_or(version);
+    let minor = base
+        .strip_prefix("7.")
+        .and_then(|minor| minor.parse::<u32>().ok())
+        .ok_or_else(|| format!("'{}' is not a 7.x data-plane version", version))?;
+    if minor < MIN_KEYVAULT_DATA_MINOR {
+        return Err(format!(
+            "'{}' is older than 7.{}",
+            version, MIN_KEYVAULT_DATA_MINOR
+        ));
+    }
+    Ok(())
+}
+
+/// Accepts `YYYY-MM-DD` or `YYYY-MM-DD-preview` no older than
+/// `MIN_KEYVAULT_MGMT_DATE`.
+fn check_mgmt_api_version(version: &str) -> Result<(), String> {
+    let date = version.strip_suffix("-preview").unwrap_or(version);
+    if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+        return Err(format!("'{}' is not a dated ARM version", version));
+    }
+    // ISO dates compare correctly as strings.
+    if date < MIN_KEYVAULT_MGMT_DATE {
+        return Err(format!(
+            "'{}' is older than {}",
+            version, MIN_KEYVAULT_MGMT_DATE
+        ));
+    }
+    Ok(())
+}
+
+/// Called with a `RetryNotice` each time a retry is scheduled, before the
+/// backoff sleep.
+pub type RetryHook = Box<dyn Fn(&RetryNotice) + Send + Sync>;
 
 /// HTTP client wrapper for Azure REST APIs.
 pub struct AzureClient {
     client: Client,
+    /// National cloud whose ARM endpoint is targeted.
+    cloud: Cloud,
+    /// Static headers from `AzureClientConfig`.
+    headers: HeaderMap,
+    /// Page limit per listing (see `AzureClientConfig::max_pages`).
+    max_pages: usize,
+    /// List page URL → last ETag and body, for conditional GETs.
+    etag_cache: Mutex<HashMap<String, CachedPage>>,
+    breaker: CircuitBreaker,
+    /// Shared across all commands so concurrent bulk operations cannot
+    /// exhaust the connection pool (see `max_concurrent_requests`).
+    limiter: Semaphore,
+    max_concurrent_requests: usize,
+    /// `api-version` values (see `AzureClientConfig`).
+    keyvault_data_api_version: String,
+    keyvault_mgmt_api_version: String,
+    retry_hook: Option<RetryHook>,
+    /// Host → throttling headers of its most recent response.
+    rate_limits: Mutex<HashMap<String, RateLimitStatus>>,
 }
 
 impl AzureClient {
-    /// Creates a new client with conservative timeouts (10s connect, 30s total).
-    pub fn new() -> Self {
+    /// Creates a new client from `config` with conservative timeouts
+    /// (`CONNECT_TIMEOUT`, `REQUEST_TIMEOUT`).
+    pub fn new(config: AzureClientConfig) -> Self {
         let client = Client::builder()
-            .connect_timeout(Duration::from_secs(10))
-            .timeout(Duration::from_secs(30))
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
             .build()
             .unwrap_or_else(|_| Client::new());
-        Self { client }
+        Self {
+            client,
+            cloud: config.cloud,
+            headers: config.headers,
+            max_pages: config.max_pages,
+            etag_cache: Mutex::new(HashMap::new()),
+            breaker: CircuitBreaker::default(),
+            limiter: Semaphore::new(config.max_concurrent_requests),
+            max_concurrent_requests: config.max_concurrent_requests,
+            keyvault_data_api_version: config.keyvault_data_api_version,
+            keyvault_mgmt_api_version: config.keyvault_mgmt_api_version,
+            retry_hook: None,
+            rate_limits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the throttling headers last seen from each host, by host.
+    pub fn rate_limit_status(&self) -> Vec<RateLimitStatus> {
+        let mut statuses: Vec<RateLimitStatus> = self
+            .rate_limits
+            .lock()
+            .map(|limits| limits.values().cloned().collect())
+            .unwrap_or_default();
+        statuses.sort_by(|a, b| a.host.cmp(&b.host));
+        statuses
+    }
+
+    /// Records the throttling headers of a response from `url`'s host,
+    /// resetting the map when it is full. Responses without any are ignored.
+    fn remember_rate_limit(&self, url: &str, status: StatusCode, headers: &HeaderMap) {
+        let headers = Self::rate_limit_headers(headers);
+        if headers.is_empty() {
+            return;
+        }
+        let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+        else {
+            return;
+        };
+        let Ok(mut limits) = self.rate_limits.lock() else {
+            return;
+        };
+        if limits.len() >= MAX_RATE_LIMIT_HOSTS && !limits.contains_key(&host) {
+            limits.clear();
+        }
+        limits.insert(
+            host.clone(),
+            RateLimitStatus {
+                host,
+                observed_at: chrono::Utc::now()
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                status: status.as_u16(),
+                headers,
+            },
+        );
+    }
+
+    /// Picks the throttling-related headers (non-sensitive) from a response.
+    fn rate_limit_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+        headers
+            .iter()
+            .filter(|(name, _)| {
+                let name = name.as_str();
+                name.starts_with("x-ms-ratelimit-")
+                    || name.starts_with("x-ms-keyvault-")
+                    || name == "retry-after"
+            })
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.as_str().to_string(), value.to_string()))
+            })
+            .collect()
+    }
+
+    /// Reports every scheduled retry to `hook` (e.g. to tell the UI that
+    /// Azure is rate-limiting). Retry behaviour itself is unchanged.
+    pub fn with_retry_hook(mut self, hook: RetryHook) -> Self {
+        self.retry_hook = Some(hook);
+        self
+    }
+
+    /// Passes a scheduled retry to the hook, if one is set.
+    fn notify_retry(&self, attempt: usize, delay_secs: u64, reason: String) {
+        if let Some(hook) = &self.retry_hook {
+            hook(&RetryNotice {
+                attempt,
+                delay_secs,
+                reason,
+            });
+        }
+    }
+
+    /// Hosts (exact) and host suffixes (leading dot) accepted by the
+    /// outbound allowlist.
+    pub fn allowed_hosts(&self) -> Vec<String> {
+        std::iter::once(self.cloud.arm_host().to_string())
+            .chain(Cloud::ALL.iter().map(|c| c.vault_suffix().to_string()))
+            .collect()
+    }
+
+    /// Names of the static headers added to every request (values omitted).
+    pub fn static_header_names(&self) -> Vec<String> {
+        self.headers
+            .keys()
+            .map(|k| k.as_str().to_string())
+            .collect()
+    }
+
+    /// Returns the national cloud this client targets.
+    pub fn cloud(&self) -> Cloud {
+        self.cloud
+    }
+
+    /// Returns the page limit applied to each listing.
+    pub fn max_pages(&self) -> usize {
+        self.max_pages
+    }
+
+    /// Returns the limit on requests in flight at once.
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
+    }
+
+    /// Returns the Key Vault data-plane and ARM `api-version` values in use.
+    pub fn keyvault_api_versions(&self) -> (&str, &str) {
+        (
+            &self.keyvault_data_api_version,
+            &self.keyvault_mgmt_api_version,
+        )
+    }
+
+    // ── Authority discovery ──
+
+    /// Fetches a tenant's OpenID configuration from `cloud`'s default
+    /// authority (unauthenticated). `Ok(None)` means that authority does not
+    /// know the tenant. Only the fixed authority hosts of `Cloud` are
+    /// contacted; `tenant` must already be validated.
+    pub async fn get_openid_configuration(
+        &self,
+        cloud: Cloud,
+        tenant: &str,
+    ) -> Result<Option<Value>, String> {
+        let url = format!(
+            "{}/{}/v2.0/.well-known/openid-configuration",
+            cloud.authority_host(),
+            tenant
+        );
+        let _permit = self.limiter.acquire().await.ok();
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.headers.clone())
+            .send()
+            .await
+            .map_err(|e| format!("{} is unreachable: {}", cloud.authority_host(), e))?;
+        let status = response.status();
+        if status.is_success() {
+            return response
+                .json::<Value>()
+                .await
+                .map(Some)
+                .map_err(|e| format!("Invalid OpenID configuration: {}", e));
+        }
+        if status == StatusCode::BAD_REQUEST || status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Err(format!(
+            "[{}] {} returned an unexpected status",
+            status.as_u16(),
+            cloud.authority_host()
+        ))
     }
 
     // ── ARM discovery endpoints ──
 
     /// Lists all Azure AD tenants accessible to the authenticated identity.
     pub async fn list_tenants(&self, token: &str) -> Result<Vec<Tenant>, String> {
-        let url = format!("{}/tenants?api-version={}", ARM_BASE, API_VERSION_TENANTS);
+        let url = format!(
+            "{}/tenants?api-version={}",
+            self.cloud.arm_base(),
+            API_VERSION_TENANTS
+        );
         let body = self.request_json(Method::GET, &url, token, None).await?;
 
         let tenants = body["value"]
@@ -76,7 +538,8 @@ impl AzureClient {
     pub async fn list_subscriptions(&self, token: &str) -> Result<Vec<Subscription>, String> {
         let url = format!(
             "{}/subscriptions?api-version={}",
-            ARM_BASE, API_VERSION_SUBSCRIPTIONS
+            self.cloud.arm_base(),
+            API_VERSION_SUBSCRIPTIONS
         );
         let body = self.request_json(Method::GET, &url, token, None).await?;
 
@@ -108,15 +571,69 @@ impl AzureClient {
         token: &str,
         subscription_id: &str,
     ) -> Result<Vec<KeyVaultInfo>, String> {
+        self.query_keyvaults(token, subscription_id, None, None)
+            .await
+    }
+
+    /// Like `list_keyvaults`, but vaults in `home_region` are returned (and
+    /// their soft-delete state fetched) first.
+    pub async fn list_keyvaults_home_first(
+        &self,
+        token: &str,
+        subscription_id: &str,
+        home_region: &str,
+    ) -> Result<Vec<KeyVaultInfo>, String> {
+        self.query_keyvaults(token, subscription_id, None, Some(home_region))
+            .await
+    }
+
+    /// Finds the vault named `vault_name` within a subscription, if any.
+    pub async fn find_keyvault(
+        &self,
+        token: &str,
+        subscription_id: &str,
+        vault_name: &str,
+    ) -> Result<Option<KeyVaultInfo>, String> {
+        let vaults = self
+            .query_keyvaults(token, subscription_id, Some(vault_name), None)
+            .await?;
+        Ok(vaults.into_iter().next())
+    }
+
+    /// Runs the vault resource query, optionally narrowed to a single name
+    /// and ordered so vaults in `home_region` are processed first.
+    async fn query_keyvaults(
+        &self,
+        token: &str,
+        subscription_id: &str,
+        name: Option<&str>,
+        home_region: Option<&str>,
+    ) -> Result<Vec<KeyVaultInfo>, String> {
+        let name_filter = name
+            .map(|n| format!(" and name eq '{}'", n))
+            .unwrap_or_default();
         let url = format!(
-            "{}/subscriptions/{}/resources?$filter=resourceType eq 'Microsoft.KeyVault/vaults'&api-version={}",
-            ARM_BASE, subscription_id, API_VERSION_RESOURCES
+            "{}/subscriptions/{}/resources?$filter=resourceType eq 'Microsoft.KeyVault/vaults'{}&api-version={}",
+            self.cloud.arm_base(),
+            subscription_id,
+            name_filter,
+            API_VERSION_RESOURCES
         );
 
         let body = self.request_json(Method::GET, &url, token, None).await?;
 
+        let mut resources = body["value"].as_array().cloned().unwrap_or_default();
+        if let Some(home) = home_region {
+            // Stable sort: home-region vaults first, original order otherwise
+            resources.sort_by_key(|v| {
+                !v["location"]
+                    .as_str()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(home))
+            });
+        }
+
         let mut vaults: Vec<KeyVaultInfo> = Vec::new();
-        for v in body["value"].as_array().cloned().unwrap_or_default() {
+        for v in resources {
             let id = v["id"].as_str().unwrap_or_default();
             let name = v["name"].as_str().unwrap_or_default();
             let location = v["location"].as_str().unwrap_or_default();
@@ -128,27 +645,89 @@ impl AzureClient {
                 .and_then(|s| s.split('/').next())
                 .unwrap_or_default();
 
-            let soft_delete_enabled = self
-                .get_vault_soft_delete_state(token, id)
+            let properties = self
+                .get_vault_properties(token, id)
                 .await
-                .unwrap_or(None);
+                .unwrap_or_default();
 
             vaults.push(KeyVaultInfo {
                 id: id.to_string(),
                 name: name.to_string(),
                 location: location.to_string(),
                 resource_group: rg.to_string(),
-                vault_uri: format!("https://{}.vault.azure.net", name),
+                vault_uri: format!("https://{}{}", name, self.cloud.vault_suffix()),
                 tags: v
                     .get("tags")
                     .and_then(|t| serde_json::from_value(t.clone()).ok()),
-                soft_delete_enabled,
+                soft_delete_enabled: properties.soft_delete_enabled,
+                public_network_access: properties.public_network_access,
+                network_default_deny: properties.network_default_deny,
             });
         }
 
         Ok(vaults)
     }
 
+    /// Lists soft-deleted vaults in a subscription (paginated).
+    pub async fn list_deleted_vaults(
+        &self,
+        token: &str,
+        subscription_id: &str,
+    ) -> Result<ListResult<DeletedVaultInfo>, String> {
+        let url = format!(
+            "{}/subscriptions/{}/providers/Microsoft.KeyVault/deletedVaults?api-version={}",
+            self.cloud.arm_base(),
+            subscription_id,
+            self.keyvault_mgmt_api_version
+        );
+        self.list_paged(token, url, Self::parse_deleted_vault).await
+    }
+
+    /// Recovers a soft-deleted vault into its original resource group by
+    /// re-creating it with `createMode: recover` (as `az keyvault recover`
+    /// does). `tenant_id` is the tenant the vault belongs to.
+    pub async fn recover_vault(
+        &self,
+        token: &str,
+        subscription_id: &str,
+        location: &str,
+        name: &str,
+        tenant_id: &str,
+    ) -> Result<KeyVaultInfo, String> {
+        let url = format!(
+            "{}/subscriptions/{}/providers/Microsoft.KeyVault/locations/{}/deletedVaults/{}?api-version={}",
+            self.cloud.arm_base(),
+            subscription_id,
+            location,
+            name,
+            self.keyvault_mgmt_api_version
+        );
+        let deleted =
+            Self::parse_deleted_vault(&self.request_json(Method::GET, &url, token, None).await?);
+        if deleted.resource_group.is_empty() {
+            return Err("Azure did not report the deleted vault's resource group.".to_string());
+        }
+
+        let url = format!(
+            "{}{}?api-version={}",
+            self.cloud.arm_base(),
+            deleted.vault_id,
+            self.keyvault_mgmt_api_version
+        );
+        let payload = serde_json::json!({
+            "location": deleted.location,
+            "properties": {
+                "tenantId": tenant_id,
+                "sku": { "family": "A", "name": "standard" },
+                "createMode": "recover"
+            }
+        });
+        let body = self
+            .request_json(Method::PUT, &url, token, Some(payload))
+            .await?;
+        Ok(self.parse_vault_resource(&body, &deleted))
+    }
+
     // ── Key Vault data-plane: Secrets ──
 
     /// Lists all secrets in a vault (follows pagination via `nextLink`).
@@ -156,31 +735,12 @@ impl AzureClient {
         &self,
         token: &str,
         vault_uri: &str,
-    ) -> Result<Vec<SecretItem>, String> {
+    ) -> Result<ListResult<SecretItem>, String> {
         let url = format!(
             "{}/secrets?api-version={}",
-            vault_uri, API_VERSION_KEYVAULT_DATA
+            vault_uri, self.keyvault_data_api_version
         );
-
-        let mut next_url = Some(url);
-        let mut items = Vec::new();
-
-        while let Some(current_url) = next_url {
-            let body = self
-                .request_json(Method::GET, &current_url, token, None)
-                .await?;
-            if let Some(values) = body["value"].as_array() {
-                for value in values {
-                    items.push(Self::parse_secret_item(value));
-                }
-            }
-            next_url = body
-                .get("nextLink")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-        }
-
-        Ok(items)
+        self.list_paged(token, url, Self::parse_secret_item).await
     }
 
     /// Fetches the latest version's metadata for a specific secret.
@@ -192,7 +752,7 @@ impl AzureClient {
     ) -> Result<SecretItem, String> {
         let url = format!(
             "{}/secrets/{}/versions?api-version={}&maxresults=1",
-            vault_uri, name, API_VERSION_KEYVAULT_DATA
+            vault_uri, name, self.keyvault_data_api_version
         );
 
         let body = self.request_json(Method::GET, &url, token, None).await?;
@@ -204,6 +764,31 @@ impl AzureClient {
         maybe_item.ok_or_else(|| format!("Secret metadata not found for '{}'", name))
     }
 
+    /// Fetches the current version's metadata from the un-versioned secret
+    /// bundle. The versions listing is not ordered newest-first, so writes
+    /// that carry existing metadata forward read it here; the value in the
+    /// response is zeroized and discarded.
+    pub async fn get_current_secret_metadata(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+    ) -> Result<SecretItem, String> {
+        let url = format!(
+            "{}/secrets/{}?api-version={}",
+            vault_uri, name, self.keyvault_data_api_version
+        );
+
+        let mut body = self.request_json(Method::GET, &url, token, None).await?;
+        if let Some(Value::String(mut value)) = body
+            .as_object_mut()
+            .and_then(|bundle| bundle.remove("value"))
+        {
+            value.zeroize();
+        }
+        Ok(Self::parse_secret_item(&body))
+    }
+
     /// Fetches the actual secret value (sensitive – should be audited).
     pub async fn get_secret_value(
         &self,
@@ -213,15 +798,75 @@ impl AzureClient {
     ) -> Result<SecretValue, String> {
         let url = format!(
             "{}/secrets/{}?api-version={}",
-            vault_uri, name, API_VERSION_KEYVAULT_DATA
+            vault_uri, name, self.keyvault_data_api_version
         );
 
         let body = self.request_json(Method::GET, &url, token, None).await?;
+        Ok(Self::parse_secret_value(&body, name))
+    }
 
-        Ok(SecretValue {
-            value: body["value"].as_str().unwrap_or_default().to_string(),
+    /// Fetches the value of one specific secret version (sensitive – should
+    /// be audited).
+    pub async fn get_secret_version_value(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<SecretValue, String> {
+        let url = format!(
+            "{}/secrets/{}/{}?api-version={}",
+            vault_uri, name, version, self.keyvault_data_api_version
+        );
+
+        let body = self.request_json(Method::GET, &url, token, None).await?;
+        Ok(Self::parse_secret_value(&body, name))
+    }
+
+    /// Splits a secret id (`https://{vault}/secrets/{name}[/{version}]`)
+    /// into vault URI, name, and version. The id must pass the outbound
+    /// allowlist; the parts are not otherwise validated.
+    pub fn parse_secret_id(&self, id: &str) -> Result<(String, String, Option<String>), String> {
+        self.check_url(id)?;
+        let parsed = Url::parse(id).map_err(|_| "Not a valid URL.".to_string())?;
+        let segments: Vec<&str> = parsed.path().trim_matches('/').split('/').collect();
+        if !(2..=3).contains(&segments.len()) || segments[0] != "secrets" {
+            return Err(
+                "Not a secret identifier (expected .../secrets/{name}/{version}).".to_string(),
+            );
+        }
+        let host = parsed.host_str().unwrap_or_default();
+        let name = Self::extract_name_from_id(parsed.path(), "secrets");
+        let version = segments.get(2).map(|v| v.to_string());
+        Ok((format!("https://{}", host), name, version))
+    }
+
+    /// Fetches the managed secret backing certificate `name` (sensitive –
+    /// contains the private key). Fails for ordinary secrets.
+    pub async fn get_certificate_secret(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+    ) -> Result<CertificateSecret, String> {
+        let url = format!(
+            "{}/secrets/{}?api-version={}",
+            vault_uri, name, self.keyvault_data_api_version
+        );
+
+        let body = self.request_json(Method::GET, &url, token, None).await?;
+        if body["managed"].as_bool() != Some(true) {
+            return Err(format!(
+                "'{}' is not the backing secret of a certificate.",
+                name
+            ));
+        }
+
+        Ok(CertificateSecret {
             id: body["id"].as_str().unwrap_or_default().to_string(),
             name: name.to_string(),
+            content_type: body["contentType"].as_str().map(|s| s.to_string()),
+            value: body["value"].as_str().unwrap_or_default().to_string(),
         })
     }
 
@@ -234,7 +879,7 @@ impl AzureClient {
     ) -> Result<SecretItem, String> {
         let url = format!(
             "{}/secrets/{}?api-version={}",
-            vault_uri, req.name, API_VERSION_KEYVAULT_DATA
+            vault_uri, req.name, self.keyvault_data_api_version
         );
 
         let mut payload = serde_json::json!({
@@ -251,14 +896,12 @@ impl AzureClient {
             payload["tags"] = serde_json::json!(tags);
         }
         if let Some(exp) = &req.expires {
-            if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(exp) {
-                payload["attributes"]["exp"] = serde_json::json!(dt.timestamp());
-            }
+            payload["attributes"]["exp"] =
+                serde_json::json!(Self::rfc3339_to_epoch("expires", exp)?);
         }
         if let Some(nbf) = &req.not_before {
-            if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(nbf) {
-                payload["attributes"]["nbf"] = serde_json::json!(dt.timestamp());
-            }
+            payload["attributes"]["nbf"] =
+                serde_json::json!(Self::rfc3339_to_epoch("notBefore", nbf)?);
         }
 
         let body = self
@@ -268,6 +911,26 @@ impl AzureClient {
         Ok(Self::parse_secret_item(&body))
     }
 
+    /// Updates attributes/tags/content type of a secret's latest version
+    /// without creating a new version (PATCH; an empty version segment
+    /// targets the current version).
+    pub async fn update_secret(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+        patch: Value,
+    ) -> Result<SecretItem, String> {
+        let url = format!(
+            "{}/secrets/{}/?api-version={}",
+            vault_uri, name, self.keyvault_data_api_version
+        );
+        let body = self
+            .request_json(Method::PATCH, &url, token, Some(patch))
+            .await?;
+        Ok(Self::parse_secret_item(&body))
+    }
+
     /// Soft-deletes a secret (recoverable if soft-delete is enabled).
     pub async fn delete_secret(
         &self,
@@ -277,7 +940,7 @@ impl AzureClient {
     ) -> Result<(), String> {
         let url = format!(
             "{}/secrets/{}?api-version={}",
-            vault_uri, name, API_VERSION_KEYVAULT_DATA
+            vault_uri, name, self.keyvault_data_api_version
         );
         self.request_json(Method::DELETE, &url, token, None).await?;
         Ok(())
@@ -292,12 +955,42 @@ impl AzureClient {
     ) -> Result<(), String> {
         let url = format!(
             "{}/deletedsecrets/{}/recover?api-version={}",
-            vault_uri, name, API_VERSION_KEYVAULT_DATA
+            vault_uri, name, self.keyvault_data_api_version
         );
         self.request_json(Method::POST, &url, token, None).await?;
         Ok(())
     }
 
+    /// Fetches a soft-deleted secret's deletion dates and original
+    /// attributes (metadata only; the value is not returned).
+    pub async fn get_deleted_secret(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+    ) -> Result<DeletedSecretItem, String> {
+        let url = format!(
+            "{}/deletedsecrets/{}?api-version={}",
+            vault_uri, name, self.keyvault_data_api_version
+        );
+        let body = self.request_json(Method::GET, &url, token, None).await?;
+        Ok(Self::parse_deleted_secret_item(&body))
+    }
+
+    /// Lists soft-deleted secrets in a vault (paginated).
+    pub async fn list_deleted_secrets(
+        &self,
+        token: &str,
+        vault_uri: &str,
+    ) -> Result<ListResult<DeletedSecretItem>, String> {
+        let url = format!(
+            "{}/deletedsecrets?api-version={}",
+            vault_uri, self.keyvault_data_api_version
+        );
+        self.list_paged(token, url, Self::parse_deleted_secret_item)
+            .await
+    }
+
     /// Permanently purges a deleted secret (irreversible).
     pub async fn purge_secret(
         &self,
@@ -307,7 +1000,7 @@ impl AzureClient {
     ) -> Result<(), String> {
         let url = format!(
             "{}/deletedsecrets/{}?api-version={}",
-            vault_uri, name, API_VERSION_KEYVAULT_DATA
+            vault_uri, name, self.keyvault_data_api_version
         );
         self.request_json(Method::DELETE, &url, token, None).await?;
         Ok(())
@@ -316,61 +1009,152 @@ impl AzureClient {
     // ── Key Vault data-plane: Keys ──
 
     /// Lists all cryptographic keys in a vault (paginated).
-    pub async fn list_keys(&self, token: &str, vault_uri: &str) -> Result<Vec<KeyItem>, String> {
+    pub async fn list_keys(
+        &self,
+        token: &str,
+        vault_uri: &str,
+    ) -> Result<ListResult<KeyItem>, String> {
         let url = format!(
             "{}/keys?api-version={}",
-            vault_uri, API_VERSION_KEYVAULT_DATA
+            vault_uri, self.keyvault_data_api_version
         );
+        self.list_paged(token, url, Self::parse_key_item).await
+    }
 
-        let mut items = Vec::new();
-        let mut next_url = Some(url);
-
-        while let Some(current_url) = next_url {
-            let body = self
-                .request_json(Method::GET, &current_url, token, None)
-                .await?;
-
-            if let Some(values) = body["value"].as_array() {
-                for v in values {
-                    let id = v["kid"].as_str().unwrap_or_default().to_string();
-                    let name = Self::extract_name_from_id(&id, "keys");
-                    let attrs = &v["attributes"];
-
-                    items.push(KeyItem {
-                        id,
-                        name,
-                        enabled: attrs["enabled"].as_bool().unwrap_or(true),
-                        created: Self::epoch_to_rfc3339(
-                            attrs.get("created").and_then(|v| v.as_u64()),
-                        ),
-                        updated: Self::epoch_to_rfc3339(
-                            attrs.get("updated").and_then(|v| v.as_u64()),
-                        ),
-                        expires: Self::epoch_to_rfc3339(attrs.get("exp").and_then(|v| v.as_u64())),
-                        not_before: Self::epoch_to_rfc3339(
-                            attrs.get("nbf").and_then(|v| v.as_u64()),
-                        ),
-                        key_type: v.get("kty").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                        key_ops: v.get("key_ops").and_then(|v| v.as_array()).map(|arr| {
-                            arr.iter()
-                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                .collect()
-                        }),
-                        tags: v
-                            .get("tags")
-                            .and_then(|t| serde_json::from_value(t.clone()).ok()),
-                        managed: v.get("managed").and_then(|v| v.as_bool()),
-                    });
-                }
-            }
+    /// Fetches a key's latest version, including its public JWK material.
+    pub async fn get_key(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+    ) -> Result<KeyDetails, String> {
+        let url = format!(
+            "{}/keys/{}?api-version={}",
+            vault_uri, name, self.keyvault_data_api_version
+        );
+        let body = self.request_json(Method::GET, &url, token, None).await?;
+        Ok(Self::parse_key_details(&body))
+    }
 
-            next_url = body
-                .get("nextLink")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-        }
+    /// Fetches a key's automated rotation policy.
+    pub async fn get_key_rotation_policy(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+    ) -> Result<KeyRotationPolicy, String> {
+        let url = format!(
+            "{}/keys/{}/rotationpolicy?api-version={}",
+            vault_uri, name, self.keyvault_data_api_version
+        );
+        let body = self.request_json(Method::GET, &url, token, None).await?;
+        Ok(Self::parse_rotation_policy(&body))
+    }
+
+    /// Replaces a key's automated rotation policy.
+    pub async fn set_key_rotation_policy(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+        policy: &KeyRotationPolicy,
+    ) -> Result<KeyRotationPolicy, String> {
+        let url = format!(
+            "{}/keys/{}/rotationpolicy?api-version={}",
+            vault_uri, name, self.keyvault_data_api_version
+        );
+        let body = self
+            .request_json(
+                Method::PUT,
+                &url,
+                token,
+                Some(Self::rotation_policy_payload(policy)),
+            )
+            .await?;
+        Ok(Self::parse_rotation_policy(&body))
+    }
+
+    /// Encrypts `plaintext` with one key version using `algorithm`
+    /// (e.g. `RSA-OAEP-256`), returning the base64url ciphertext.
+    pub async fn encrypt(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+        version: &str,
+        algorithm: &str,
+        plaintext: &[u8],
+    ) -> Result<String, String> {
+        let key_id = format!("{}/keys/{}/{}", vault_uri, name, version);
+        let value = URL_SAFE_NO_PAD.encode(plaintext);
+        self.key_operation(token, &key_id, "encrypt", algorithm, value)
+            .await
+    }
+
+    /// Decrypts a base64url `ciphertext` with one key version. The
+    /// plaintext is zeroized when dropped.
+    pub async fn decrypt(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+        version: &str,
+        algorithm: &str,
+        ciphertext: &str,
+    ) -> Result<Zeroizing<Vec<u8>>, String> {
+        let key_id = format!("{}/keys/{}/{}", vault_uri, name, version);
+        let value = Zeroizing::new(
+            self.key_operation(token, &key_id, "decrypt", algorithm, ciphertext.to_string())
+                .await?,
+        );
+        URL_SAFE_NO_PAD
+            .decode(value.as_bytes())
+            .map(Zeroizing::new)
+            .map_err(|_| "Key Vault returned an invalid decrypt result.".to_string())
+    }
 
-        Ok(items)
+    /// POSTs a base64url `value` to `{key_id}/{operation}` (`key_id` being
+    /// a versioned key URL) and returns the base64url result.
+    async fn key_operation(
+        &self,
+        token: &str,
+        key_id: &str,
+        operation: &str,
+        algorithm: &str,
+        value: String,
+    ) -> Result<String, String> {
+        let url = format!(
+            "{}/{}?api-version={}",
+            key_id, operation, self.keyvault_data_api_version
+        );
+        let payload = serde_json::json!({ "alg": algorithm, "value": value });
+        let body = self
+            .request_json(Method::POST, &url, token, Some(payload))
+            .await?;
+        Self::parse_key_operation_result(&body, operation)
+    }
+
+    /// Extracts the `value` of a key operation response.
+    fn parse_key_operation_result(body: &Value, operation: &str) -> Result<String, String> {
+        body["value"]
+            .as_str()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+            .ok_or_else(|| format!("Key Vault returned no {} result.", operation))
+    }
+
+    /// Lists soft-deleted keys in a vault (paginated).
+    pub async fn list_deleted_keys(
+        &self,
+        token: &str,
+        vault_uri: &str,
+    ) -> Result<ListResult<DeletedKeyItem>, String> {
+        let url = format!(
+            "{}/deletedkeys?api-version={}",
+            vault_uri, self.keyvault_data_api_version
+        );
+        self.list_paged(token, url, Self::parse_deleted_key_item)
+            .await
     }
 
     // ── Key Vault data-plane: Certificates ──
@@ -380,80 +1164,299 @@ impl AzureClient {
         &self,
         token: &str,
         vault_uri: &str,
-    ) -> Result<Vec<CertificateItem>, String> {
+    ) -> Result<ListResult<CertificateItem>, String> {
         let url = format!(
             "{}/certificates?api-version={}",
-            vault_uri, API_VERSION_KEYVAULT_DATA
+            vault_uri, self.keyvault_data_api_version
         );
+        self.list_paged(token, url, Self::parse_certificate_item)
+            .await
+    }
 
-        let mut items = Vec::new();
-        let mut next_url = Some(url);
+    /// Imports a PKCS#12 (base64) or PEM certificate, including its private
+    /// key, as a new certificate version (sensitive – should be audited).
+    pub async fn import_certificate(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        name: &str,
+        pfx_base64: &str,
+        password: Option<String>,
+    ) -> Result<CertificateItem, String> {
+        let url = format!(
+            "{}/certificates/{}/import?api-version={}",
+            vault_uri, name, self.keyvault_data_api_version
+        );
 
-        while let Some(current_url) = next_url {
-            let body = self
-                .request_json(Method::GET, &current_url, token, None)
-                .await?;
+        let content_type = if pfx_base64.trim_start().starts_with("-----BEGIN") {
+            "application/x-pem-file"
+        } else {
+            "application/x-pkcs12"
+        };
 
-            if let Some(values) = body["value"].as_array() {
-                for v in values {
-                    let id = v["id"].as_str().unwrap_or_default().to_string();
-                    let name = Self::extract_name_from_id(&id, "certificates");
-                    let attrs = &v["attributes"];
-
-                    items.push(CertificateItem {
-                        id,
-                        name,
-                        enabled: attrs["enabled"].as_bool().unwrap_or(true),
-                        created: Self::epoch_to_rfc3339(
-                            attrs.get("created").and_then(|v| v.as_u64()),
-                        ),
-                        updated: Self::epoch_to_rfc3339(
-                            attrs.get("updated").and_then(|v| v.as_u64()),
-                        ),
-                        expires: Self::epoch_to_rfc3339(attrs.get("exp").and_then(|v| v.as_u64())),
-                        not_before: Self::epoch_to_rfc3339(
-                            attrs.get("nbf").and_then(|v| v.as_u64()),
-                        ),
-                        subject: v
-                            .get("policy")
-                            .and_then(|p| p.get("x509_props"))
-                            .and_then(|x| x.get("subject"))
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string()),
-                        thumbprint: v.get("x5t").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                        tags: v
-                            .get("tags")
-                            .and_then(|t| serde_json::from_value(t.clone()).ok()),
-                    });
-                }
+        let mut payload = serde_json::json!({
+            "value": pfx_base64,
+            "policy": {
+                "secret_props": { "contentType": content_type }
             }
-
-            next_url = body
-                .get("nextLink")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
+        });
+        if let Some(pwd) = password {
+            payload["pwd"] = serde_json::json!(pwd);
         }
 
-        Ok(items)
+        let body = self
+            .request_json(Method::POST, &url, token, Some(payload))
+            .await?;
+
+        Ok(Self::parse_certificate_item(&body))
+    }
+
+    /// Lists soft-deleted certificates in a vault (paginated).
+    pub async fn list_deleted_certificates(
+        &self,
+        token: &str,
+        vault_uri: &str,
+    ) -> Result<ListResult<DeletedCertificateItem>, String> {
+        let url = format!(
+            "{}/deletedcertificates?api-version={}",
+            vault_uri, self.keyvault_data_api_version
+        );
+        self.list_paged(token, url, Self::parse_deleted_certificate_item)
+            .await
+    }
+
+    // ── Access probes ──
+
+    /// Fetches a single-item page of `collection` (e.g. `secrets`,
+    /// `deletedsecrets`) to test whether the caller may list it.
+    pub async fn probe_collection(
+        &self,
+        token: &str,
+        vault_uri: &str,
+        collection: &str,
+    ) -> Result<(), String> {
+        let url = format!(
+            "{}/{}?api-version={}&maxresults=1",
+            vault_uri, collection, self.keyvault_data_api_version
+        );
+        self.request_json(Method::GET, &url, token, None)
+            .await
+            .map(|_| ())
     }
 
     // ── Internal helpers ──
 
-    /// Fetches vault-level properties to determine soft-delete state.
-    async fn get_vault_soft_delete_state(
+    /// Fetches vault-level properties (soft-delete and network access).
+    async fn get_vault_properties(
         &self,
         token: &str,
         vault_id: &str,
-    ) -> Result<Option<bool>, String> {
+    ) -> Result<VaultProperties, String> {
         let url = format!(
             "{}{}?api-version={}",
-            ARM_BASE, vault_id, API_VERSION_KEYVAULT_MGMT
+            self.cloud.arm_base(),
+            vault_id,
+            self.keyvault_mgmt_api_version
         );
         let body = self.request_json(Method::GET, &url, token, None).await?;
-        Ok(body
-            .get("properties")
-            .and_then(|p| p.get("enableSoftDelete"))
-            .and_then(|v| v.as_bool()))
+        Ok(Self::parse_vault_properties(&body))
+    }
+
+    /// Parses an ARM `deletedVaults` entry into a `DeletedVaultInfo`.
+    fn parse_deleted_vault(v: &Value) -> DeletedVaultInfo {
+        let properties = &v["properties"];
+        let text = |value: &Value| value.as_str().map(|s| s.to_string());
+        let vault_id = properties["vaultId"].as_str().unwrap_or_default();
+        DeletedVaultInfo {
+            id: v["id"].as_str().unwrap_or_default().to_string(),
+            name: v["name"].as_str().unwrap_or_default().to_string(),
+            location: properties["location"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            vault_id: vault_id.to_string(),
+            resource_group: vault_id
+                .split("/resourceGroups/")
+                .nth(1)
+                .and_then(|s| s.split('/').next())
+                .unwrap_or_default()
+                .to_string(),
+            deletion_date: text(&properties["deletionDate"]),
+            scheduled_purge_date: text(&properties["scheduledPurgeDate"]),
+            purge_protection_enabled: properties["purgeProtectionEnabled"].as_bool(),
+            tags: properties
+                .get("tags")
+                .and_then(|t| serde_json::from_value(t.clone()).ok()),
+        }
+    }
+
+    /// Builds a `KeyVaultInfo` from the ARM vault returned by a recovery,
+    /// falling back to the deleted vault's details for missing fields.
+    fn parse_vault_resource(&self, body: &Value, deleted: &DeletedVaultInfo) -> KeyVaultInfo {
+        let properties = Self::parse_vault_properties(body);
+        let name = body["name"].as_str().unwrap_or(&deleted.name).to_string();
+        KeyVaultInfo {
+            id: body["id"].as_str().unwrap_or(&deleted.vault_id).to_string(),
+            vault_uri: body["properties"]["vaultUri"]
+                .as_str()
+                .map(|uri| uri.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| format!("https://{}{}", name, self.cloud.vault_suffix())),
+            name,
+            location: body["location"]
+                .as_str()
+                .unwrap_or(&deleted.location)
+                .to_string(),
+            resource_group: deleted.resource_group.clone(),
+            tags: body
+                .get("tags")
+                .and_then(|t| serde_json::from_value(t.clone()).ok())
+                .or_else(|| deleted.tags.clone()),
+            soft_delete_enabled: properties.soft_delete_enabled,
+            public_network_access: properties.public_network_access,
+            network_default_deny: properties.network_default_deny,
+        }
+    }
+
+    /// Extracts soft-delete and network settings from an ARM vault resource.
+    fn parse_vault_properties(body: &Value) -> VaultProperties {
+        let properties = &body["properties"];
+        VaultProperties {
+            soft_delete_enabled: properties["enableSoftDelete"].as_bool(),
+            public_network_access: properties["publicNetworkAccess"]
+                .as_str()
+                .map(|s| s.to_string()),
+            network_default_deny: properties["networkAcls"]["defaultAction"]
+                .as_str()
+                .map(|action| action.eq_ignore_ascii_case("Deny")),
+        }
+    }
+
+    /// Follows `nextLink` pagination from `url`, parsing each `value` entry
+    /// with `parse`.
+    ///
+    /// If a later page fails (after retries), the items gathered so far are
+    /// returned with `truncated: true` and the error. A failure on the first
+    /// page is returned as an error, since nothing could be listed.
+    async fn list_paged<T>(
+        &self,
+        token: &str,
+        url: String,
+        parse: fn(&Value) -> T,
+    ) -> Result<ListResult<T>, String> {
+        Self::collect_pages(url, self.max_pages, parse, move |page_url| async move {
+            self.get_json_conditional(&page_url, token).await
+        })
+        .await
+    }
+
+    /// Follows `nextLink` from `url` for at most `max_pages` pages, fetching
+    /// each page (body, served-from-cache flag) with `fetch`.
+    async fn collect_pages<T, F, Fut>(
+        url: String,
+        max_pages: usize,
+        parse: fn(&Value) -> T,
+        mut fetch: F,
+    ) -> Result<ListResult<T>, String>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<(Value, bool), String>>,
+    {
+        let mut next_url = Some(url);
+        let mut items = Vec::new();
+        let mut pages = 0;
+        let mut from_cache = true;
+
+        while let Some(current_url) = next_url {
+            if pages >= max_pages {
+                return Ok(ListResult {
+                    items,
+                    truncated: true,
+                    error: Some(format!(
+                        "Stopped after {} pages; the listing is incomplete.",
+                        max_pages
+                    )),
+                    stale: false,
+                    from_cache: false,
+                });
+            }
+
+            let body = match fetch(current_url).await {
+                Ok((body, not_modified)) => {
+                    from_cache &= not_modified;
+                    body
+                }
+                Err(e) if pages == 0 => return Err(e),
+                Err(e) => {
+                    return Ok(ListResult {
+                        items,
+                        truncated: true,
+                        error: Some(e),
+                        stale: false,
+                        from_cache: false,
+                    })
+                }
+            };
+            pages += 1;
+
+            if let Some(values) = body["value"].as_array() {
+                items.extend(values.iter().map(parse));
+            }
+            next_url = body
+                .get("nextLink")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+        }
+
+        Ok(ListResult {
+            items,
+            truncated: false,
+            error: None,
+            stale: false,
+            from_cache,
+        })
+    }
+
+    /// GETs `url`, sending `If-None-Match` when an ETag for it is cached.
+    /// Returns the body and whether it was reused after a `304 Not Modified`.
+    async fn get_json_conditional(&self, url: &str, token: &str) -> Result<(Value, bool), String> {
+        let cached = self
+            .etag_cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(url).cloned());
+        let if_none_match = cached.as_ref().map(|c| c.etag.as_str());
+        let sent = self
+            .send(Method::GET, url, token, None, if_none_match)
+            .await?;
+
+        if sent.status == StatusCode::NOT_MODIFIED {
+            if let Some(page) = cached {
+                return Ok((page.body, true));
+            }
+        }
+
+        if let Some(etag) = sent.etag {
+            self.remember_page(url, etag, &sent.body);
+        }
+
+        Ok((sent.body, false))
+    }
+
+    /// Caches a list page for revalidation, resetting the cache when full.
+    fn remember_page(&self, url: &str, etag: String, body: &Value) {
+        let Ok(mut cache) = self.etag_cache.lock() else {
+            return;
+        };
+        if cache.len() >= MAX_ETAG_ENTRIES && !cache.contains_key(url) {
+            cache.clear();
+        }
+        cache.insert(
+            url.to_string(),
+            CachedPage {
+                etag,
+                body: body.clone(),
+            },
+        );
     }
 
     /// Core HTTP request handler with URL allowlist, retry, and backoff.
@@ -468,79 +1471,444 @@ impl AzureClient {
         token: &str,
         payload: Option<Value>,
     ) -> Result<Value, String> {
-        if !Self::is_allowed_azure_url(url) {
+        self.send(method, url, token, payload, None)
+            .await
+            .map(|sent| sent.body)
+    }
+
+    /// Sends a request through the host's circuit breaker and the retry
+    /// loop. When `if_none_match` is set, a `304 Not Modified` is accepted
+    /// alongside 2xx responses.
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        token: &str,
+        payload: Option<Value>,
+        if_none_match: Option<&str>,
+    ) -> Result<SentResponse, String> {
+        if !Self::is_allowed_azure_url(url, self.cloud) {
             return Err("Blocked outbound request to non-Azure endpoint.".to_string());
         }
 
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .unwrap_or_default();
+        self.breaker.check(&host, Instant::now())?;
+
+        let request_id =
+            crate::correlation::current().unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let result = self
+            .send_with_retry(method, url, token, payload, if_none_match, &request_id)
+            .await;
+        if let Err(e) = &result {
+            log::debug!("Request {} to {} failed: {}", request_id, host, e.message);
+        }
+        let failed = matches!(&result, Err(e) if e.host_failure);
+        self.breaker.record(&host, failed, Instant::now());
+        result.map_err(|e| e.message)
+    }
+
+    /// Retry loop with exponential backoff and Retry-After support.
+    async fn send_with_retry(
+        &self,
+        method: Method,
+        url: &str,
+        token: &str,
+        payload: Option<Value>,
+        if_none_match: Option<&str>,
+        request_id: &str,
+    ) -> Result<SentResponse, SendError> {
         let mut attempt = 0usize;
+        let target = Self::log_target(url);
         loop {
-            let mut req = self.client.request(method.clone(), url).bearer_auth(token);
+            let started = Instant::now();
+            let mut req = self
+                .client
+                .request(method.clone(), url)
+                .headers(self.headers.clone())
+                .header(CLIENT_REQUEST_ID, request_id)
+                .bearer_auth(token);
             if let Some(p) = &payload {
                 req = req.json(p);
             }
+            if let Some(etag) = if_none_match {
+                req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
 
+            // Held for the request and body read, released before backoff.
+            let permit = self.limiter.acquire().await.ok();
             let response = req.send().await;
 
             match response {
                 Ok(resp) => {
                     let status = resp.status();
+                    self.remember_rate_limit(url, status, resp.headers());
+                    log::debug!(
+                        "{} {} -> {} in {}ms (attempt {})",
+                        method,
+                        target,
+                        status.as_u16(),
+                        started.elapsed().as_millis(),
+                        attempt + 1
+                    );
                     let retry_after = resp
                         .headers()
                         .get(reqwest::header::RETRY_AFTER)
                         .and_then(|h| h.to_str().ok())
                         .and_then(|s| s.parse::<u64>().ok());
-                    let body: Value = resp.json().await.unwrap_or_else(|_| serde_json::json!({}));
-
-                    if status.is_success() {
-                        return Ok(body);
+                    let etag = resp
+                        .headers()
+                        .get(reqwest::header::ETAG)
+                        .and_then(|h| h.to_str().ok())
+                        .map(|s| s.to_string());
+                    let not_modified =
+                        if_none_match.is_some() && status == StatusCode::NOT_MODIFIED;
+                    let body = if not_modified {
+                        Value::Null
+                    } else {
+                        Self::read_body(resp).await
+                    };
+
+                    drop(permit);
+
+                    if status.is_success() || not_modified {
+                        return Ok(SentResponse { status, etag, body });
                     }
 
                     // Retry on 429 (rate limit) or 5xx (server errors)
                     let should_retry = status.as_u16() == 429 || status.is_server_error();
                     if should_retry && attempt < MAX_RETRIES {
                         let backoff_secs = retry_after.unwrap_or((1_u64 << attempt).min(8));
+                        let reason = if status.as_u16() == 429 {
+                            format!("rate limited ({})", status.as_u16())
+                        } else {
+                            format!("server error ({})", status.as_u16())
+                        };
+                        self.notify_retry(attempt + 1, backoff_secs, reason);
                         tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
                         attempt += 1;
                         continue;
                     }
 
-                    return Err(Self::parse_error(&body, status.as_u16()));
+                    return Err(SendError {
+                        message: Self::parse_error(&body, status.as_u16()),
+                        host_failure: should_retry,
+                    });
                 }
                 Err(err) => {
+                    drop(permit);
+                    log::debug!(
+                        "{} {} -> network error in {}ms (attempt {})",
+                        method,
+                        target,
+                        started.elapsed().as_millis(),
+                        attempt + 1
+                    );
                     if attempt < MAX_RETRIES {
                         let backoff_secs = (1_u64 << attempt).min(8);
+                        self.notify_retry(attempt + 1, backoff_secs, "network error".to_string());
                         tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
                         attempt += 1;
                         continue;
                     }
-                    return Err(format!("Network error: {}", err));
+                    return Err(SendError {
+                        message: format!("Network error: {}", err),
+                        host_failure: true,
+                    });
                 }
             }
         }
     }
 
-    /// Parses a Key Vault secret JSON object into a `SecretItem`.
-    fn parse_secret_item(v: &Value) -> SecretItem {
+    /// Reads a response body as JSON. `204 No Content` and empty bodies yield
+    /// `Value::Null` without attempting to parse; unparseable bodies yield `{}`.
+    async fn read_body(resp: reqwest::Response) -> Value {
+        if resp.status() == StatusCode::NO_CONTENT || resp.content_length() == Some(0) {
+            return Value::Null;
+        }
+        resp.json().await.unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    /// Parses a Key Vault secret JSON object into a `SecretItem`.
+    fn parse_secret_item(v: &Value) -> SecretItem {
+        let id = v["id"].as_str().unwrap_or_default().to_string();
+        let name = Self::extract_name_from_id(&id, "secrets");
+        let version = Self::extract_version_from_id(&id, "secrets");
+        let attrs = &v["attributes"];
+
+        SecretItem {
+            id,
+            name,
+            version,
+            enabled: attrs["enabled"].as_bool().unwrap_or(true),
+            created: Self::epoch_to_rfc3339(attrs.get("created").and_then(|v| v.as_i64())),
+            updated: Self::epoch_to_rfc3339(attrs.get("updated").and_then(|v| v.as_i64())),
+            expires: Self::epoch_to_rfc3339(attrs.get("exp").and_then(|v| v.as_i64())),
+            not_before: Self::epoch_to_rfc3339(attrs.get("nbf").and_then(|v| v.as_i64())),
+            created_epoch: attrs.get("created").and_then(|v| v.as_i64()),
+            updated_epoch: attrs.get("updated").and_then(|v| v.as_i64()),
+            expires_epoch: attrs.get("exp").and_then(|v| v.as_i64()),
+            recovery_level: attrs
+                .get("recoveryLevel")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            content_type: v
+                .get("contentType")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            tags: v
+                .get("tags")
+                .and_then(|t| serde_json::from_value(t.clone()).ok()),
+            managed: v.get("managed").and_then(|v| v.as_bool()),
+        }
+    }
+
+    /// Parses a rotation policy response.
+    fn parse_rotation_policy(v: &Value) -> KeyRotationPolicy {
+        let lifetime_actions = v["lifetimeActions"]
+            .as_array()
+            .map(|actions| {
+                actions
+                    .iter()
+                    .map(|a| LifetimeAction {
+                        action: a["action"]["type"].as_str().unwrap_or_default().to_string(),
+                        time_after_create: a["trigger"]["timeAfterCreate"]
+                            .as_str()
+                            .map(|s| s.to_string()),
+                        time_before_expiry: a["trigger"]["timeBeforeExpiry"]
+                            .as_str()
+                            .map(|s| s.to_string()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        KeyRotationPolicy {
+            id: v["id"].as_str().map(|s| s.to_string()),
+            lifetime_actions,
+            expiry_time: v["attributes"]["expiryTime"]
+                .as_str()
+                .map(|s| s.to_string()),
+        }
+    }
+
+    /// Builds the PUT body for a rotation policy.
+    fn rotation_policy_payload(policy: &KeyRotationPolicy) -> Value {
+        let actions: Vec<Value> = policy
+            .lifetime_actions
+            .iter()
+            .map(|a| {
+                let mut trigger = serde_json::Map::new();
+                if let Some(after) = &a.time_after_create {
+                    trigger.insert("timeAfterCreate".to_string(), Value::from(after.as_str()));
+                }
+                if let Some(before) = &a.time_before_expiry {
+                    trigger.insert("timeBeforeExpiry".to_string(), Value::from(before.as_str()));
+                }
+                serde_json::json!({
+                    "trigger": trigger,
+                    "action": { "type": a.action },
+                })
+            })
+            .collect();
+
+        let mut payload = serde_json::json!({ "lifetimeActions": actions });
+        if let Some(expiry) = &policy.expiry_time {
+            payload["attributes"] = serde_json::json!({ "expiryTime": expiry });
+        }
+        payload
+    }
+
+    /// Parses a Key Vault key JSON object into a `KeyItem`. Key bundles
+    /// (`GET /keys/{name}`) nest `kid`/`kty`/`key_ops` under `key`; list
+    /// items carry them at the top level.
+    fn parse_key_item(v: &Value) -> KeyItem {
+        let jwk = if v["key"].is_object() { &v["key"] } else { v };
+        let id = jwk["kid"].as_str().unwrap_or_default().to_string();
+        let name = Self::extract_name_from_id(&id, "keys");
+        let attrs = &v["attributes"];
+
+        KeyItem {
+            id,
+            name,
+            enabled: attrs["enabled"].as_bool().unwrap_or(true),
+            created: Self::epoch_to_rfc3339(attrs.get("created").and_then(|v| v.as_i64())),
+            updated: Self::epoch_to_rfc3339(attrs.get("updated").and_then(|v| v.as_i64())),
+            expires: Self::epoch_to_rfc3339(attrs.get("exp").and_then(|v| v.as_i64())),
+            not_before: Self::epoch_to_rfc3339(attrs.get("nbf").and_then(|v| v.as_i64())),
+            created_epoch: attrs.get("created").and_then(|v| v.as_i64()),
+            updated_epoch: attrs.get("updated").and_then(|v| v.as_i64()),
+            expires_epoch: attrs.get("exp").and_then(|v| v.as_i64()),
+            recovery_level: attrs
+                .get("recoveryLevel")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            key_type: jwk
+                .get("kty")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            key_ops: jwk.get("key_ops").and_then(|v| v.as_array()).map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            }),
+            tags: v
+                .get("tags")
+                .and_then(|t| serde_json::from_value(t.clone()).ok()),
+            managed: v.get("managed").and_then(|v| v.as_bool()),
+        }
+    }
+
+    /// Parses a key bundle into `KeyDetails`. Only the public JWK members are
+    /// copied (by name), so private components can never be passed through.
+    fn parse_key_details(v: &Value) -> KeyDetails {
+        let jwk = &v["key"];
+        let member = |name: &str| jwk[name].as_str().map(|s| s.to_string());
+        KeyDetails {
+            metadata: Self::parse_key_item(v),
+            public_key: PublicJwk {
+                kid: member("kid"),
+                kty: member("kty"),
+                n: member("n"),
+                e: member("e"),
+                crv: member("crv"),
+                x: member("x"),
+                y: member("y"),
+            },
+        }
+    }
+
+    /// Parses a Key Vault certificate JSON object into a `CertificateItem`.
+    fn parse_certificate_item(v: &Value) -> CertificateItem {
+        let id = v["id"].as_str().unwrap_or_default().to_string();
+        let name = Self::extract_name_from_id(&id, "certificates");
+        let attrs = &v["attributes"];
+
+        CertificateItem {
+            id,
+            name,
+            enabled: attrs["enabled"].as_bool().unwrap_or(true),
+            created: Self::epoch_to_rfc3339(attrs.get("created").and_then(|v| v.as_i64())),
+            updated: Self::epoch_to_rfc3339(attrs.get("updated").and_then(|v| v.as_i64())),
+            expires: Self::epoch_to_rfc3339(attrs.get("exp").and_then(|v| v.as_i64())),
+            not_before: Self::epoch_to_rfc3339(attrs.get("nbf").and_then(|v| v.as_i64())),
+            created_epoch: attrs.get("created").and_then(|v| v.as_i64()),
+            updated_epoch: attrs.get("updated").and_then(|v| v.as_i64()),
+            expires_epoch: attrs.get("exp").and_then(|v| v.as_i64()),
+            recovery_level: attrs
+                .get("recoveryLevel")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            subject: v
+                .get("policy")
+                .and_then(|p| p.get("x509_props"))
+                .and_then(|x| x.get("subject"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            thumbprint: v.get("x5t").and_then(|v| v.as_str()).map(|s| s.to_string()),
+            tags: v
+                .get("tags")
+                .and_then(|t| serde_json::from_value(t.clone()).ok()),
+        }
+    }
+
+    /// Parses a Key Vault deleted-secret JSON object into a `DeletedSecretItem`.
+    fn parse_deleted_secret_item(v: &Value) -> DeletedSecretItem {
+        let id = v["id"].as_str().unwrap_or_default().to_string();
+        let name = Self::extract_name_from_id(&id, "secrets");
+        let attrs = &v["attributes"];
+
+        DeletedSecretItem {
+            id,
+            name,
+            recovery_id: v
+                .get("recoveryId")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            deleted_date: Self::epoch_to_rfc3339(v.get("deletedDate").and_then(|v| v.as_i64())),
+            scheduled_purge_date: Self::epoch_to_rfc3339(
+                v.get("scheduledPurgeDate").and_then(|v| v.as_i64()),
+            ),
+            recovery_level: v["attributes"]
+                .get("recoveryLevel")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            content_type: v
+                .get("contentType")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            tags: v
+                .get("tags")
+                .and_then(|t| serde_json::from_value(t.clone()).ok()),
+            enabled: attrs["enabled"].as_bool(),
+            created: Self::epoch_to_rfc3339(attrs["created"].as_i64()),
+            updated: Self::epoch_to_rfc3339(attrs["updated"].as_i64()),
+            expires: Self::epoch_to_rfc3339(attrs["exp"].as_i64()),
+        }
+    }
+
+    /// Parses a Key Vault deleted-key JSON object into a `DeletedKeyItem`.
+    fn parse_deleted_key_item(v: &Value) -> DeletedKeyItem {
+        let id = v["kid"].as_str().unwrap_or_default().to_string();
+        let name = Self::extract_name_from_id(&id, "keys");
+
+        DeletedKeyItem {
+            id,
+            name,
+            recovery_id: v
+                .get("recoveryId")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            deleted_date: Self::epoch_to_rfc3339(v.get("deletedDate").and_then(|v| v.as_i64())),
+            scheduled_purge_date: Self::epoch_to_rfc3339(
+                v.get("scheduledPurgeDate").and_then(|v| v.as_i64()),
+            ),
+            recovery_level: v["attributes"]
+                .get("recoveryLevel")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            tags: v
+                .get("tags")
+                .and_then(|t| serde_json::from_value(t.clone()).ok()),
+        }
+    }
+
+    /// Parses a Key Vault deleted-certificate JSON object into a
+    /// `DeletedCertificateItem`.
+    fn parse_deleted_certificate_item(v: &Value) -> DeletedCertificateItem {
         let id = v["id"].as_str().unwrap_or_default().to_string();
-        let name = Self::extract_name_from_id(&id, "secrets");
-        let attrs = &v["attributes"];
+        let name = Self::extract_name_from_id(&id, "certificates");
 
-        SecretItem {
+        DeletedCertificateItem {
             id,
             name,
-            enabled: attrs["enabled"].as_bool().unwrap_or(true),
-            created: Self::epoch_to_rfc3339(attrs.get("created").and_then(|v| v.as_u64())),
-            updated: Self::epoch_to_rfc3339(attrs.get("updated").and_then(|v| v.as_u64())),
-            expires: Self::epoch_to_rfc3339(attrs.get("exp").and_then(|v| v.as_u64())),
-            not_before: Self::epoch_to_rfc3339(attrs.get("nbf").and_then(|v| v.as_u64())),
-            content_type: v
-                .get("contentType")
+            recovery_id: v
+                .get("recoveryId")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            deleted_date: Self::epoch_to_rfc3339(v.get("deletedDate").and_then(|v| v.as_i64())),
+            scheduled_purge_date: Self::epoch_to_rfc3339(
+                v.get("scheduledPurgeDate").and_then(|v| v.as_i64()),
+            ),
+            recovery_level: v["attributes"]
+                .get("recoveryLevel")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string()),
+            thumbprint: v.get("x5t").and_then(|v| v.as_str()).map(|s| s.to_string()),
             tags: v
                 .get("tags")
                 .and_then(|t| serde_json::from_value(t.clone()).ok()),
-            managed: v.get("managed").and_then(|v| v.as_bool()),
+        }
+    }
+
+    /// Builds a `SecretValue` from a secret bundle, keeping the requested name.
+    fn parse_secret_value(body: &Value, name: &str) -> SecretValue {
+        SecretValue {
+            value: body["value"].as_str().unwrap_or_default().to_string(),
+            id: body["id"].as_str().unwrap_or_default().to_string(),
+            name: name.to_string(),
         }
     }
 
@@ -556,14 +1924,63 @@ impl AzureClient {
         parts.last().unwrap_or(&"").to_string()
     }
 
-    /// Converts a Unix epoch timestamp to RFC 3339 string.
-    fn epoch_to_rfc3339(epoch: Option<u64>) -> Option<String> {
-        epoch
-            .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0).map(|dt| dt.to_rfc3339()))
+    /// Host and path of `url` for debug logs, without the query string.
+    /// Everything after `/secrets/` or `/deletedsecrets/` is replaced with
+    /// `***` so secret names never reach the log.
+    fn log_target(url: &str) -> String {
+        let Ok(parsed) = Url::parse(url) else {
+            return "<invalid url>".to_string();
+        };
+        let mut path = Vec::new();
+        for segment in parsed.path().split('/') {
+            path.push(segment);
+            if segment.eq_ignore_ascii_case("secrets")
+                || segment.eq_ignore_ascii_case("deletedsecrets")
+            {
+                path.push("***");
+                break;
+            }
+        }
+        format!(
+            "{}{}",
+            parsed.host_str().unwrap_or_default(),
+            path.join("/")
+        )
+    }
+
+    /// Extracts the version segment following `/{entity}/{name}/` in an id.
+    fn extract_version_from_id(id: &str, entity: &str) -> Option<String> {
+        let mut parts = id.split('/').skip_while(|p| *p != entity).skip(2);
+        parts
+            .next()
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string())
+    }
+
+    /// Converts a Unix epoch timestamp to RFC 3339 string. Epochs outside
+    /// chrono's range are returned as the raw number (with a warning) so
+    /// extreme dates are not silently dropped.
+    fn epoch_to_rfc3339(epoch: Option<i64>) -> Option<String> {
+        epoch.map(|ts| match chrono::DateTime::from_timestamp(ts, 0) {
+            Some(dt) => dt.to_rfc3339(),
+            None => {
+                log::warn!("Epoch {} is out of range; returning it unconverted", ts);
+                ts.to_string()
+            }
+        })
+    }
+
+    /// Parses an RFC 3339 timestamp into epoch seconds for an attribute
+    /// payload, rejecting (rather than dropping) unparseable values.
+    fn rfc3339_to_epoch(field: &str, value: &str) -> Result<i64, String> {
+        chrono::DateTime::parse_from_rfc3339(value)
+            .map(|dt| dt.timestamp())
+            .map_err(|_| format!("Invalid {} timestamp '{}'. Use RFC 3339.", field, value))
     }
 
     /// Formats an Azure REST API error response into a user-friendly message
-    /// with contextual hints for common HTTP status codes.
+    /// with contextual hints for common HTTP status codes. The innermost
+    /// `innererror` code (and message, if any) is appended when present.
     fn parse_error(body: &Value, status: u16) -> String {
         let code = body["error"]["code"].as_str().unwrap_or("UnknownError");
         let message = body["error"]["message"]
@@ -580,6 +1997,20 @@ impl AzureClient {
         };
 
         let mut result = format!("[{}] {}: {}", status, code, message);
+
+        let mut inner = &body["error"]["innererror"];
+        while inner["innererror"].is_object() {
+            inner = &inner["innererror"];
+        }
+        if let Some(inner_code) = inner["code"].as_str() {
+            match inner["message"].as_str() {
+                Some(inner_message) => {
+                    result.push_str(&format!(" ({}: {})", inner_code, inner_message))
+                }
+                None => result.push_str(&format!(" ({})", inner_code)),
+            }
+        }
+
         if let Some(h) = hint {
             result.push_str(&format!(" | Hint: {}", h));
         }
@@ -587,36 +2018,377 @@ impl AzureClient {
     }
 
     /// Validates that a URL targets an allowed Azure endpoint.
-    /// Only HTTPS connections to known Azure hosts are permitted.
-    fn is_allowed_azure_url(url: &str) -> bool {
-        let parsed = match Url::parse(url) {
-            Ok(v) => v,
-            Err(_) => return false,
-        };
+    /// Only HTTPS connections to the ARM host of `cloud` and known Key Vault
+    /// data-plane hosts are permitted.
+    fn is_allowed_azure_url(url: &str, cloud: Cloud) -> bool {
+        Self::check_azure_url(url, cloud).is_ok()
+    }
+
+    /// Checks `url` against the outbound allowlist for this client's cloud,
+    /// explaining why it is rejected.
+    pub fn check_url(&self, url: &str) -> Result<(), String> {
+        Self::check_azure_url(url, self.cloud)
+    }
+
+    /// Allowlist check behind `is_allowed_azure_url`. Hosts that merely
+    /// embed an Azure host name (e.g. `kv.vault.azure.net.example.com`) are
+    /// reported as spoofed rather than simply unknown.
+    fn check_azure_url(url: &str, cloud: Cloud) -> Result<(), String> {
+        let parsed = Url::parse(url).map_err(|_| "Not a valid URL.".to_string())?;
 
         // Only HTTPS is allowed
         if parsed.scheme() != "https" {
-            return false;
+            return Err(format!("URL must use HTTPS, not '{}'.", parsed.scheme()));
         }
 
         let Some(host) = parsed.host_str() else {
-            return false;
+            return Err("URL must include a host.".to_string());
         };
 
-        // Allow ARM management plane and Key Vault data-plane endpoints
-        host == "management.azure.com"
-            || host.ends_with(".vault.azure.net")
-            || host.ends_with(".vault.usgovcloudapi.net")
-            || host.ends_with(".vault.azure.cn")
+        // Allow the selected ARM management plane and Key Vault data-plane endpoints
+        if host == cloud.arm_host() || Cloud::ALL.iter().any(|c| host.ends_with(c.vault_suffix())) {
+            return Ok(());
+        }
+
+        let imitates_azure = host.contains(cloud.arm_host())
+            || Cloud::ALL
+                .iter()
+                .any(|c| host.contains(c.vault_suffix().trim_start_matches('.')));
+        if imitates_azure {
+            Err(format!(
+                "Host '{}' imitates an Azure endpoint but is not one.",
+                host
+            ))
+        } else {
+            Err(format!(
+                "Host '{}' is not an Azure Resource Manager or Key Vault endpoint.",
+                host
+            ))
+        }
     }
 }
 
+/// Vault properties read from ARM alongside the resource listing.
+#[derive(Debug, Default)]
+struct VaultProperties {
+    soft_delete_enabled: Option<bool>,
+    public_network_access: Option<String>,
+    network_default_deny: Option<bool>,
+}
+
 // ── Tests ──
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serves a single raw HTTP response on a local port and returns its URL.
+    async fn serve_once(response: String) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server address");
+        tokio::spawn(async move {
+            if let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn records_only_throttling_headers_per_host() {
+        let client = AzureClient::new(AzureClientConfig::new(Cloud::Public));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ms-ratelimit-remaining-subscription-reads",
+            HeaderValue::from_static("11999"),
+        );
+        headers.insert(
+            "x-ms-keyvault-region",
+            HeaderValue::from_static("westeurope"),
+        );
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        client.remember_rate_limit(
+            "https://management.azure.com/subscriptions?api-version=2022-12-01",
+            StatusCode::OK,
+            &headers,
+        );
+        client.remember_rate_limit(
+            "https://kv.vault.azure.net/secrets",
+            StatusCode::OK,
+            &HeaderMap::new(),
+        );
+
+        let statuses = client.rate_limit_status();
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].host, "management.azure.com");
+        assert_eq!(statuses[0].status, 200);
+        assert_eq!(
+            statuses[0].headers.keys().collect::<Vec<_>>(),
+            vec![
+                "x-ms-keyvault-region",
+                "x-ms-ratelimit-remaining-subscription-reads"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_hook_reports_each_scheduled_retry() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let url = format!("http://{}/secrets", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let responses = [
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+            ];
+            for response in responses {
+                if let Ok((mut socket, _)) = listener.accept().await {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    let _ = socket.write_all(response.as_bytes()).await;
+                }
+            }
+        });
+
+        let notices = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let seen = notices.clone();
+        let client = AzureClient::new(AzureClientConfig::new(Cloud::Public)).with_retry_hook(
+            Box::new(move |notice| seen.lock().unwrap().push(notice.clone())),
+        );
+
+        client
+            .send_with_retry(Method::GET, &url, "token", None, None, "request-id")
+            .await
+            .unwrap_or_else(|_| panic!("retry should succeed"));
+
+        assert_eq!(
+            *notices.lock().unwrap(),
+            vec![RetryNotice {
+                attempt: 1,
+                delay_secs: 0,
+                reason: "rate limited (429)".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn pagination_stops_at_page_limit_for_self_referential_next_link() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let url = format!("http://{}/secrets", listener.local_addr().unwrap());
+        let body = json!({ "value": [{ "id": "a" }], "nextLink": url.clone() }).to_string();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let result = AzureClient::collect_pages(
+            url,
+            3,
+            |v: &Value| v["id"].as_str().unwrap_or_default().to_string(),
+            |page_url| async move {
+                match reqwest::get(&page_url).await {
+                    Ok(resp) => Ok((AzureClient::read_body(resp).await, false)),
+                    Err(e) => Err(e.to_string()),
+                }
+            },
+        )
+        .await
+        .expect("first page succeeds");
+
+        assert_eq!(result.items.len(), 3);
+        assert!(result.truncated);
+        assert!(result.error.unwrap().contains("Stopped after 3 pages"));
+    }
+
+    #[test]
+    fn log_target_redacts_secret_names_and_query() {
+        assert_eq!(
+            AzureClient::log_target(
+                "https://kv.vault.azure.net/secrets/db-conn/v1?api-version=7.5"
+            ),
+            "kv.vault.azure.net/secrets/***"
+        );
+        assert_eq!(
+            AzureClient::log_target("https://kv.vault.azure.net/keys/signing?api-version=7.5"),
+            "kv.vault.azure.net/keys/signing"
+        );
+    }
+
+    #[test]
+    fn allowed_hosts_list_arm_host_and_vault_suffixes() {
+        let client = AzureClient::new(AzureClientConfig::new(Cloud::China));
+        let hosts = client.allowed_hosts();
+        assert_eq!(hosts[0], "management.chinacloudapi.cn");
+        assert!(hosts.contains(&".vault.azure.net".to_string()));
+        assert_eq!(hosts.len(), 1 + Cloud::ALL.len());
+    }
+
+    #[test]
+    fn client_config_accepts_custom_headers() {
+        let mut config = AzureClientConfig::new(Cloud::Public);
+        config.add_header("X-Correlation-Id", "abc").unwrap();
+        assert_eq!(config.headers.get("x-correlation-id").unwrap(), "abc");
+    }
+
+    #[test]
+    fn validates_api_version_overrides() {
+        assert!(check_data_api_version("7.6").is_ok());
+        assert!(check_data_api_version("7.6-preview").is_ok());
+        assert!(check_data_api_version("7.3").is_err());
+        assert!(check_data_api_version("8.0").is_err());
+        assert!(check_data_api_version("latest").is_err());
+
+        assert!(check_mgmt_api_version("2024-11-01").is_ok());
+        assert!(check_mgmt_api_version("2024-12-01-preview").is_ok());
+        assert!(check_mgmt_api_version("2021-10-01").is_err());
+        assert!(check_mgmt_api_version("2024-13-01").is_err());
+    }
+
+    #[test]
+    fn limiter_has_configured_permits() {
+        let mut config = AzureClientConfig::new(Cloud::Public);
+        config.max_concurrent_requests = 2;
+        let client = AzureClient::new(config);
+        assert_eq!(client.max_concurrent_requests(), 2);
+
+        let _first = client.limiter.try_acquire().unwrap();
+        let _second = client.limiter.try_acquire().unwrap();
+        assert!(client.limiter.try_acquire().is_err());
+    }
+
+    #[test]
+    fn client_config_rejects_authorization_override() {
+        let mut config = AzureClientConfig::new(Cloud::Public);
+        assert!(config.add_header("Authorization", "Bearer x").is_err());
+        assert!(config
+            .add_header("x-ms-client-request-id", "fixed")
+            .is_err());
+        assert!(config.add_header("bad header", "x").is_err());
+        assert!(config.headers.is_empty());
+    }
+
+    #[test]
+    fn breaker_opens_after_threshold_failures() {
+        let breaker = CircuitBreaker::default();
+        let now = Instant::now();
+        for _ in 0..BREAKER_THRESHOLD - 1 {
+            breaker.record("kv.vault.azure.net", true, now);
+        }
+        assert!(breaker.check("kv.vault.azure.net", now).is_ok());
+
+        breaker.record("kv.vault.azure.net", true, now);
+        let err = breaker.check("kv.vault.azure.net", now).unwrap_err();
+        assert!(err.contains("temporarily unavailable"));
+        assert!(breaker.check("other.vault.azure.net", now).is_ok());
+    }
+
+    #[test]
+    fn breaker_allows_probe_after_cooldown_and_resets_on_success() {
+        let breaker = CircuitBreaker::default();
+        let now = Instant::now();
+        for _ in 0..BREAKER_THRESHOLD {
+            breaker.record("kv.vault.azure.net", true, now);
+        }
+
+        let later = now + BREAKER_COOLDOWN + Duration::from_secs(1);
+        assert!(breaker.check("kv.vault.azure.net", later).is_ok());
+
+        // A failed probe re-opens immediately.
+        breaker.record("kv.vault.azure.net", true, later);
+        assert!(breaker.check("kv.vault.azure.net", later).is_err());
+
+        let after_probe = later + BREAKER_COOLDOWN + Duration::from_secs(1);
+        breaker.record("kv.vault.azure.net", false, after_probe);
+        assert!(breaker.check("kv.vault.azure.net", after_probe).is_ok());
+        breaker.record("kv.vault.azure.net", true, after_probe);
+        assert!(breaker.check("kv.vault.azure.net", after_probe).is_ok());
+    }
+
+    #[test]
+    fn breaker_forgets_failures_outside_window() {
+        let breaker = CircuitBreaker::default();
+        let start = Instant::now();
+        for _ in 0..BREAKER_THRESHOLD - 1 {
+            breaker.record("kv.vault.azure.net", true, start);
+        }
+        let later = start + BREAKER_WINDOW + Duration::from_secs(1);
+        breaker.record("kv.vault.azure.net", true, later);
+        assert!(breaker.check("kv.vault.azure.net", later).is_ok());
+    }
+
+    #[test]
+    fn remember_page_stores_etag_and_body() {
+        let client = AzureClient::new(AzureClientConfig::new(Cloud::Public));
+        let url = "https://kv.vault.azure.net/secrets?api-version=7.5";
+        client.remember_page(url, "\"v1\"".to_string(), &json!({ "value": [] }));
+
+        let cache = client.etag_cache.lock().unwrap();
+        let page = cache.get(url).expect("page should be cached");
+        assert_eq!(page.etag, "\"v1\"");
+        assert_eq!(page.body, json!({ "value": [] }));
+    }
+
+    #[test]
+    fn remember_page_is_bounded() {
+        let client = AzureClient::new(AzureClientConfig::new(Cloud::Public));
+        for i in 0..=MAX_ETAG_ENTRIES {
+            let url = format!("https://kv.vault.azure.net/secrets?page={}", i);
+            client.remember_page(&url, format!("etag-{}", i), &Value::Null);
+        }
+        assert!(client.etag_cache.lock().unwrap().len() <= MAX_ETAG_ENTRIES);
+    }
+
+    #[tokio::test]
+    async fn read_body_returns_null_for_no_content() {
+        let url = serve_once("HTTP/1.1 204 No Content\r\n\r\n".to_string()).await;
+        let resp = reqwest::get(&url)
+            .await
+            .expect("mock server should respond");
+        assert!(resp.status().is_success());
+        assert_eq!(AzureClient::read_body(resp).await, Value::Null);
+    }
+
+    #[tokio::test]
+    async fn read_body_returns_null_for_empty_body() {
+        let url = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".to_string()).await;
+        let resp = reqwest::get(&url)
+            .await
+            .expect("mock server should respond");
+        assert_eq!(AzureClient::read_body(resp).await, Value::Null);
+    }
+
+    #[tokio::test]
+    async fn read_body_parses_json_body() {
+        let body = r#"{"value":[]}"#;
+        let url = serve_once(format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ))
+        .await;
+        let resp = reqwest::get(&url)
+            .await
+            .expect("mock server should respond");
+        assert_eq!(AzureClient::read_body(resp).await, json!({ "value": [] }));
+    }
 
     #[test]
     fn extracts_name_from_secret_id() {
@@ -627,6 +2399,39 @@ mod tests {
         assert_eq!(name, "my-secret");
     }
 
+    #[test]
+    fn parses_versioned_and_unversioned_secret_ids() {
+        let client = AzureClient::new(AzureClientConfig::new(Cloud::Public));
+        assert_eq!(
+            client
+                .parse_secret_id("https://demo.vault.azure.net/secrets/my-secret/v1")
+                .unwrap(),
+            (
+                "https://demo.vault.azure.net".to_string(),
+                "my-secret".to_string(),
+                Some("v1".to_string())
+            )
+        );
+        let (_, name, version) = client
+            .parse_secret_id("https://demo.vault.azure.net/secrets/my-secret/")
+            .unwrap();
+        assert_eq!(name, "my-secret");
+        assert!(version.is_none());
+    }
+
+    #[test]
+    fn rejects_non_secret_or_unlisted_ids() {
+        let client = AzureClient::new(AzureClientConfig::new(Cloud::Public));
+        for id in [
+            "https://demo.vault.azure.net/keys/rsa-key/v1",
+            "https://demo.vault.azure.net/secrets/a/v1/extra",
+            "https://demo.vault.azure.net/secrets",
+            "https://demo.example.com/secrets/my-secret/v1",
+        ] {
+            assert!(client.parse_secret_id(id).is_err(), "{}", id);
+        }
+    }
+
     #[test]
     fn extracts_name_from_key_id() {
         let name = AzureClient::extract_name_from_id(
@@ -680,6 +2485,34 @@ mod tests {
         assert!(result.unwrap().contains("1970"));
     }
 
+    #[test]
+    fn epoch_to_rfc3339_handles_year_9999() {
+        // 9999-12-31T23:59:59Z = 253402300799
+        let result = AzureClient::epoch_to_rfc3339(Some(253_402_300_799)).unwrap();
+        assert!(result.starts_with("9999-12-31T23:59:59"));
+    }
+
+    #[test]
+    fn epoch_to_rfc3339_handles_negative_epoch() {
+        let result = AzureClient::epoch_to_rfc3339(Some(-86_400)).unwrap();
+        assert!(result.starts_with("1969-12-31"));
+    }
+
+    #[test]
+    fn epoch_to_rfc3339_preserves_out_of_range_epoch() {
+        let result = AzureClient::epoch_to_rfc3339(Some(i64::MAX));
+        assert_eq!(result.as_deref(), Some("9223372036854775807"));
+    }
+
+    #[test]
+    fn rfc3339_to_epoch_rejects_invalid_timestamp() {
+        assert_eq!(
+            AzureClient::rfc3339_to_epoch("expires", "2024-01-01T00:00:00Z"),
+            Ok(1_704_067_200)
+        );
+        assert!(AzureClient::rfc3339_to_epoch("expires", "next tuesday").is_err());
+    }
+
     #[test]
     fn parses_error_with_hint_403() {
         let body = json!({
@@ -718,6 +2551,37 @@ mod tests {
         assert!(!result.contains("Hint"));
     }
 
+    #[test]
+    fn parses_error_with_nested_innererror_code() {
+        let body = json!({
+            "error": {
+                "code": "Forbidden",
+                "message": "Operation get is not allowed on a disabled secret.",
+                "innererror": {
+                    "code": "AccessDenied",
+                    "innererror": { "code": "SecretDisabled" }
+                }
+            }
+        });
+        let result = AzureClient::parse_error(&body, 403);
+        assert!(result.contains("(SecretDisabled)"));
+        assert!(!result.contains("AccessDenied"));
+        assert!(result.ends_with("access policy."), "hint stays last");
+    }
+
+    #[test]
+    fn parses_error_with_innererror_message() {
+        let body = json!({
+            "error": {
+                "code": "BadParameter",
+                "message": "Invalid request",
+                "innererror": { "code": "InvalidTag", "message": "Tag value too long" }
+            }
+        });
+        let result = AzureClient::parse_error(&body, 400);
+        assert!(result.contains("(InvalidTag: Tag value too long)"));
+    }
+
     #[test]
     fn parses_error_with_fallback_description() {
         let body = json!({
@@ -730,60 +2594,119 @@ mod tests {
     #[test]
     fn allows_azure_public_management_url() {
         assert!(AzureClient::is_allowed_azure_url(
-            "https://management.azure.com/subscriptions"
+            "https://management.azure.com/subscriptions",
+            Cloud::Public
         ));
     }
 
     #[test]
     fn allows_vault_data_plane_url() {
         assert!(AzureClient::is_allowed_azure_url(
-            "https://my-vault.vault.azure.net/secrets/test"
+            "https://my-vault.vault.azure.net/secrets/test",
+            Cloud::Public
         ));
     }
 
     #[test]
     fn allows_us_gov_vault_url() {
         assert!(AzureClient::is_allowed_azure_url(
-            "https://my-vault.vault.usgovcloudapi.net/keys"
+            "https://my-vault.vault.usgovcloudapi.net/keys",
+            Cloud::Public
         ));
     }
 
     #[test]
     fn allows_china_vault_url() {
         assert!(AzureClient::is_allowed_azure_url(
-            "https://my-vault.vault.azure.cn/certificates"
+            "https://my-vault.vault.azure.cn/certificates",
+            Cloud::Public
+        ));
+    }
+
+    #[test]
+    fn allows_only_selected_cloud_management_url() {
+        assert!(AzureClient::is_allowed_azure_url(
+            "https://management.chinacloudapi.cn/tenants",
+            Cloud::China
+        ));
+        assert!(!AzureClient::is_allowed_azure_url(
+            "https://management.chinacloudapi.cn/tenants",
+            Cloud::Public
         ));
+        assert!(!AzureClient::is_allowed_azure_url(
+            "https://management.azure.com/tenants",
+            Cloud::UsGov
+        ));
+    }
+
+    #[test]
+    fn parses_vault_network_properties() {
+        let body = json!({
+            "properties": {
+                "enableSoftDelete": true,
+                "publicNetworkAccess": "Disabled",
+                "networkAcls": { "defaultAction": "Deny" }
+            }
+        });
+        let properties = AzureClient::parse_vault_properties(&body);
+        assert_eq!(properties.soft_delete_enabled, Some(true));
+        assert_eq!(
+            properties.public_network_access.as_deref(),
+            Some("Disabled")
+        );
+        assert_eq!(properties.network_default_deny, Some(true));
+
+        let empty = AzureClient::parse_vault_properties(&json!({ "properties": {} }));
+        assert!(empty.public_network_access.is_none());
+        assert!(empty.network_default_deny.is_none());
+    }
+
+    #[test]
+    fn check_azure_url_explains_rejections() {
+        let reason = |url| AzureClient::check_azure_url(url, Cloud::Public).unwrap_err();
+        assert!(reason("http://kv.vault.azure.net").contains("HTTPS"));
+        assert!(reason("https://evil.example.com").contains("not an Azure"));
+        assert!(reason("https://kv.vault.azure.net.evil.example.com").contains("imitates"));
+        assert!(reason("https://kv.vault.azure.net@evil.example.com").contains("not an Azure"));
+        assert!(reason("not a url").contains("valid URL"));
+        assert!(AzureClient::check_azure_url("https://kv.vault.azure.net", Cloud::Public).is_ok());
     }
 
     #[test]
     fn rejects_non_azure_url() {
         assert!(!AzureClient::is_allowed_azure_url(
-            "https://evil.example.com/data"
+            "https://evil.example.com/data",
+            Cloud::Public
         ));
     }
 
     #[test]
     fn rejects_http_url() {
         assert!(!AzureClient::is_allowed_azure_url(
-            "http://management.azure.com/subscriptions"
+            "http://management.azure.com/subscriptions",
+            Cloud::Public
         ));
     }
 
     #[test]
     fn rejects_invalid_url() {
-        assert!(!AzureClient::is_allowed_azure_url("not a url"));
+        assert!(!AzureClient::is_allowed_azure_url(
+            "not a url",
+            Cloud::Public
+        ));
     }
 
     #[test]
     fn rejects_empty_url() {
-        assert!(!AzureClient::is_allowed_azure_url(""));
+        assert!(!AzureClient::is_allowed_azure_url("", Cloud::Public));
     }
 
     #[test]
     fn rejects_url_with_azure_in_subdomain_but_wrong_host() {
         // Prevent subdomain spoofing
         assert!(!AzureClient::is_allowed_azure_url(
-            "https://vault.azure.net.evil.com/secrets"
+            "https://vault.azure.net.evil.com/secrets",
+            Cloud::Public
         ));
     }
 
@@ -795,7 +2718,8 @@ mod tests {
                 "enabled": true,
                 "created": 1704067200,
                 "updated": 1704153600,
-                "exp": 1735689600
+                "exp": 1735689600,
+                "recoveryLevel": "Recoverable+Purgeable"
             },
             "contentType": "text/plain",
             "tags": {"env": "prod"},
@@ -804,12 +2728,217 @@ mod tests {
 
         let item = AzureClient::parse_secret_item(&kv_json);
         assert_eq!(item.name, "db-conn");
+        assert_eq!(item.created_epoch, Some(1704067200));
+        assert_eq!(item.updated_epoch, Some(1704153600));
+        assert_eq!(item.version.as_deref(), Some("abc123"));
+        assert_eq!(
+            item.recovery_level.as_deref(),
+            Some("Recoverable+Purgeable")
+        );
         assert!(item.enabled);
         assert!(item.created.is_some());
         assert_eq!(item.content_type.as_deref(), Some("text/plain"));
         assert_eq!(item.tags.unwrap().get("env").unwrap(), "prod");
     }
 
+    #[test]
+    fn parse_key_item_from_kv_response() {
+        let kv_json = json!({
+            "kid": "https://myvault.vault.azure.net/keys/signing-key",
+            "attributes": { "enabled": false, "created": 1704067200 },
+            "kty": "RSA",
+            "key_ops": ["sign", "verify"]
+        });
+
+        let item = AzureClient::parse_key_item(&kv_json);
+        assert_eq!(item.name, "signing-key");
+        assert!(!item.enabled);
+        assert_eq!(item.key_type.as_deref(), Some("RSA"));
+        assert_eq!(item.key_ops.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn parse_key_details_keeps_only_public_members() {
+        let kv_json = json!({
+            "key": {
+                "kid": "https://myvault.vault.azure.net/keys/signing-key/v1",
+                "kty": "RSA",
+                "key_ops": ["verify"],
+                "n": "AQAB-modulus",
+                "e": "AQAB",
+                "d": "private-exponent"
+            },
+            "attributes": { "enabled": true }
+        });
+
+        let details = AzureClient::parse_key_details(&kv_json);
+        assert_eq!(details.metadata.name, "signing-key");
+        assert_eq!(details.metadata.key_type.as_deref(), Some("RSA"));
+        assert_eq!(details.public_key.e.as_deref(), Some("AQAB"));
+        assert!(details.public_key.crv.is_none());
+        let serialized = serde_json::to_string(&details).unwrap();
+        assert!(!serialized.contains("private-exponent"));
+    }
+
+    #[test]
+    fn parses_deleted_vault_and_recovered_resource() {
+        let deleted = AzureClient::parse_deleted_vault(&json!({
+            "id": "/subscriptions/sub/providers/Microsoft.KeyVault/locations/westeurope/deletedVaults/kv-app",
+            "name": "kv-app",
+            "properties": {
+                "vaultId": "/subscriptions/sub/resourceGroups/rg-app/providers/Microsoft.KeyVault/vaults/kv-app",
+                "location": "westeurope",
+                "deletionDate": "2024-06-01T10:00:00Z",
+                "scheduledPurgeDate": "2024-08-30T10:00:00Z",
+                "purgeProtectionEnabled": true,
+                "tags": { "env": "prod" }
+            }
+        }));
+        assert_eq!(deleted.resource_group, "rg-app");
+        assert_eq!(deleted.location, "westeurope");
+        assert_eq!(
+            deleted.scheduled_purge_date.as_deref(),
+            Some("2024-08-30T10:00:00Z")
+        );
+        assert_eq!(deleted.purge_protection_enabled, Some(true));
+
+        let client = AzureClient::new(AzureClientConfig::new(Cloud::Public));
+        let vault = client.parse_vault_resource(
+            &json!({ "properties": { "enableSoftDelete": true } }),
+            &deleted,
+        );
+        assert_eq!(vault.id, deleted.vault_id);
+        assert_eq!(vault.vault_uri, "https://kv-app.vault.azure.net");
+        assert_eq!(vault.soft_delete_enabled, Some(true));
+        assert_eq!(vault.tags.unwrap()["env"], "prod");
+    }
+
+    #[test]
+    fn parses_key_operation_result_value() {
+        assert_eq!(
+            AzureClient::parse_key_operation_result(
+                &json!({ "kid": "https://kv.vault.azure.net/keys/k/v1", "value": "b3V0" }),
+                "encrypt"
+            )
+            .unwrap(),
+            "b3V0"
+        );
+        let err = AzureClient::parse_key_operation_result(&json!({}), "decrypt").unwrap_err();
+        assert!(err.contains("decrypt"));
+    }
+
+    #[test]
+    fn rotation_policy_round_trips_through_payload() {
+        let policy = KeyRotationPolicy {
+            id: None,
+            lifetime_actions: vec![
+                LifetimeAction {
+                    action: "Rotate".to_string(),
+                    time_after_create: Some("P90D".to_string()),
+                    time_before_expiry: None,
+                },
+                LifetimeAction {
+                    action: "Notify".to_string(),
+                    time_after_create: None,
+                    time_before_expiry: Some("P30D".to_string()),
+                },
+            ],
+            expiry_time: Some("P1Y".to_string()),
+        };
+
+        let payload = AzureClient::rotation_policy_payload(&policy);
+        assert_eq!(
+            payload["lifetimeActions"][0]["trigger"]["timeAfterCreate"],
+            "P90D"
+        );
+        assert_eq!(payload["lifetimeActions"][1]["action"]["type"], "Notify");
+        assert_eq!(payload["attributes"]["expiryTime"], "P1Y");
+        assert_eq!(AzureClient::parse_rotation_policy(&payload), policy);
+    }
+
+    #[test]
+    fn parse_certificate_item_from_kv_response() {
+        let kv_json = json!({
+            "id": "https://myvault.vault.azure.net/certificates/tls-cert",
+            "attributes": { "enabled": true, "exp": 1735689600 },
+            "x5t": "abc123"
+        });
+
+        let item = AzureClient::parse_certificate_item(&kv_json);
+        assert_eq!(item.name, "tls-cert");
+        assert!(item.expires.is_some());
+        assert_eq!(item.expires_epoch, Some(1735689600));
+        assert!(item.created_epoch.is_none());
+        assert_eq!(item.thumbprint.as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn parse_deleted_secret_item_from_kv_response() {
+        let kv_json = json!({
+            "recoveryId": "https://myvault.vault.azure.net/deletedsecrets/old-conn",
+            "deletedDate": 1704067200,
+            "scheduledPurgeDate": 1711843200,
+            "id": "https://myvault.vault.azure.net/secrets/old-conn",
+            "attributes": {
+                "enabled": true,
+                "recoveryLevel": "Recoverable+Purgeable"
+            }
+        });
+
+        let item = AzureClient::parse_deleted_secret_item(&kv_json);
+        assert_eq!(item.name, "old-conn");
+        assert_eq!(item.enabled, Some(true));
+        assert!(item.deleted_date.unwrap().starts_with("2024-01-01"));
+        assert!(item.scheduled_purge_date.is_some());
+        assert_eq!(
+            item.recovery_level.as_deref(),
+            Some("Recoverable+Purgeable")
+        );
+    }
+
+    #[test]
+    fn parse_deleted_key_and_certificate_items() {
+        let key = AzureClient::parse_deleted_key_item(&json!({
+            "kid": "https://myvault.vault.azure.net/keys/old-key/v1",
+            "recoveryId": "https://myvault.vault.azure.net/deletedkeys/old-key",
+            "deletedDate": 1704067200,
+            "attributes": { "recoveryLevel": "Recoverable" }
+        }));
+        assert_eq!(key.name, "old-key");
+        assert!(key.recovery_id.unwrap().contains("deletedkeys"));
+        assert!(key.scheduled_purge_date.is_none());
+        assert_eq!(key.recovery_level.as_deref(), Some("Recoverable"));
+
+        let cert = AzureClient::parse_deleted_certificate_item(&json!({
+            "id": "https://myvault.vault.azure.net/certificates/old-cert",
+            "scheduledPurgeDate": 1711843200,
+            "x5t": "thumb",
+            "attributes": {}
+        }));
+        assert_eq!(cert.name, "old-cert");
+        assert!(cert.scheduled_purge_date.is_some());
+        assert_eq!(cert.thumbprint.as_deref(), Some("thumb"));
+    }
+
+    #[test]
+    fn parse_secret_item_from_put_response_includes_version() {
+        let put_json = json!({
+            "value": "redacted",
+            "id": "https://myvault.vault.azure.net/secrets/api-key/00000000000000000000000000000002",
+            "attributes": { "enabled": true, "created": 1704067200 }
+        });
+
+        let item = AzureClient::parse_secret_item(&put_json);
+        assert_eq!(item.name, "api-key");
+        assert_eq!(
+            item.version.as_deref(),
+            Some("00000000000000000000000000000002")
+        );
+        assert!(item
+            .id
+            .ends_with("/api-key/00000000000000000000000000000002"));
+    }
+
     #[test]
     fn parse_secret_item_handles_minimal_response() {
         let kv_json = json!({
@@ -819,6 +2948,7 @@ mod tests {
 
         let item = AzureClient::parse_secret_item(&kv_json);
         assert_eq!(item.name, "minimal");
+        assert!(item.version.is_none());
         assert!(item.enabled); // defaults to true
         assert!(item.created.is_none());
         assert!(item.content_type.is_none());
@@ -0,0 +1,176 @@
+//! Local metadata cache for offline reference.
+//!
+//! Security guarantees:
+//! - Only item **metadata** listings are cached; the cached types carry no
+//!   secret values, so values can never reach the cache.
+//! - Caching is opt-in and disabled by default; disabling clears the cache.
+//! - On Unix, cache files have `0o600` permissions (owner-only read/write).
+//! - Cache file names are derived from the validated vault host only.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use url::Url;
+
+/// A cached listing as persisted on disk (`I` is a slice when storing and a
+/// `Vec` when loading).
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedListing<I> {
+    cached_at: String,
+    items: I,
+}
+
+/// On-disk cache of vault item listings, keyed by vault URI and item kind.
+pub struct MetadataCache {
+    dir: PathBuf,
+    enabled: AtomicBool,
+}
+
+impl MetadataCache {
+    /// Creates a (disabled) cache rooted in the app data directory.
+    pub fn new(app_data_dir: PathBuf) -> Self {
+        Self {
+            dir: app_data_dir.join("metadata_cache"),
+            enabled: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns `true` if listings are currently written to the cache.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables caching. Disabling removes all cached listings.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    /// Persists a complete listing for `vault_uri`/`kind` (no-op when disabled).
+    pub fn store<T: Serialize>(&self, vault_uri: &str, kind: &str, items: &[T]) {
+        if !self.is_enabled() {
+            return;
+        }
+        let Some(path) = self.path_for(vault_uri, kind) else {
+            return;
+        };
+        let listing = CachedListing {
+            cached_at: chrono::Utc::now().to_rfc3339(),
+            items,
+        };
+        let Ok(json) = serde_json::to_string(&listing) else {
+            return;
+        };
+
+        std::fs::create_dir_all(&self.dir).ok();
+        if let Ok(mut file) = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(&path)
+        {
+            let _ = file.write_all(json.as_bytes());
+            // Security: restrict cache files to owner-only on Unix
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+            }
+        }
+    }
+
+    /// Loads the last cached listing for `vault_uri`/`kind`, if any.
+    pub fn load<T: DeserializeOwned>(&self, vault_uri: &str, kind: &str) -> Option<Vec<T>> {
+        let path = self.path_for(vault_uri, kind)?;
+        let content = std::fs::read_to_string(path).ok()?;
+        let listing: CachedListing<Vec<T>> = serde_json::from_str(&content).ok()?;
+        Some(listing.items)
+    }
+
+    /// Removes all cached listings.
+    pub fn clear(&self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+
+    /// Builds the cache file path from the vault host and item kind.
+    fn path_for(&self, vault_uri: &str, kind: &str) -> Option<PathBuf> {
+        let parsed = Url::parse(vault_uri).ok()?;
+        let host = parsed.host_str()?;
+        let safe = |s: &str| {
+            s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        };
+        if !safe(host) || !safe(kind) || host.contains("..") {
+            return None;
+        }
+        Some(self.dir.join(format!("{}-{}.json", host, kind)))
+    }
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache() -> (MetadataCache, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("azvault-cache-test-{}", uuid::Uuid::new_v4()));
+        (MetadataCache::new(dir.clone()), dir)
+    }
+
+    #[test]
+    fn disabled_cache_does_not_store() {
+        let (cache, dir) = temp_cache();
+        cache.store(
+            "https://demo.vault.azure.net",
+            "secrets",
+            &["a".to_string()],
+        );
+        assert!(cache
+            .load::<String>("https://demo.vault.azure.net", "secrets")
+            .is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stores_and_loads_listing() {
+        let (cache, dir) = temp_cache();
+        cache.set_enabled(true);
+        cache.store(
+            "https://demo.vault.azure.net",
+            "keys",
+            &["k1".to_string(), "k2".to_string()],
+        );
+
+        let items: Vec<String> = cache
+            .load("https://demo.vault.azure.net", "keys")
+            .expect("cached listing");
+        assert_eq!(items, vec!["k1", "k2"]);
+        assert!(cache
+            .load::<String>("https://other.vault.azure.net", "keys")
+            .is_none());
+
+        cache.set_enabled(false);
+        assert!(cache
+            .load::<String>("https://demo.vault.azure.net", "keys")
+            .is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_unsafe_cache_paths() {
+        let (cache, _dir) = temp_cache();
+        assert!(cache.path_for("not a url", "secrets").is_none());
+        assert!(cache
+            .path_for("https://demo.vault.azure.net", "../secrets")
+            .is_none());
+        assert!(cache
+            .path_for("https://demo.vault.azure.net", "secrets")
+            .is_some());
+    }
+}
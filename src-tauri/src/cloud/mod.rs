@@ -0,0 +1,200 @@
+//! National cloud configuration (Public, US Government, China).
+//!
+//! AzVault targets exactly one Azure cloud per process. The cloud is chosen
+//! via the `AZVAULT_CLOUD` environment variable and decides:
+//! - the authority (login) host reported in the effective configuration,
+//! - the token resource scopes requested from Azure CLI,
+//! - the ARM management-plane base URL and Key Vault DNS suffix.
+//!
+//! Tenant discovery probes the fixed authority host of every cloud, so
+//! authorities are not configurable.
+//!
+//! The Azure CLI session itself must point at the same cloud
+//! (`az cloud set --name <cli name>`), since AzVault never signs in itself.
+
+use serde::{Deserialize, Serialize};
+
+/// Environment variable selecting the target cloud.
+const ENV_CLOUD: &str = "AZVAULT_CLOUD";
+
+/// Known Azure clouds. Serialized names match the frontend `AzureEnvironment`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cloud {
+    #[serde(rename = "azurePublic")]
+    Public,
+    #[serde(rename = "azureUsGovernment")]
+    UsGov,
+    #[serde(rename = "azureChina")]
+    China,
+}
+
+impl Cloud {
+    /// All supported clouds, in probing order.
+    pub const ALL: [Cloud; 3] = [Cloud::Public, Cloud::UsGov, Cloud::China];
+
+    /// Parses a cloud name (case-insensitive). Accepts short names
+    /// (`public`, `usgov`, `china`) and Azure CLI names (`AzureCloud`, ...).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "public" | "azurepublic" | "azurecloud" => Some(Cloud::Public),
+            "usgov" | "azureusgovernment" => Some(Cloud::UsGov),
+            "china" | "azurechina" | "azurechinacloud" => Some(Cloud::China),
+            _ => None,
+        }
+    }
+
+    /// Name of the cloud as understood by `az cloud set --name`.
+    pub fn cli_name(self) -> &'static str {
+        match self {
+            Cloud::Public => "AzureCloud",
+            Cloud::UsGov => "AzureUSGovernment",
+            Cloud::China => "AzureChinaCloud",
+        }
+    }
+
+    /// Default authority (login) origin for the cloud.
+    pub fn authority_host(self) -> &'static str {
+        match self {
+            Cloud::Public => "https://login.microsoftonline.com",
+            Cloud::UsGov => "https://login.microsoftonline.us",
+            Cloud::China => "https://login.chinacloudapi.cn",
+        }
+    }
+
+    /// ARM management-plane token resource scope.
+    pub fn management_scope(self) -> &'static str {
+        match self {
+            Cloud::Public => "https://management.azure.com/",
+            Cloud::UsGov => "https://management.usgovcloudapi.net/",
+            Cloud::China => "https://management.chinacloudapi.cn/",
+        }
+    }
+
+    /// ARM management-plane base URL (no trailing slash).
+    pub fn arm_base(self) -> &'static str {
+        match self {
+            Cloud::Public => "https://management.azure.com",
+            Cloud::UsGov => "https://management.usgovcloudapi.net",
+            Cloud::China => "https://management.chinacloudapi.cn",
+        }
+    }
+
+    /// Host of the ARM management plane.
+    pub fn arm_host(self) -> &'static str {
+        self.arm_base().trim_start_matches("https://")
+    }
+
+    /// DNS suffix of Key Vault data-plane hosts (including the leading dot).
+    pub fn vault_suffix(self) -> &'static str {
+        match self {
+            Cloud::Public => ".vault.azure.net",
+            Cloud::UsGov => ".vault.usgovcloudapi.net",
+            Cloud::China => ".vault.azure.cn",
+        }
+    }
+
+    /// Maps an OpenID configuration `cloud_instance_name` (e.g.
+    /// `microsoftonline.us`) to its cloud.
+    pub fn from_instance_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "microsoftonline.com" => Some(Cloud::Public),
+            "microsoftonline.us" => Some(Cloud::UsGov),
+            "partner.microsoftonline.cn" | "chinacloudapi.cn" => Some(Cloud::China),
+            _ => None,
+        }
+    }
+
+    /// Key Vault data-plane token resource scope.
+    pub fn vault_scope(self) -> &'static str {
+        match self {
+            Cloud::Public => "https://vault.azure.net",
+            Cloud::UsGov => "https://vault.usgovcloudapi.net",
+            Cloud::China => "https://vault.azure.cn",
+        }
+    }
+}
+
+/// Effective cloud configuration for this process.
+#[derive(Debug, Clone)]
+pub struct CloudConfig {
+    pub cloud: Cloud,
+}
+
+impl Default for CloudConfig {
+    fn default() -> Self {
+        Self::new(Cloud::Public)
+    }
+}
+
+impl CloudConfig {
+    /// Creates a configuration for `cloud`.
+    pub fn new(cloud: Cloud) -> Self {
+        Self { cloud }
+    }
+
+    /// Reads `AZVAULT_CLOUD`, falling back to the public cloud for unset or
+    /// invalid values.
+    pub fn from_env() -> Self {
+        let cloud = match std::env::var(ENV_CLOUD) {
+            Ok(value) => Cloud::parse(&value).unwrap_or_else(|| {
+                log::warn!(
+                    "Ignoring unknown {}='{}'; using public cloud",
+                    ENV_CLOUD,
+                    value
+                );
+                Cloud::Public
+            }),
+            Err(_) => Cloud::Public,
+        };
+
+        Self::new(cloud)
+    }
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_openid_instance_names() {
+        assert_eq!(
+            Cloud::from_instance_name("microsoftonline.com"),
+            Some(Cloud::Public)
+        );
+        assert_eq!(
+            Cloud::from_instance_name("microsoftonline.us"),
+            Some(Cloud::UsGov)
+        );
+        assert_eq!(
+            Cloud::from_instance_name("partner.microsoftonline.cn"),
+            Some(Cloud::China)
+        );
+        assert_eq!(Cloud::from_instance_name("example.com"), None);
+    }
+
+    #[test]
+    fn parses_cloud_names_case_insensitively() {
+        assert_eq!(Cloud::parse("public"), Some(Cloud::Public));
+        assert_eq!(Cloud::parse("AzureCloud"), Some(Cloud::Public));
+        assert_eq!(Cloud::parse("USGOV"), Some(Cloud::UsGov));
+        assert_eq!(Cloud::parse("AzureChinaCloud"), Some(Cloud::China));
+        assert_eq!(Cloud::parse("mars"), None);
+    }
+
+    #[test]
+    fn scopes_match_cloud() {
+        assert_eq!(
+            Cloud::UsGov.management_scope(),
+            "https://management.usgovcloudapi.net/"
+        );
+        assert_eq!(Cloud::China.vault_scope(), "https://vault.azure.cn");
+        assert_eq!(Cloud::China.arm_host(), "management.chinacloudapi.cn");
+        assert_eq!(Cloud::UsGov.vault_suffix(), ".vault.usgovcloudapi.net");
+        assert_eq!(
+            CloudConfig::default().cloud.authority_host(),
+            "https://login.microsoftonline.com"
+        );
+    }
+}
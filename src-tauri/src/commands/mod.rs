@@ -7,19 +7,54 @@
 //! - Secret names are restricted to alphanumeric + dashes (Azure KV constraint).
 //! - Export payloads are size-bounded to prevent DoS via oversized input.
 //! - Audit fields are truncated to prevent log bloat from malicious input.
+//! - In offline mode, item listings are served from the metadata cache.
+//! - Commands that reach Azure or issue grants run their body (`*_inner`)
+//!   inside `correlation::scope`, so their requests and audit entries share
+//!   one `request_id`.
 
-use crate::audit::AuditLogger;
+use crate::audit::{AuditLevel, AuditLogger};
 use crate::auth::AuthManager;
-use crate::azure::AzureClient;
+use crate::azure::{self, AzureClient};
+use crate::backup;
+use crate::cache::MetadataCache;
+use crate::cloud::Cloud;
+use crate::correlation;
+use crate::ct::constant_time_eq;
+use crate::favorites::FavoritesStore;
+use crate::generate;
+use crate::grants::{mask_value, ItemGrants};
 use crate::models::*;
-use tauri::State;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use futures_util::stream::{self, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::RwLock;
 use url::Url;
+use zeroize::{Zeroize, Zeroizing};
 
 /// Shared application state managed by Tauri.
 pub struct AppState {
     pub auth: AuthManager,
     pub azure: AzureClient,
     pub audit: AuditLogger,
+    pub cache: MetadataCache,
+    /// When set, item listings are served from `cache` without network calls.
+    pub offline: AtomicBool,
+    /// Data-plane vault URI (normalised) → ARM resource, filled by discovery.
+    pub vault_index: RwLock<HashMap<String, KeyVaultInfo>>,
+    pub favorites: FavoritesStore,
+    /// Single-use tokens from `get_secret_value` for `reveal_secret_value`.
+    pub reveal_grants: ItemGrants,
+    /// Single-use confirmations from `request_purge` for `purge_secret`.
+    pub purge_grants: ItemGrants,
 }
 
 // ── Safety limits ──
@@ -27,12 +62,85 @@ pub struct AppState {
 /// Maximum size (bytes) of raw JSON input accepted by `export_items`.
 const MAX_EXPORT_INPUT_BYTES: usize = 2_000_000;
 
+/// Maximum size (bytes) of data accepted by `import_secrets` (enough for
+/// `MAX_BULK_ITEMS` values at the 25,000 character limit).
+const MAX_IMPORT_INPUT_BYTES: usize = 5_500_000;
+
 /// Maximum number of rows in a single export request.
 const MAX_EXPORT_ITEMS: usize = 20_000;
 
 /// Maximum character length for audit log fields before truncation.
 const MAX_AUDIT_FIELD_LEN: usize = 512;
 
+/// Maximum size (bytes) of an encoded certificate accepted by `import_certificate`.
+const MAX_CERTIFICATE_IMPORT_BYTES: usize = 256_000;
+
+/// Maximum number of items accepted by a single bulk command.
+const MAX_BULK_ITEMS: usize = 200;
+
+/// Error for direct edits of a certificate's backing secret.
+const MANAGED_SECRET_READ_ONLY: &str =
+    "This secret is managed by a certificate and cannot be modified directly.";
+
+/// Number of items processed concurrently by bulk commands.
+const BULK_CONCURRENCY: usize = 4;
+
+/// Formats accepted by `export_items`.
+const EXPORT_FORMATS: [&str; 3] = ["json", "csv", "ndjson"];
+
+/// Item types with list commands.
+const ITEM_TYPES: [&str; 3] = ["secret", "key", "certificate"];
+
+/// Mutating vault commands, as reported by `capabilities`.
+const MUTATIONS: [&str; 15] = [
+    "set_secret",
+    "ensure_secret",
+    "rotate_secret",
+    "import_secrets",
+    "set_secrets_tags",
+    "set_secrets_content_type",
+    "set_secrets_enabled",
+    "delete_secret",
+    "recover_secret",
+    "recover_all_deleted_secrets",
+    "purge_secret",
+    "purge_all_deleted_secrets",
+    "import_certificate",
+    "set_key_rotation_policy",
+    "recover_vault",
+];
+
+/// How long a reveal token from `get_secret_value` stays valid.
+pub const REVEAL_GRANT_TTL: Duration = Duration::from_secs(60);
+
+/// How long a purge confirmation from `request_purge` stays valid.
+pub const PURGE_GRANT_TTL: Duration = Duration::from_secs(30);
+
+/// Maximum number of matches returned by `search_items_by_tag`.
+const MAX_TAG_SEARCH_RESULTS: usize = 500;
+
+/// Most enabled secrets a value scan (`find_duplicate_secret_values`,
+/// `vault_value_size`) will read.
+const MAX_VALUE_SCAN_SECRETS: usize = 1000;
+
+/// Most required tag keys a tag policy may list (Key Vault allows 15 tags).
+const MAX_REQUIRED_TAG_KEYS: usize = 15;
+
+/// Key Vault tag limits: tags per item, and key/value lengths in characters.
+const MAX_ITEM_TAGS: usize = 15;
+const MAX_TAG_KEY_LEN: usize = 512;
+const MAX_TAG_VALUE_LEN: usize = 256;
+
+/// Algorithms accepted by `roundtrip_key_test` (RSA encryption).
+const KEY_ROUNDTRIP_ALGORITHMS: [&str; 3] = ["RSA-OAEP-256", "RSA-OAEP", "RSA1_5"];
+
+/// Longest window accepted by `expiring_certificates` (ten years).
+const MAX_EXPIRY_WINDOW_DAYS: u32 = 3650;
+
+/// Shown instead of listing deleted items on vaults without soft-delete.
+const SOFT_DELETE_DISABLED: &str =
+    "Soft-delete is not enabled on this vault, so there are no recoverable items.";
+
 // ─────────────────────────────────────────────
 // Auth Commands
 // ─────────────────────────────────────────────
@@ -70,6 +178,64 @@ pub async fn set_tenant(state: State<'_, AppState>, tenant_id: String) -> Result
     Ok(())
 }
 
+/// Prefetches the management and vault tokens in parallel (e.g. right
+/// after sign-in) so the first vault action does not wait on Azure CLI.
+#[tauri::command]
+pub async fn warm_tokens(state: State<'_, AppState>) -> Result<(), String> {
+    state.auth.warm_tokens().await
+}
+
+/// Decodes the current access token for `scope` (`"management"` or `"vault"`)
+/// and returns its header and claims for RBAC debugging.
+///
+/// # Security
+/// The token is decoded but not verified, and the raw token is never returned.
+#[tauri::command]
+pub async fn inspect_token(
+    state: State<'_, AppState>,
+    scope: String,
+) -> Result<TokenInspection, String> {
+    let token = match scope.as_str() {
+        "management" => state.auth.get_management_token().await?,
+        "vault" => state.auth.get_vault_token().await?,
+        _ => {
+            return Err(format!(
+                "Unsupported token scope: '{}'. Use 'management' or 'vault'.",
+                scope
+            ))
+        }
+    };
+
+    let result = AuthManager::decode_token(&token).map(|(header, claims)| TokenInspection {
+        scope: scope.clone(),
+        header,
+        claims,
+    });
+
+    state
+        .audit
+        .log_action(
+            "system",
+            "inspect_token",
+            "auth",
+            &scope,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    result
+}
+
+/// Returns the signed-in identity (object id, tenant, username, app id)
+/// from the management token's claims.
+#[tauri::command]
+pub async fn whoami(state: State<'_, AppState>) -> Result<Identity, String> {
+    let token = state.auth.get_management_token().await?;
+    let (_, claims) = AuthManager::decode_token(&token)?;
+    Ok(identity_from_claims(&claims))
+}
+
 // ─────────────────────────────────────────────
 // Resource Discovery Commands
 // ─────────────────────────────────────────────
@@ -77,15 +243,34 @@ pub async fn set_tenant(state: State<'_, AppState>, tenant_id: String) -> Result
 /// Lists Azure AD tenants accessible to the current identity.
 #[tauri::command]
 pub async fn list_tenants(state: State<'_, AppState>) -> Result<Vec<Tenant>, String> {
+    correlation::scope(list_tenants_inner(state)).await
+}
+
+async fn list_tenants_inner(state: State<'_, AppState>) -> Result<Vec<Tenant>, String> {
     let token = state.auth.get_management_token().await?;
     state.azure.list_tenants(&token).await
 }
 
 /// Lists Azure subscriptions accessible to the current identity.
+///
+/// With `state_filter`, only subscriptions in that state are returned
+/// (case-insensitive). Common states are `Enabled`, `Disabled`, `Warned`,
+/// and `PastDue`; without a filter every subscription is returned.
 #[tauri::command]
-pub async fn list_subscriptions(state: State<'_, AppState>) -> Result<Vec<Subscription>, String> {
+pub async fn list_subscriptions(
+    state: State<'_, AppState>,
+    state_filter: Option<String>,
+) -> Result<Vec<Subscription>, String> {
+    correlation::scope(list_subscriptions_inner(state, state_filter)).await
+}
+
+async fn list_subscriptions_inner(
+    state: State<'_, AppState>,
+    state_filter: Option<String>,
+) -> Result<Vec<Subscription>, String> {
     let token = state.auth.get_management_token().await?;
-    state.azure.list_subscriptions(&token).await
+    let subscriptions = state.azure.list_subscriptions(&token).await?;
+    Ok(filter_subscriptions(subscriptions, state_filter.as_deref()))
 }
 
 /// Lists Key Vault resources within a subscription.
@@ -93,6 +278,13 @@ pub async fn list_subscriptions(state: State<'_, AppState>) -> Result<Vec<Subscr
 pub async fn list_keyvaults(
     state: State<'_, AppState>,
     subscription_id: String,
+) -> Result<Vec<KeyVaultInfo>, String> {
+    correlation::scope(list_keyvaults_inner(state, subscription_id)).await
+}
+
+async fn list_keyvaults_inner(
+    state: State<'_, AppState>,
+    subscription_id: String,
 ) -> Result<Vec<KeyVaultInfo>, String> {
     let token = state.auth.get_management_token().await?;
     let result = state.azure.list_keyvaults(&token, &subscription_id).await;
@@ -100,6 +292,7 @@ pub async fn list_keyvaults(
     // Audit: log vault discovery results
     match &result {
         Ok(vaults) => {
+            index_vaults(&state.vault_index, vaults).await;
             state
                 .audit
                 .log_action(
@@ -130,627 +323,6176 @@ pub async fn list_keyvaults(
     result
 }
 
-// ─────────────────────────────────────────────
-// Vault Item Commands
-// ─────────────────────────────────────────────
-
-/// Lists all secrets in the specified vault.
+/// Lists Key Vault resources within a subscription bucketed by region.
+/// With `home_region`, that region's vaults are fetched and listed first;
+/// the remaining groups are ordered by location name.
 #[tauri::command]
-pub async fn list_secrets(
+pub async fn list_keyvaults_grouped(
     state: State<'_, AppState>,
-    vault_uri: String,
-) -> Result<Vec<SecretItem>, String> {
-    validate_vault_uri(&vault_uri)?;
-    let token = state.auth.get_vault_token().await?;
-    let vault_name = extract_vault_name(&vault_uri);
-    let result = state.azure.list_secrets(&token, &vault_uri).await;
-
-    state
-        .audit
-        .log_action(
-            &vault_name,
-            "list_secrets",
-            "secret",
-            "*",
-            result_status(&result),
-            None,
-        )
-        .await;
-
-    result
+    subscription_id: String,
+    home_region: Option<String>,
+) -> Result<Vec<VaultRegionGroup>, String> {
+    correlation::scope(list_keyvaults_grouped_inner(
+        state,
+        subscription_id,
+        home_region,
+    ))
+    .await
 }
 
-/// Lists all cryptographic keys in the specified vault.
-#[tauri::command]
-pub async fn list_keys(
+async fn list_keyvaults_grouped_inner(
     state: State<'_, AppState>,
-    vault_uri: String,
-) -> Result<Vec<KeyItem>, String> {
-    validate_vault_uri(&vault_uri)?;
-    let token = state.auth.get_vault_token().await?;
-    let vault_name = extract_vault_name(&vault_uri);
-    let result = state.azure.list_keys(&token, &vault_uri).await;
-
-    state
-        .audit
-        .log_action(
-            &vault_name,
-            "list_keys",
-            "key",
-            "*",
-            result_status(&result),
-            None,
-        )
-        .await;
+    subscription_id: String,
+    home_region: Option<String>,
+) -> Result<Vec<VaultRegionGroup>, String> {
+    let token = state.auth.get_management_token().await?;
+    let result = match &home_region {
+        Some(home) => {
+            state
+                .azure
+                .list_keyvaults_home_first(&token, &subscription_id, home)
+                .await
+        }
+        None => state.azure.list_keyvaults(&token, &subscription_id).await,
+    };
 
-    result
+    // Audit: log vault discovery results, as `list_keyvaults` does
+    match result {
+        Ok(vaults) => {
+            index_vaults(&state.vault_index, &vaults).await;
+            let count = vaults.len();
+            let groups = group_by_region(vaults, home_region.as_deref());
+            state
+                .audit
+                .log_action(
+                    "system",
+                    "list_keyvaults",
+                    "vault",
+                    &subscription_id,
+                    &format!("found {} vaults", count),
+                    Some(&format!("grouped into {} regions", groups.len())),
+                )
+                .await;
+            Ok(groups)
+        }
+        Err(e) => {
+            state
+                .audit
+                .log_action(
+                    "system",
+                    "list_keyvaults",
+                    "vault",
+                    &subscription_id,
+                    "error",
+                    Some(&e),
+                )
+                .await;
+            Err(e)
+        }
+    }
 }
 
-/// Lists all certificates in the specified vault.
+/// Lists soft-deleted vaults in a subscription, with their deletion and
+/// scheduled purge dates. Their items are unreachable until recovery.
 #[tauri::command]
-pub async fn list_certificates(
+pub async fn list_deleted_vaults(
     state: State<'_, AppState>,
-    vault_uri: String,
-) -> Result<Vec<CertificateItem>, String> {
-    validate_vault_uri(&vault_uri)?;
-    let token = state.auth.get_vault_token().await?;
-    let vault_name = extract_vault_name(&vault_uri);
-    let result = state.azure.list_certificates(&token, &vault_uri).await;
-
-    state
-        .audit
-        .log_action(
-            &vault_name,
-            "list_certificates",
-            "certificate",
-            "*",
-            result_status(&result),
-            None,
-        )
-        .await;
-
-    result
+    subscription_id: String,
+) -> Result<ListResult<DeletedVaultInfo>, String> {
+    correlation::scope(list_deleted_vaults_inner(state, subscription_id)).await
 }
 
-/// Fetches a secret's value from the data plane (sensitive – always audited).
-#[tauri::command]
-pub async fn get_secret_value(
+async fn list_deleted_vaults_inner(
     state: State<'_, AppState>,
-    vault_uri: String,
-    name: String,
-) -> Result<SecretValue, String> {
-    validate_vault_uri(&vault_uri)?;
-    validate_item_name(&name)?;
-    let token = state.auth.get_vault_token().await?;
-    let vault_name = extract_vault_name(&vault_uri);
+    subscription_id: String,
+) -> Result<ListResult<DeletedVaultInfo>, String> {
+    validate_subscription_id(&subscription_id)?;
+    let token = state.auth.get_management_token().await?;
 
     let result = state
         .azure
-        .get_secret_value(&token, &vault_uri, &name)
+        .list_deleted_vaults(&token, &subscription_id)
         .await;
 
-    // Always redact value details in audit
     state
         .audit
         .log_action(
-            &vault_name,
-            "get_secret_value",
-            "secret",
-            &name,
+            "system",
+            "list_deleted_vaults",
+            "vault",
+            &subscription_id,
             result_status(&result),
-            Some("[value retrieved - REDACTED]"),
+            result
+                .as_ref()
+                .ok()
+                .map(|listing| format!("found {} deleted vaults", listing.items.len()))
+                .as_deref(),
         )
         .await;
 
     result
 }
 
-/// Fetches secret metadata (without the value).
+/// Recovers a soft-deleted vault (with all its items) into its original
+/// resource group, in the tenant of the signed-in identity.
 #[tauri::command]
-pub async fn get_secret_metadata(
+pub async fn recover_vault(
     state: State<'_, AppState>,
-    vault_uri: String,
+    subscription_id: String,
+    location: String,
     name: String,
-) -> Result<SecretItem, String> {
-    validate_vault_uri(&vault_uri)?;
-    validate_item_name(&name)?;
-    let token = state.auth.get_vault_token().await?;
-    let vault_name = extract_vault_name(&vault_uri);
+) -> Result<KeyVaultInfo, String> {
+    correlation::scope(recover_vault_inner(state, subscription_id, location, name)).await
+}
+
+async fn recover_vault_inner(
+    state: State<'_, AppState>,
+    subscription_id: String,
+    location: String,
+    name: String,
+) -> Result<KeyVaultInfo, String> {
+    validate_subscription_id(&subscription_id)?;
+    validate_location(&location)?;
+    validate_vault_name(&name)?;
+    let token = state.auth.get_management_token().await?;
+    let (_, claims) = AuthManager::decode_token(&token)?;
+    let tenant_id = identity_from_claims(&claims)
+        .tenant_id
+        .ok_or_else(|| "Could not determine the signed-in tenant.".to_string())?;
 
     let result = state
         .azure
-        .get_secret_metadata(&token, &vault_uri, &name)
+        .recover_vault(&token, &subscription_id, &location, &name, &tenant_id)
         .await;
+    if let Ok(vault) = &result {
+        index_vaults(&state.vault_index, std::slice::from_ref(vault)).await;
+    }
 
     state
         .audit
         .log_action(
-            &vault_name,
-            "get_secret_metadata",
-            "secret",
+            &name,
+            "recover_vault",
+            "vault",
             &name,
             result_status(&result),
-            None,
+            Some(&format!("subscription {}, {}", subscription_id, location)),
         )
         .await;
 
     result
 }
 
-/// Creates or versions a secret.
+/// Finds items tagged `tag_key` (with `tag_value`, if given) across every
+/// vault in a subscription. Values are never fetched; listings run
+/// concurrently (bounded) and matches are capped at `MAX_TAG_SEARCH_RESULTS`.
 #[tauri::command]
-pub async fn set_secret(
+pub async fn search_items_by_tag(
     state: State<'_, AppState>,
-    vault_uri: String,
-    request: CreateSecretRequest,
-) -> Result<SecretItem, String> {
-    validate_vault_uri(&vault_uri)?;
-    validate_item_name(&request.name)?;
+    subscription_id: String,
+    tag_key: String,
+    tag_value: Option<String>,
+    item_types: Vec<String>,
+) -> Result<TagSearchResult, String> {
+    correlation::scope(search_items_by_tag_inner(
+        state,
+        subscription_id,
+        tag_key,
+        tag_value,
+        item_types,
+    ))
+    .await
+}
 
-    // Enforce value size limits (Azure KV limit is 25KB)
-    if request.value.is_empty() || request.value.len() > 25_000 {
-        return Err("Secret value must be between 1 and 25,000 characters.".to_string());
+async fn search_items_by_tag_inner(
+    state: State<'_, AppState>,
+    subscription_id: String,
+    tag_key: String,
+    tag_value: Option<String>,
+    item_types: Vec<String>,
+) -> Result<TagSearchResult, String> {
+    if tag_key.trim().is_empty() {
+        return Err("A tag key is required.".to_string());
+    }
+    if item_types.is_empty() {
+        return Err("Select at least one item type.".to_string());
+    }
+    if let Some(unknown) = item_types
+        .iter()
+        .find(|t| !ITEM_TYPES.contains(&t.as_str()))
+    {
+        return Err(format!(
+            "Unknown item type '{}'. Use secret, key, or certificate.",
+            unknown
+        ));
     }
 
+    let management_token = state.auth.get_management_token().await?;
+    let vaults = state
+        .azure
+        .list_keyvaults(&management_token, &subscription_id)
+        .await?;
+    index_vaults(&state.vault_index, &vaults).await;
     let token = state.auth.get_vault_token().await?;
-    let vault_name = extract_vault_name(&vault_uri);
-    let secret_name = request.name.clone();
 
-    let result = state.azure.set_secret(&token, &vault_uri, &request).await;
+    let app: &AppState = &state;
+    let (token, tag_key, tag_value) = (&token, &tag_key, tag_value.as_deref());
+    let jobs: Vec<(&KeyVaultInfo, &str)> = vaults
+        .iter()
+        .flat_map(|vault| item_types.iter().map(move |t| (vault, t.as_str())))
+        .collect();
+
+    let outcomes: Vec<(&KeyVaultInfo, Result<(bool, Vec<TaggedItem>), String>)> =
+        stream::iter(jobs)
+            .map(|(vault, item_type)| async move {
+                let listing = list_tagged_items(app, token, &vault.vault_uri, item_type).await;
+                (vault, listing)
+            })
+            .buffer_unordered(BULK_CONCURRENCY)
+            .collect()
+            .await;
+
+    let mut result = TagSearchResult {
+        matches: Vec::new(),
+        truncated: false,
+        errors: Vec::new(),
+    };
+    for (vault, outcome) in outcomes {
+        match outcome {
+            Ok((truncated, items)) => {
+                result.truncated |= truncated;
+                result.matches.extend(
+                    items
+                        .into_iter()
+                        .filter(|item| tag_matches(&item.tags, tag_key, tag_value))
+                        .map(|item| TagMatch {
+                            vault_name: vault.name.clone(),
+                            vault_uri: vault.vault_uri.clone(),
+                            item_type: item.item_type.to_string(),
+                            name: item.name,
+                            id: item.id,
+                        }),
+                );
+            }
+            Err(e) => result.errors.push(format!("{}: {}", vault.name, e)),
+        }
+    }
+    result.matches.sort_by(|a, b| {
+        (&a.vault_name, &a.item_type, &a.name).cmp(&(&b.vault_name, &b.item_type, &b.name))
+    });
+    if result.matches.len() > MAX_TAG_SEARCH_RESULTS {
+        result.matches.truncate(MAX_TAG_SEARCH_RESULTS);
+        result.truncated = true;
+    }
 
     state
         .audit
         .log_action(
-            &vault_name,
-            "set_secret",
-            "secret",
-            &secret_name,
-            result_status(&result),
-            Some("[value set - REDACTED]"),
+            "system",
+            "search_items_by_tag",
+            "vault",
+            &subscription_id,
+            if result.errors.is_empty() {
+                "success"
+            } else {
+                "partial"
+            },
+            Some(&format!(
+                "tag={} matches={} vaults={}",
+                tag_key,
+                result.matches.len(),
+                vaults.len()
+            )),
         )
         .await;
 
-    result
+    Ok(result)
 }
 
-/// Soft-deletes a secret.
-#[tauri::command]
-pub async fn delete_secret(
-    state: State<'_, AppState>,
-    vault_uri: String,
+/// Name, id, and tags of a listed item, for tag search.
+struct TaggedItem {
+    item_type: &'static str,
     name: String,
-) -> Result<(), String> {
-    validate_vault_uri(&vault_uri)?;
-    validate_item_name(&name)?;
-    let token = state.auth.get_vault_token().await?;
-    let vault_name = extract_vault_name(&vault_uri);
-
-    let result = state.azure.delete_secret(&token, &vault_uri, &name).await;
+    id: String,
+    tags: Option<HashMap<String, String>>,
+}
 
-    state
-        .audit
-        .log_action(
-            &vault_name,
-            "delete_secret",
-            "secret",
-            &name,
-            result_status(&result),
-            None,
-        )
-        .await;
+/// Lists one item type of a vault as `TaggedItem`s, with the listing's
+/// truncation flag.
+async fn list_tagged_items(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    item_type: &str,
+) -> Result<(bool, Vec<TaggedItem>), String> {
+    fn tagged<T>(
+        listing: ListResult<T>,
+        item_type: &'static str,
+        parts: fn(T) -> (String, String, Option<HashMap<String, String>>),
+    ) -> (bool, Vec<TaggedItem>) {
+        let items = listing
+            .items
+            .into_iter()
+            .map(|item| {
+                let (name, id, tags) = parts(item);
+                TaggedItem {
+                    item_type,
+                    name,
+                    id,
+                    tags,
+                }
+            })
+            .collect();
+        (listing.truncated, items)
+    }
 
-    result
+    match item_type {
+        "secret" => state
+            .azure
+            .list_secrets(token, vault_uri)
+            .await
+            .map(|l| tagged(l, "secret", |i| (i.name, i.id, i.tags))),
+        "key" => state
+            .azure
+            .list_keys(token, vault_uri)
+            .await
+            .map(|l| tagged(l, "key", |i| (i.name, i.id, i.tags))),
+        _ => state
+            .azure
+            .list_certificates(token, vault_uri)
+            .await
+            .map(|l| tagged(l, "certificate", |i| (i.name, i.id, i.tags))),
+    }
 }
 
-/// Recovers a soft-deleted secret.
+/// Resolves a data-plane vault URI to its ARM resource by searching the
+/// accessible subscriptions. Results are cached in `AppState::vault_index`.
+/// Subscriptions that cannot be searched are skipped, and reported only if
+/// the vault is not found elsewhere.
 #[tauri::command]
-pub async fn recover_secret(
+pub async fn resolve_vault(
     state: State<'_, AppState>,
     vault_uri: String,
-    name: String,
-) -> Result<(), String> {
-    validate_vault_uri(&vault_uri)?;
-    validate_item_name(&name)?;
-    let token = state.auth.get_vault_token().await?;
-    let vault_name = extract_vault_name(&vault_uri);
+) -> Result<KeyVaultInfo, String> {
+    correlation::scope(resolve_vault_inner(state, vault_uri)).await
+}
 
-    let result = state.azure.recover_secret(&token, &vault_uri, &name).await;
+async fn resolve_vault_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<KeyVaultInfo, String> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let key = vault_index_key(&vault_uri);
+    if let Some(info) = state.vault_index.read().await.get(&key) {
+        return Ok(info.clone());
+    }
 
-    state
-        .audit
-        .log_action(
-            &vault_name,
-            "recover_secret",
-            "secret",
-            &name,
-            result_status(&result),
-            None,
+    let vault_name = extract_vault_name(&vault_uri);
+    validate_item_name(&vault_name)?;
+    let token = state.auth.get_management_token().await?;
+
+    // One unreadable subscription must not hide the vault in another.
+    let mut errors = Vec::new();
+    for sub in state.azure.list_subscriptions(&token).await? {
+        let found = match state
+            .azure
+            .find_keyvault(&token, &sub.subscription_id, &vault_name)
+            .await
+        {
+            Ok(found) => found,
+            Err(e) => {
+                errors.push(format!("{}: {}", sub.subscription_id, e));
+                continue;
+            }
+        };
+        if let Some(info) = found.filter(|v| vault_index_key(&v.vault_uri) == key) {
+            index_vaults(&state.vault_index, std::slice::from_ref(&info)).await;
+            return Ok(info);
+        }
+    }
+
+    let mut message = format!(
+        "No vault matching '{}' was found in the accessible subscriptions.",
+        vault_name
+    );
+    if !errors.is_empty() {
+        message.push_str(&format!(
+            " {} subscription(s) could not be searched: {}",
+            errors.len(),
+            errors.join("; ")
+        ));
+    }
+    Err(message)
+}
+
+// ─────────────────────────────────────────────
+// Favorite Vault Commands
+// ─────────────────────────────────────────────
+
+/// Lists the locally pinned vault URIs.
+#[tauri::command]
+pub async fn list_favorite_vaults(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    Ok(state.favorites.list().await)
+}
+
+/// Pins a vault URI and returns the updated favorites.
+#[tauri::command]
+pub async fn add_favorite_vault(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<Vec<String>, String> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    state.favorites.add(&vault_uri).await
+}
+
+/// Unpins a vault URI and returns the updated favorites.
+#[tauri::command]
+pub async fn remove_favorite_vault(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<Vec<String>, String> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    state.favorites.remove(&vault_uri).await
+}
+
+// ─────────────────────────────────────────────
+// UI Context Commands
+// ─────────────────────────────────────────────
+
+/// Store file and key holding the last-used tenant/subscription/vault.
+const CONTEXT_STORE: &str = "context.json";
+const CONTEXT_KEY: &str = "lastContext";
+
+/// Persists the UI scope so it can be restored on the next launch. The
+/// tenant is also applied to the auth manager, as with `set_tenant`.
+#[tauri::command]
+pub async fn save_context(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    tenant_id: Option<String>,
+    subscription_id: Option<String>,
+    vault_uri: Option<String>,
+) -> Result<UiContext, String> {
+    let vault_uri = vault_uri
+        .filter(|uri| !uri.is_empty())
+        .map(|uri| validate_vault_uri(&uri))
+        .transpose()?;
+    let subscription_id = subscription_id.filter(|id| !id.is_empty());
+    if let Some(id) = &subscription_id {
+        validate_subscription_id(id)?;
+    }
+    let tenant_id = match tenant_id.filter(|id| !id.is_empty()) {
+        Some(id) => {
+            state.auth.set_tenant(&id).await;
+            Some(state.auth.get_tenant().await)
+        }
+        None => None,
+    };
+
+    let context = UiContext {
+        tenant_id,
+        subscription_id,
+        vault_uri,
+    };
+    let store = app.store(CONTEXT_STORE).map_err(|e| e.to_string())?;
+    store.set(
+        CONTEXT_KEY,
+        serde_json::to_value(&context).map_err(|e| e.to_string())?,
+    );
+    store
+        .save()
+        .map_err(|e| format!("Failed to save context: {}", e))?;
+    Ok(context)
+}
+
+/// Returns the last saved UI scope (empty if none) and re-applies its tenant.
+/// Stored values are re-validated; invalid entries are dropped.
+#[tauri::command]
+pub async fn load_context(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<UiContext, String> {
+    let store = app.store(CONTEXT_STORE).map_err(|e| e.to_string())?;
+    let context = stored_context(store.get(CONTEXT_KEY));
+    if let Some(tenant_id) = &context.tenant_id {
+        state.auth.set_tenant(tenant_id).await;
+    }
+    Ok(context)
+}
+
+// ─────────────────────────────────────────────
+// Tag Policy Commands
+// ─────────────────────────────────────────────
+
+/// Store file and key holding the required tag keys enforced by
+/// `set_secret`.
+const POLICY_STORE: &str = "policy.json";
+const REQUIRED_TAG_KEYS_KEY: &str = "requiredTagKeys";
+
+/// Returns the persisted required tag keys (empty if no policy is set).
+#[tauri::command]
+pub async fn get_required_tag_keys(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let store = app.store(POLICY_STORE).map_err(|e| e.to_string())?;
+    Ok(stored_tag_keys(store.get(REQUIRED_TAG_KEYS_KEY)))
+}
+
+/// Replaces the required tag keys. Keys are trimmed and de-duplicated; an
+/// empty list clears the policy.
+#[tauri::command]
+pub async fn set_required_tag_keys(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    keys: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let keys = normalize_tag_keys(keys)?;
+    let store = app.store(POLICY_STORE).map_err(|e| e.to_string())?;
+    store.set(REQUIRED_TAG_KEYS_KEY, serde_json::json!(keys));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save tag policy: {}", e))?;
+
+    state
+        .audit
+        .log_action(
+            "system",
+            "set_required_tag_keys",
+            "policy",
+            "tags",
+            "success",
+            Some(&keys.join(", ")),
         )
         .await;
+    Ok(keys)
+}
 
-    result
+/// Checks `tags` against `required_keys` without contacting Azure.
+#[tauri::command]
+pub fn validate_item_tags(
+    tags: Option<HashMap<String, String>>,
+    required_keys: Vec<String>,
+) -> Result<TagValidation, String> {
+    let required_keys = normalize_tag_keys(required_keys)?;
+    let missing = missing_tag_keys(tags.as_ref(), &required_keys);
+    Ok(TagValidation {
+        valid: missing.is_empty(),
+        missing,
+    })
 }
 
-/// Permanently purges a deleted secret (irreversible).
+// ─────────────────────────────────────────────
+// Vault Item Commands
+// ─────────────────────────────────────────────
+
+/// Lists all secrets in the specified vault (partial on a failed later page, cached while offline).
+/// `name_prefix` filters client-side (see `filter_by_name_prefix`).
 #[tauri::command]
-pub async fn purge_secret(
+pub async fn list_secrets(
     state: State<'_, AppState>,
     vault_uri: String,
-    name: String,
-) -> Result<(), String> {
-    validate_vault_uri(&vault_uri)?;
-    validate_item_name(&name)?;
+    name_prefix: Option<String>,
+) -> Result<ListResult<SecretItem>, CommandError> {
+    correlation::scope(list_secrets_inner(state, vault_uri, name_prefix)).await
+}
+
+async fn list_secrets_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name_prefix: Option<String>,
+) -> Result<ListResult<SecretItem>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let name_prefix = validate_name_prefix(name_prefix)?;
+    if state.offline.load(Ordering::Relaxed) {
+        let mut result: Result<ListResult<SecretItem>, String> =
+            serve_cached_listing(&state, &vault_uri, "secrets", "list_secrets", "secret").await;
+        filter_by_name_prefix(
+            &mut result,
+            name_prefix.as_deref(),
+            |item| item.name.as_str(),
+            None,
+        );
+        return result.map_err(CommandError::from);
+    }
+
     let token = state.auth.get_vault_token().await?;
     let vault_name = extract_vault_name(&vault_uri);
-
-    let result = state.azure.purge_secret(&token, &vault_uri, &name).await;
+    let mut result = state.azure.list_secrets(&token, &vault_uri).await;
+    cache_listing(&state.cache, &vault_uri, "secrets", &result);
+    let (status, details) = list_audit_outcome(&result);
+    let details = filter_by_name_prefix(
+        &mut result,
+        name_prefix.as_deref(),
+        |item| item.name.as_str(),
+        details,
+    );
 
     state
         .audit
         .log_action(
             &vault_name,
-            "purge_secret",
+            "list_secrets",
             "secret",
-            &name,
-            result_status(&result),
-            None,
+            "*",
+            status,
+            details.as_deref(),
         )
         .await;
 
-    result
+    result.map_err(CommandError::from)
 }
 
-// ─────────────────────────────────────────────
-// Audit Commands
-// ─────────────────────────────────────────────
-
-/// Returns the most recent audit log entries.
+/// Lists all cryptographic keys in the specified vault (partial on a failed later page, cached while offline).
+/// `name_prefix` filters client-side (see `filter_by_name_prefix`).
 #[tauri::command]
-pub async fn get_audit_log(
+pub async fn list_keys(
     state: State<'_, AppState>,
-    limit: Option<usize>,
-) -> Result<Vec<AuditEntry>, String> {
-    Ok(state.audit.get_entries(limit).await)
+    vault_uri: String,
+    name_prefix: Option<String>,
+) -> Result<ListResult<KeyItem>, CommandError> {
+    correlation::scope(list_keys_inner(state, vault_uri, name_prefix)).await
 }
 
-/// Alias for `get_audit_log` (backwards compatibility).
-#[tauri::command]
-pub async fn read_audit_log(
+async fn list_keys_inner(
     state: State<'_, AppState>,
-    limit: Option<usize>,
-) -> Result<Vec<AuditEntry>, String> {
-    get_audit_log(state, limit).await
+    vault_uri: String,
+    name_prefix: Option<String>,
+) -> Result<ListResult<KeyItem>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let name_prefix = validate_name_prefix(name_prefix)?;
+    if state.offline.load(Ordering::Relaxed) {
+        let mut result: Result<ListResult<KeyItem>, String> =
+            serve_cached_listing(&state, &vault_uri, "keys", "list_keys", "key").await;
+        filter_by_name_prefix(
+            &mut result,
+            name_prefix.as_deref(),
+            |item| item.name.as_str(),
+            None,
+        );
+        return result.map_err(CommandError::from);
+    }
+
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let mut result = state.azure.list_keys(&token, &vault_uri).await;
+    cache_listing(&state.cache, &vault_uri, "keys", &result);
+    let (status, details) = list_audit_outcome(&result);
+    let details = filter_by_name_prefix(
+        &mut result,
+        name_prefix.as_deref(),
+        |item| item.name.as_str(),
+        details,
+    );
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "list_keys",
+            "key",
+            "*",
+            status,
+            details.as_deref(),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
 }
 
-/// Writes a custom audit log entry (all fields are truncated for safety).
+/// Lists all certificates in the specified vault (partial on a failed later page, cached while offline).
+/// `name_prefix` filters client-side (see `filter_by_name_prefix`).
 #[tauri::command]
-pub async fn write_audit_log(
+pub async fn list_certificates(
     state: State<'_, AppState>,
-    vault_name: String,
-    action: String,
-    item_type: String,
-    item_name: String,
-    result: String,
-    details: Option<String>,
-) -> Result<(), String> {
-    let vault_name = truncate_for_audit(vault_name);
-    let action = truncate_for_audit(action);
-    let item_type = truncate_for_audit(item_type);
-    let item_name = truncate_for_audit(item_name);
-    let result = truncate_for_audit(result);
-    let details = details.map(truncate_for_audit);
+    vault_uri: String,
+    name_prefix: Option<String>,
+) -> Result<ListResult<CertificateItem>, CommandError> {
+    correlation::scope(list_certificates_inner(state, vault_uri, name_prefix)).await
+}
+
+async fn list_certificates_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name_prefix: Option<String>,
+) -> Result<ListResult<CertificateItem>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let name_prefix = validate_name_prefix(name_prefix)?;
+    if state.offline.load(Ordering::Relaxed) {
+        let mut result: Result<ListResult<CertificateItem>, String> = serve_cached_listing(
+            &state,
+            &vault_uri,
+            "certificates",
+            "list_certificates",
+            "certificate",
+        )
+        .await;
+        filter_by_name_prefix(
+            &mut result,
+            name_prefix.as_deref(),
+            |item| item.name.as_str(),
+            None,
+        );
+        return result.map_err(CommandError::from);
+    }
+
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let mut result = state.azure.list_certificates(&token, &vault_uri).await;
+    cache_listing(&state.cache, &vault_uri, "certificates", &result);
+    let (status, details) = list_audit_outcome(&result);
+    let details = filter_by_name_prefix(
+        &mut result,
+        name_prefix.as_deref(),
+        |item| item.name.as_str(),
+        details,
+    );
 
     state
         .audit
         .log_action(
             &vault_name,
-            &action,
-            &item_type,
-            &item_name,
-            &result,
+            "list_certificates",
+            "certificate",
+            "*",
+            status,
             details.as_deref(),
         )
         .await;
-    Ok(())
+
+    result.map_err(CommandError::from)
 }
 
-/// Returns the full audit log as sanitised JSON (suitable for export/clipboard).
+/// Lists secrets, keys, and certificates of a vault concurrently for the
+/// vault overview. Each type reports its own error, so one failing listing
+/// does not blank the others; a single audit entry summarises the counts.
 #[tauri::command]
-pub async fn export_audit_log(state: State<'_, AppState>) -> Result<String, String> {
-    Ok(state.audit.get_sanitized_export().await)
+pub async fn list_all_items(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<VaultItems, CommandError> {
+    correlation::scope(list_all_items_inner(state, vault_uri)).await
 }
 
-/// Clears all audit log entries from memory and disk.
+async fn list_all_items_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<VaultItems, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let offline = state.offline.load(Ordering::Relaxed);
+
+    let (secrets, keys, certificates) = if offline {
+        (
+            cached_listing(&state.cache, &vault_uri, "secrets"),
+            cached_listing(&state.cache, &vault_uri, "keys"),
+            cached_listing(&state.cache, &vault_uri, "certificates"),
+        )
+    } else {
+        let token = state.auth.get_vault_token().await?;
+        let (secrets, keys, certificates) = tokio::join!(
+            state.azure.list_secrets(&token, &vault_uri),
+            state.azure.list_keys(&token, &vault_uri),
+            state.azure.list_certificates(&token, &vault_uri)
+        );
+        cache_listing(&state.cache, &vault_uri, "secrets", &secrets);
+        cache_listing(&state.cache, &vault_uri, "keys", &keys);
+        cache_listing(&state.cache, &vault_uri, "certificates", &certificates);
+        (secrets, keys, certificates)
+    };
+
+    let (secrets, secrets_error) = split_listing(secrets);
+    let (keys, keys_error) = split_listing(keys);
+    let (certificates, certificates_error) = split_listing(certificates);
+    let items = VaultItems {
+        secrets,
+        secrets_error,
+        keys,
+        keys_error,
+        certificates,
+        certificates_error,
+    };
+
+    let (status, details) =
+        all_items_audit_outcome(&items.secrets, &items.keys, &items.certificates);
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "list_all_items",
+            "vault",
+            "*",
+            if offline && status != "error" {
+                "cached"
+            } else {
+                status
+            },
+            Some(&details),
+        )
+        .await;
+
+    Ok(items)
+}
+
+/// Fetches a secret's value from the data plane but returns only a masked
+/// preview plus a single-use reveal token (sensitive – always audited).
 #[tauri::command]
-pub async fn clear_audit_log(state: State<'_, AppState>) -> Result<(), String> {
-    state.audit.clear().await;
-    Ok(())
+pub async fn get_secret_value(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<MaskedSecretValue, CommandError> {
+    correlation::scope(get_secret_value_inner(state, vault_uri, name)).await
 }
 
-// ─────────────────────────────────────────────
-// Export Commands
-// ─────────────────────────────────────────────
+async fn get_secret_value_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<MaskedSecretValue, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
 
-/// Exports vault item metadata as JSON or CSV.
-///
-/// # Security
-/// - Input size is bounded to `MAX_EXPORT_INPUT_BYTES`.
-/// - Row count is bounded to `MAX_EXPORT_ITEMS`.
-/// - Only metadata is exported; secret values are never included.
+    let result = state
+        .azure
+        .get_secret_value(&token, &vault_uri, &name)
+        .await
+        .map(|secret| MaskedSecretValue {
+            preview: mask_value(&secret.value),
+            length: secret.value.chars().count(),
+            reveal_token: state.reveal_grants.issue(&vault_uri, &name),
+            id: secret.id,
+            name: secret.name,
+        });
+
+    // Always redact value details in audit
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "get_secret_value",
+            "secret",
+            &name,
+            result_status(&result),
+            Some("[masked preview retrieved - REDACTED]"),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Fetches the exact secret version named by a full id URL (e.g. from an
+/// export). As with `get_secret_value`, only a masked preview is returned,
+/// with a reveal token bound to that version (sensitive – always audited).
 #[tauri::command]
-pub async fn export_items(items_json: String, format: String) -> Result<String, String> {
-    if items_json.len() > MAX_EXPORT_INPUT_BYTES {
-        return Err(format!(
-            "Export payload too large (max {} bytes).",
-            MAX_EXPORT_INPUT_BYTES
-        ));
+pub async fn get_secret_value_by_id(
+    state: State<'_, AppState>,
+    id_url: String,
+) -> Result<MaskedSecretValue, CommandError> {
+    correlation::scope(get_secret_value_by_id_inner(state, id_url)).await
+}
+
+async fn get_secret_value_by_id_inner(
+    state: State<'_, AppState>,
+    id_url: String,
+) -> Result<MaskedSecretValue, CommandError> {
+    let (vault_uri, name, version) = state.azure.parse_secret_id(id_url.trim())?;
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    if let Some(version) = &version {
+        validate_item_version(version)?;
     }
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
 
-    let items: Vec<serde_json::Value> =
-        serde_json::from_str(&items_json).map_err(|e| format!("Invalid JSON: {}", e))?;
-    if items.len() > MAX_EXPORT_ITEMS {
-        return Err(format!(
-            "Too many items to export (max {}).",
-            MAX_EXPORT_ITEMS
-        ));
+    let fetched = match &version {
+        Some(version) => {
+            state
+                .azure
+                .get_secret_version_value(&token, &vault_uri, &name, version)
+                .await
+        }
+        None => {
+            state
+                .azure
+                .get_secret_value(&token, &vault_uri, &name)
+                .await
+        }
+    };
+    let grant_name = versioned_name(&name, version.as_deref());
+    let result = fetched.map(|secret| MaskedSecretValue {
+        preview: mask_value(&secret.value),
+        length: secret.value.chars().count(),
+        reveal_token: state.reveal_grants.issue(&vault_uri, &grant_name),
+        id: secret.id,
+        name: secret.name,
+    });
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "get_secret_value",
+            "secret",
+            &name,
+            result_status(&result),
+            Some(&format!(
+                "[masked preview retrieved - REDACTED] version={}",
+                version.as_deref().unwrap_or("latest")
+            )),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Returns a secret's full value after an explicit reveal, redeeming the
+/// token from `get_secret_value` or, with `version`, from
+/// `get_secret_value_by_id` (sensitive – audited separately).
+#[tauri::command]
+pub async fn reveal_secret_value(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    reveal_token: String,
+    version: Option<String>,
+) -> Result<SecretValue, CommandError> {
+    correlation::scope(reveal_secret_value_inner(
+        state,
+        vault_uri,
+        name,
+        reveal_token,
+        version,
+    ))
+    .await
+}
+
+async fn reveal_secret_value_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    reveal_token: String,
+    version: Option<String>,
+) -> Result<SecretValue, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    if let Some(version) = &version {
+        validate_item_version(version)?;
+    }
+    let vault_name = extract_vault_name(&vault_uri);
+    let grant_name = versioned_name(&name, version.as_deref());
+
+    let result = if state
+        .reveal_grants
+        .redeem(&reveal_token, &vault_uri, &grant_name)
+    {
+        let token = state.auth.get_vault_token().await?;
+        match &version {
+            Some(version) => {
+                state
+                    .azure
+                    .get_secret_version_value(&token, &vault_uri, &name, version)
+                    .await
+            }
+            None => {
+                state
+                    .azure
+                    .get_secret_value(&token, &vault_uri, &name)
+                    .await
+            }
+        }
+    } else {
+        Err("Reveal token is invalid or expired. Fetch the value again.".to_string())
+    };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "reveal_secret_value",
+            "secret",
+            &name,
+            result_status(&result),
+            Some("[value revealed - REDACTED]"),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Reads a certificate's exportable material (PFX/PEM with private key)
+/// from its managed backing secret, returning only a masked preview plus a
+/// single-use reveal token. Audited as a private-key read.
+#[tauri::command]
+pub async fn get_certificate_as_secret(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<MaskedCertificateSecret, CommandError> {
+    correlation::scope(get_certificate_as_secret_inner(state, vault_uri, name)).await
+}
+
+async fn get_certificate_as_secret_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<MaskedCertificateSecret, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state
+        .azure
+        .get_certificate_secret(&token, &vault_uri, &name)
+        .await
+        .map(|cert| MaskedCertificateSecret {
+            preview: mask_value(&cert.value),
+            length: cert.value.chars().count(),
+            reveal_token: state
+                .reveal_grants
+                .issue(&vault_uri, &certificate_grant_name(&name)),
+            id: cert.id,
+            name: cert.name,
+            content_type: cert.content_type,
+        });
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "get_certificate_as_secret",
+            "certificate",
+            &name,
+            result_status(&result),
+            Some("[masked private key preview retrieved - REDACTED]"),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Returns a certificate's full PFX/PEM material after an explicit reveal,
+/// redeeming the token from `get_certificate_as_secret`. Audited as a
+/// private-key export.
+#[tauri::command]
+pub async fn reveal_certificate_as_secret(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    reveal_token: String,
+) -> Result<CertificateSecret, CommandError> {
+    correlation::scope(reveal_certificate_as_secret_inner(
+        state,
+        vault_uri,
+        name,
+        reveal_token,
+    ))
+    .await
+}
+
+async fn reveal_certificate_as_secret_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    reveal_token: String,
+) -> Result<CertificateSecret, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result =
+        if state
+            .reveal_grants
+            .redeem(&reveal_token, &vault_uri, &certificate_grant_name(&name))
+        {
+            let token = state.auth.get_vault_token().await?;
+            state
+                .azure
+                .get_certificate_secret(&token, &vault_uri, &name)
+                .await
+        } else {
+            Err("Reveal token is invalid or expired. Fetch the certificate again.".to_string())
+        };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "reveal_certificate_as_secret",
+            "certificate",
+            &name,
+            result_status(&result),
+            Some("[private key material exported - REDACTED]"),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Fetches secret metadata (without the value).
+#[tauri::command]
+pub async fn get_secret_metadata(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<SecretItem, CommandError> {
+    correlation::scope(get_secret_metadata_inner(state, vault_uri, name)).await
+}
+
+async fn get_secret_metadata_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<SecretItem, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state
+        .azure
+        .get_secret_metadata(&token, &vault_uri, &name)
+        .await;
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "get_secret_metadata",
+            "secret",
+            &name,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Reports whether `name` is already taken, so the UI can warn that
+/// `set_secret` would add a new version rather than create a secret.
+/// A missing secret is `exists: false`, not an error.
+#[tauri::command]
+pub async fn secret_exists(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<SecretExistence, CommandError> {
+    correlation::scope(secret_exists_inner(state, vault_uri, name)).await
+}
+
+async fn secret_exists_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<SecretExistence, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = secret_existence(
+        state
+            .azure
+            .get_secret_metadata(&token, &vault_uri, &name)
+            .await,
+    );
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "secret_exists",
+            "secret",
+            &name,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Creates or versions a secret. With `validate_content`, the value is first
+/// checked against its content type (see `validate_secret_content`).
+#[tauri::command]
+pub async fn set_secret(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    request: CreateSecretRequest,
+    validate_content: Option<bool>,
+    allow_managed: Option<bool>,
+    enforce_tag_policy: Option<bool>,
+) -> Result<SecretItem, CommandError> {
+    correlation::scope(set_secret_inner(
+        app,
+        state,
+        vault_uri,
+        request,
+        validate_content,
+        allow_managed,
+        enforce_tag_policy,
+    ))
+    .await
+}
+
+async fn set_secret_inner(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    request: CreateSecretRequest,
+    validate_content: Option<bool>,
+    allow_managed: Option<bool>,
+    enforce_tag_policy: Option<bool>,
+) -> Result<SecretItem, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&request.name)?;
+
+    // Enforce value size limits (Azure KV limit is 25KB)
+    if request.value.is_empty() || request.value.len() > 25_000 {
+        return Err("Secret value must be between 1 and 25,000 characters.".into());
+    }
+    if validate_content.unwrap_or(false) {
+        validate_secret_content(request.content_type.as_deref(), &request.value)?;
+    }
+    if enforce_tag_policy.unwrap_or(false) {
+        let required = get_required_tag_keys(app).await?;
+        let missing = missing_tag_keys(request.tags.as_ref(), &required);
+        if !missing.is_empty() {
+            return Err(format!("Missing required tags: {}.", missing.join(", ")).into());
+        }
+    }
+
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let secret_name = request.name.clone();
+    let guard = ensure_not_managed(&state, &token, &vault_uri, &secret_name, allow_managed).await;
+
+    // A refused write is part of `result`, so it is audited too
+    let result = match guard {
+        Ok(()) => state.azure.set_secret(&token, &vault_uri, &request).await,
+        Err(e) => Err(e),
+    };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "set_secret",
+            "secret",
+            &secret_name,
+            result_status(&result),
+            Some("[value set - REDACTED]"),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Writes a secret only when its value, content type, tags, or attributes
+/// differ from the current version (or it does not exist yet), so
+/// idempotent deployments do not pile up redundant versions.
+#[tauri::command]
+pub async fn ensure_secret(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    request: CreateSecretRequest,
+) -> Result<EnsureSecretResult, CommandError> {
+    correlation::scope(ensure_secret_inner(state, vault_uri, request)).await
+}
+
+async fn ensure_secret_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    request: CreateSecretRequest,
+) -> Result<EnsureSecretResult, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&request.name)?;
+    if request.value.is_empty() || request.value.len() > 25_000 {
+        return Err("Secret value must be between 1 and 25,000 characters.".into());
+    }
+
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = ensure_secret_version(&state, &token, &vault_uri, &request).await;
+
+    let details = match &result {
+        Ok(outcome) => format!(
+            "[value ensured - REDACTED] new_version={}",
+            outcome.new_version
+        ),
+        Err(_) => "[value ensured - REDACTED]".to_string(),
+    };
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "ensure_secret",
+            "secret",
+            &request.name,
+            result_status(&result),
+            Some(&details),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Compares the current version with `request` and writes a new version
+/// only if something differs.
+async fn ensure_secret_version(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    request: &CreateSecretRequest,
+) -> Result<EnsureSecretResult, String> {
+    let current = match state
+        .azure
+        .get_secret_metadata(token, vault_uri, &request.name)
+        .await
+    {
+        Ok(current) => Some(current),
+        Err(e) if e.starts_with("[404]") => None,
+        Err(e) => return Err(e),
+    };
+
+    if let Some(current) = current {
+        // Disabled versions cannot be read, so they are always rewritten.
+        if current.enabled && !secret_attributes_differ(&current, request) {
+            let existing = state
+                .azure
+                .get_secret_value(token, vault_uri, &request.name)
+                .await?;
+            if constant_time_eq(existing.value.as_bytes(), request.value.as_bytes()) {
+                return Ok(EnsureSecretResult {
+                    new_version: false,
+                    item: current,
+                });
+            }
+        }
+    }
+
+    let item = state.azure.set_secret(token, vault_uri, request).await?;
+    Ok(EnsureSecretResult {
+        new_version: true,
+        item,
+    })
+}
+
+/// Rotates a secret to a freshly generated random value, creating a new
+/// version that keeps the current content type, tags, and attributes.
+/// The generated value is never returned or audited. Certificate-backed
+/// (managed) secrets are refused unless `allow_managed` is set.
+#[tauri::command]
+pub async fn rotate_secret(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    length: usize,
+    charset: String,
+    allow_managed: Option<bool>,
+) -> Result<SecretItem, CommandError> {
+    correlation::scope(rotate_secret_inner(
+        state,
+        vault_uri,
+        name,
+        length,
+        charset,
+        allow_managed,
+    ))
+    .await
+}
+
+async fn rotate_secret_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    length: usize,
+    charset: String,
+    allow_managed: Option<bool>,
+) -> Result<SecretItem, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let value = Zeroizing::new(generate::random_value(
+        length,
+        generate::Charset::parse(&charset)?,
+    )?);
+
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    // A refused managed secret is part of `result`, so it is audited too
+    let current = state
+        .azure
+        .get_current_secret_metadata(&token, &vault_uri, &name)
+        .await
+        .and_then(|current| {
+            if !allow_managed.unwrap_or(false) {
+                managed_guard(current.managed)?;
+            }
+            Ok(current)
+        });
+
+    let result = match current {
+        Ok(current) => {
+            let mut request = CreateSecretRequest {
+                name: name.clone(),
+                value: (*value).clone(),
+                content_type: current.content_type,
+                tags: current.tags,
+                enabled: Some(current.enabled),
+                expires: current.expires,
+                not_before: current.not_before,
+            };
+            let result = state.azure.set_secret(&token, &vault_uri, &request).await;
+            request.value.zeroize();
+            result
+        }
+        Err(e) => Err(e),
+    };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "rotate_secret",
+            "secret",
+            &name,
+            result_status(&result),
+            Some(&format!(
+                "[value rotated - REDACTED] length={} charset={}",
+                length, charset
+            )),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Generates a policy-compliant password locally (no network call) for the
+/// UI to review before calling `set_secret`.
+#[tauri::command]
+pub async fn generate_password(
+    length: usize,
+    include_symbols: bool,
+    include_digits: bool,
+    exclude_ambiguous: bool,
+) -> Result<String, String> {
+    generate::generate_password(length, include_symbols, include_digits, exclude_ambiguous)
+}
+
+/// Soft-deletes a secret.
+#[tauri::command]
+pub async fn delete_secret(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    allow_managed: Option<bool>,
+) -> Result<(), CommandError> {
+    correlation::scope(delete_secret_inner(state, vault_uri, name, allow_managed)).await
+}
+
+async fn delete_secret_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    allow_managed: Option<bool>,
+) -> Result<(), CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let guard = ensure_not_managed(&state, &token, &vault_uri, &name, allow_managed).await;
+
+    let result = match guard {
+        Ok(()) => state.azure.delete_secret(&token, &vault_uri, &name).await,
+        Err(e) => Err(e),
+    };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "delete_secret",
+            "secret",
+            &name,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Fetches a soft-deleted secret's deletion dates and original attributes
+/// so a recovery can be confirmed first.
+#[tauri::command]
+pub async fn get_deleted_secret(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<DeletedSecretItem, CommandError> {
+    correlation::scope(get_deleted_secret_inner(state, vault_uri, name)).await
+}
+
+async fn get_deleted_secret_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<DeletedSecretItem, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state
+        .azure
+        .get_deleted_secret(&token, &vault_uri, &name)
+        .await;
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "get_deleted_secret",
+            "secret",
+            &name,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Recovers a soft-deleted secret.
+#[tauri::command]
+pub async fn recover_secret(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<(), CommandError> {
+    correlation::scope(recover_secret_inner(state, vault_uri, name)).await
+}
+
+async fn recover_secret_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<(), CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state.azure.recover_secret(&token, &vault_uri, &name).await;
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "recover_secret",
+            "secret",
+            &name,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Issues a confirmation token that `purge_secret` requires within
+/// `PURGE_GRANT_TTL`, so an irreversible purge always takes two steps.
+#[tauri::command]
+pub async fn request_purge(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<String, CommandError> {
+    correlation::scope(request_purge_inner(state, vault_uri, name)).await
+}
+
+async fn request_purge_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<String, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let confirmation = state.purge_grants.issue(&vault_uri, &name);
+
+    state
+        .audit
+        .log_action(
+            &extract_vault_name(&vault_uri),
+            "request_purge",
+            "secret",
+            &name,
+            "success",
+            Some("purge confirmation issued"),
+        )
+        .await;
+
+    Ok(confirmation)
+}
+
+/// Permanently purges a deleted secret (irreversible). Requires a
+/// matching, unexpired `confirmation_token` from `request_purge`.
+#[tauri::command]
+pub async fn purge_secret(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    confirmation_token: String,
+) -> Result<(), CommandError> {
+    correlation::scope(purge_secret_inner(
+        state,
+        vault_uri,
+        name,
+        confirmation_token,
+    ))
+    .await
+}
+
+async fn purge_secret_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    confirmation_token: String,
+) -> Result<(), CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    // The token is fetched first so a CLI failure neither burns the
+    // confirmation nor skips the audit entry.
+    let token = state.auth.get_vault_token().await;
+    let result = match &token {
+        Ok(token) => {
+            if state
+                .purge_grants
+                .redeem(&confirmation_token, &vault_uri, &name)
+            {
+                state.azure.purge_secret(token, &vault_uri, &name).await
+            } else {
+                Err("Purge confirmation is invalid or expired. Request a new one.".to_string())
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "purge_secret",
+            "secret",
+            &name,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    token?;
+    result.map_err(CommandError::from)
+}
+
+/// Imports a PFX (base64 PKCS#12) or PEM certificate into the vault.
+///
+/// # Security
+/// The payload carries a private key: it is size-bounded, never logged,
+/// and the import is always audited with redacted details.
+#[tauri::command]
+pub async fn import_certificate(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    pfx_base64: String,
+    password: Option<String>,
+) -> Result<CertificateItem, CommandError> {
+    correlation::scope(import_certificate_inner(
+        state, vault_uri, name, pfx_base64, password,
+    ))
+    .await
+}
+
+async fn import_certificate_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    pfx_base64: String,
+    password: Option<String>,
+) -> Result<CertificateItem, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    validate_certificate_payload(&pfx_base64)?;
+
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state
+        .azure
+        .import_certificate(&token, &vault_uri, &name, &pfx_base64, password)
+        .await;
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "import_certificate",
+            "certificate",
+            &name,
+            result_status(&result),
+            Some("[private key imported - REDACTED]"),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Fetches a key's metadata and public JWK material (never private parts).
+#[tauri::command]
+pub async fn get_key(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<KeyDetails, CommandError> {
+    correlation::scope(get_key_inner(state, vault_uri, name)).await
+}
+
+async fn get_key_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<KeyDetails, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state.azure.get_key(&token, &vault_uri, &name).await;
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "get_key",
+            "key",
+            &name,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Fetches a key's automated rotation policy.
+#[tauri::command]
+pub async fn get_key_rotation_policy(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<KeyRotationPolicy, CommandError> {
+    correlation::scope(get_key_rotation_policy_inner(state, vault_uri, name)).await
+}
+
+async fn get_key_rotation_policy_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+) -> Result<KeyRotationPolicy, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state
+        .azure
+        .get_key_rotation_policy(&token, &vault_uri, &name)
+        .await;
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "get_key_rotation_policy",
+            "key",
+            &name,
+            result_status(&result),
+            None,
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Replaces a key's automated rotation policy after validating its actions.
+#[tauri::command]
+pub async fn set_key_rotation_policy(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    policy: KeyRotationPolicy,
+) -> Result<KeyRotationPolicy, CommandError> {
+    correlation::scope(set_key_rotation_policy_inner(
+        state, vault_uri, name, policy,
+    ))
+    .await
+}
+
+async fn set_key_rotation_policy_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    name: String,
+    policy: KeyRotationPolicy,
+) -> Result<KeyRotationPolicy, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&name)?;
+    validate_rotation_policy(&policy)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state
+        .azure
+        .set_key_rotation_policy(&token, &vault_uri, &name, &policy)
+        .await;
+
+    let summary: Vec<String> = policy
+        .lifetime_actions
+        .iter()
+        .map(|a| {
+            let trigger = a
+                .time_after_create
+                .as_deref()
+                .map(|d| format!("after create {}", d))
+                .or_else(|| {
+                    a.time_before_expiry
+                        .as_deref()
+                        .map(|d| format!("before expiry {}", d))
+                })
+                .unwrap_or_default();
+            format!("{} {}", a.action, trigger)
+        })
+        .collect();
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "set_key_rotation_policy",
+            "key",
+            &name,
+            result_status(&result),
+            Some(&summary.join("; ")),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Self-test for a crypto key: encrypts a random probe with the key's
+/// current version, decrypts it again, and reports whether the result
+/// matched.
+///
+/// # Security
+/// Only the boolean is returned or audited; the probe is zeroized.
+#[tauri::command]
+pub async fn roundtrip_key_test(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    key_name: String,
+    algorithm: String,
+) -> Result<bool, CommandError> {
+    correlation::scope(roundtrip_key_test_inner(
+        state, vault_uri, key_name, algorithm,
+    ))
+    .await
+}
+
+async fn roundtrip_key_test_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    key_name: String,
+    algorithm: String,
+) -> Result<bool, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_item_name(&key_name)?;
+    let algorithm = validate_roundtrip_algorithm(&algorithm)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = key_roundtrip(&state, &token, &vault_uri, &key_name, algorithm).await;
+
+    let outcome = match &result {
+        Ok(true) => "matched",
+        Ok(false) => "mismatch",
+        Err(_) => "failed",
+    };
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "roundtrip_key_test",
+            "key",
+            &key_name,
+            result_status(&result),
+            Some(&format!("{}: {}", algorithm, outcome)),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Encrypts and decrypts a random probe with the current version of
+/// `name` (helper for `roundtrip_key_test`).
+async fn key_roundtrip(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    name: &str,
+    algorithm: &str,
+) -> Result<bool, String> {
+    let key = state.azure.get_key(token, vault_uri, name).await?;
+    let version = roundtrip_key_version(&key.metadata)?;
+
+    let probe = Zeroizing::new(rand::random::<[u8; 32]>());
+    let ciphertext = state
+        .azure
+        .encrypt(token, vault_uri, name, version, algorithm, &probe[..])
+        .await?;
+    let decrypted = state
+        .azure
+        .decrypt(token, vault_uri, name, version, algorithm, &ciphertext)
+        .await?;
+    Ok(constant_time_eq(&probe[..], &decrypted))
+}
+
+/// Returns the canonical spelling of a supported roundtrip algorithm.
+fn validate_roundtrip_algorithm(algorithm: &str) -> Result<&'static str, String> {
+    KEY_ROUNDTRIP_ALGORITHMS
+        .iter()
+        .find(|alg| alg.eq_ignore_ascii_case(algorithm.trim()))
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "Unsupported algorithm '{}'. Use {}.",
+                algorithm,
+                KEY_ROUNDTRIP_ALGORITHMS.join(", ")
+            )
+        })
+}
+
+/// Checks that `key` is an enabled RSA key permitted to encrypt and
+/// decrypt, and returns its current version.
+fn roundtrip_key_version(key: &KeyItem) -> Result<&str, String> {
+    if !key.enabled {
+        return Err("Key is disabled.".to_string());
+    }
+    if !key
+        .key_type
+        .as_deref()
+        .is_some_and(|kty| kty.starts_with("RSA"))
+    {
+        return Err("Roundtrip tests support RSA keys only.".to_string());
+    }
+    let ops = key.key_ops.as_deref().unwrap_or_default();
+    for op in ["encrypt", "decrypt"] {
+        if !ops.iter().any(|permitted| permitted == op) {
+            return Err(format!("Key does not permit the '{}' operation.", op));
+        }
+    }
+    key.id
+        .rsplit('/')
+        .next()
+        .filter(|version| !version.is_empty() && *version != key.name)
+        .ok_or_else(|| "Key Vault returned no key version.".to_string())
+}
+
+/// Lists soft-deleted secrets in the specified vault. Short-circuits when
+/// the discovered vault is known to have soft-delete disabled.
+#[tauri::command]
+pub async fn list_deleted_secrets(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<ListResult<DeletedSecretItem>, CommandError> {
+    correlation::scope(list_deleted_secrets_inner(state, vault_uri)).await
+}
+
+async fn list_deleted_secrets_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<ListResult<DeletedSecretItem>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let soft_delete_enabled = state
+        .vault_index
+        .read()
+        .await
+        .get(&vault_index_key(&vault_uri))
+        .and_then(|v| v.soft_delete_enabled);
+
+    let result = if soft_delete_enabled == Some(false) {
+        Err(SOFT_DELETE_DISABLED.to_string())
+    } else {
+        let token = state.auth.get_vault_token().await?;
+        state
+            .azure
+            .list_deleted_secrets(&token, &vault_uri)
+            .await
+            .map_err(map_soft_delete_error)
+    };
+    let (status, details) = list_audit_outcome(&result);
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "list_deleted_secrets",
+            "secret",
+            "*",
+            status,
+            details.as_deref(),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Lists soft-deleted keys in the specified vault.
+#[tauri::command]
+pub async fn list_deleted_keys(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<ListResult<DeletedKeyItem>, CommandError> {
+    correlation::scope(list_deleted_keys_inner(state, vault_uri)).await
+}
+
+async fn list_deleted_keys_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<ListResult<DeletedKeyItem>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let result = state.azure.list_deleted_keys(&token, &vault_uri).await;
+    let (status, details) = list_audit_outcome(&result);
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "list_deleted_keys",
+            "key",
+            "*",
+            status,
+            details.as_deref(),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Lists soft-deleted certificates in the specified vault.
+#[tauri::command]
+pub async fn list_deleted_certificates(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<ListResult<DeletedCertificateItem>, CommandError> {
+    correlation::scope(list_deleted_certificates_inner(state, vault_uri)).await
+}
+
+async fn list_deleted_certificates_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<ListResult<DeletedCertificateItem>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let result = state
+        .azure
+        .list_deleted_certificates(&token, &vault_uri)
+        .await;
+    let (status, details) = list_audit_outcome(&result);
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "list_deleted_certificates",
+            "certificate",
+            "*",
+            status,
+            details.as_deref(),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Permanently purges every soft-deleted secret in a vault (irreversible).
+///
+/// # Safety
+/// - `confirm_phrase` must equal the vault name exactly.
+/// - Items whose recovery level is not purgeable (purge protection) are
+///   skipped with a recorded reason instead of failing.
+/// - Nothing is purged unless the deleted-secrets listing is complete.
+/// - Each purge is audited, plus a summary entry for the whole operation.
+#[tauri::command]
+pub async fn purge_all_deleted_secrets(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    confirm_phrase: String,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    correlation::scope(purge_all_deleted_secrets_inner(
+        window,
+        state,
+        vault_uri,
+        confirm_phrase,
+    ))
+    .await
+}
+
+async fn purge_all_deleted_secrets_inner(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    confirm_phrase: String,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let vault_name = extract_vault_name(&vault_uri);
+    if confirm_phrase != vault_name {
+        return Err(
+            "Confirmation phrase does not match the vault name. Nothing was purged.".into(),
+        );
+    }
+
+    let token = state.auth.get_vault_token().await?;
+    let deleted = complete_deleted_listing(
+        &state,
+        &token,
+        &vault_uri,
+        "purge_all_deleted_secrets",
+        "purged",
+    )
+    .await?;
+
+    let app: &AppState = &state;
+    let (token, vault_uri, vault_name_ref) = (&token, &vault_uri, &vault_name);
+    let mut progress = BulkProgress::new(&window, "purge_all_deleted_secrets", deleted.len());
+
+    let results: Vec<BulkItemResult> = stream::iter(deleted)
+        .map(|item| async move {
+            if !is_purgeable(item.recovery_level.as_deref()) {
+                return BulkItemResult {
+                    name: item.name,
+                    success: false,
+                    skipped: true,
+                    error: Some("Skipped: purge protection is enabled for this item.".to_string()),
+                };
+            }
+
+            let result = app.azure.purge_secret(token, vault_uri, &item.name).await;
+            app.audit
+                .log_action(
+                    vault_name_ref,
+                    "purge_secret",
+                    "secret",
+                    &item.name,
+                    result_status(&result),
+                    Some("bulk purge"),
+                )
+                .await;
+            bulk_item_result(item.name, result)
+        })
+        .map(correlation::scope)
+        .buffer_unordered(BULK_CONCURRENCY)
+        .inspect(|result| progress.item_done(&result.name))
+        .collect()
+        .await;
+    progress.finish(&results);
+
+    let (status, counts) = bulk_audit_outcome("purged", &results);
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "purge_all_deleted_secrets",
+            "secret",
+            "*",
+            status,
+            Some(&format!(
+                "DESTRUCTIVE bulk purge confirmed by vault name; {}",
+                counts
+            )),
+        )
+        .await;
+
+    Ok(results)
+}
+
+/// Recovers every soft-deleted secret in a vault, e.g. after a mistaken
+/// bulk delete. Recovery is reversible, so no confirmation is required.
+/// Each recovery is audited; failures (such as a live secret already using
+/// the name) are reported per item, plus a summary entry. Nothing is
+/// recovered unless the deleted-secrets listing is complete.
+#[tauri::command]
+pub async fn recover_all_deleted_secrets(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    correlation::scope(recover_all_deleted_secrets_inner(window, state, vault_uri)).await
+}
+
+async fn recover_all_deleted_secrets_inner(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let vault_name = extract_vault_name(&vault_uri);
+    let token = state.auth.get_vault_token().await?;
+    let deleted = complete_deleted_listing(
+        &state,
+        &token,
+        &vault_uri,
+        "recover_all_deleted_secrets",
+        "recovered",
+    )
+    .await?;
+
+    let app: &AppState = &state;
+    let (token, vault_uri, vault_name_ref) = (&token, &vault_uri, &vault_name);
+    let mut progress = BulkProgress::new(&window, "recover_all_deleted_secrets", deleted.len());
+
+    let results: Vec<BulkItemResult> = stream::iter(deleted)
+        .map(|item| async move {
+            let result = app.azure.recover_secret(token, vault_uri, &item.name).await;
+            app.audit
+                .log_action(
+                    vault_name_ref,
+                    "recover_secret",
+                    "secret",
+                    &item.name,
+                    result_status(&result),
+                    Some("bulk recover"),
+                )
+                .await;
+            bulk_item_result(item.name, result)
+        })
+        .map(correlation::scope)
+        .buffer_unordered(BULK_CONCURRENCY)
+        .inspect(|result| progress.item_done(&result.name))
+        .collect()
+        .await;
+    progress.finish(&results);
+
+    let (status, counts) = bulk_audit_outcome("recovered", &results);
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "recover_all_deleted_secrets",
+            "secret",
+            "*",
+            status,
+            Some(&counts),
+        )
+        .await;
+
+    Ok(results)
+}
+
+/// Checks (best-effort) whether the current identity can perform `action`
+/// by issuing a benign single-item list probe against the vault:
+/// `list_secrets`/`list_keys`/`list_certificates` probe the live collection,
+/// `recover`/`purge` probe `deletedsecrets`.
+#[tauri::command]
+pub async fn check_permission(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    action: String,
+) -> Result<PermissionCheck, CommandError> {
+    correlation::scope(check_permission_inner(state, vault_uri, action)).await
+}
+
+async fn check_permission_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    action: String,
+) -> Result<PermissionCheck, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let collection = probe_collection_for(&action)?;
+    let token = state.auth.get_vault_token().await?;
+
+    let probe = state
+        .azure
+        .probe_collection(&token, &vault_uri, collection)
+        .await;
+    let (access, detail) = match probe {
+        Ok(()) => (PermissionAccess::Allowed, None),
+        Err(e) => (permission_access_for(&e), Some(e)),
+    };
+
+    Ok(PermissionCheck {
+        action,
+        access,
+        detail,
+    })
+}
+
+/// Compares the secret inventories (names only, never values) of two vaults.
+/// Both listings run concurrently; an incomplete listing fails the
+/// comparison rather than reporting a misleading diff.
+#[tauri::command]
+pub async fn compare_vaults(
+    state: State<'_, AppState>,
+    vault_a: String,
+    vault_b: String,
+) -> Result<VaultComparison, CommandError> {
+    correlation::scope(compare_vaults_inner(state, vault_a, vault_b)).await
+}
+
+async fn compare_vaults_inner(
+    state: State<'_, AppState>,
+    vault_a: String,
+    vault_b: String,
+) -> Result<VaultComparison, CommandError> {
+    let vault_a = validate_vault_uri(&vault_a)?;
+    let vault_b = validate_vault_uri(&vault_b)?;
+    let token = state.auth.get_vault_token().await?;
+
+    let (list_a, list_b) = tokio::join!(
+        state.azure.list_secrets(&token, &vault_a),
+        state.azure.list_secrets(&token, &vault_b)
+    );
+    let result = secret_names(&vault_a, list_a).and_then(|names_a| {
+        secret_names(&vault_b, list_b).map(|names_b| compare_names(&names_a, &names_b))
+    });
+
+    state
+        .audit
+        .log_action(
+            &extract_vault_name(&vault_a),
+            "compare_vaults",
+            "vault",
+            &extract_vault_name(&vault_b),
+            result_status(&result),
+            result.as_ref().err().map(|e| e.as_str()),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Lists certificates that expire within `within_days` (or have already
+/// expired), soonest first. An incomplete listing fails the report rather
+/// than silently omitting certificates.
+#[tauri::command]
+pub async fn expiring_certificates(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    within_days: u32,
+) -> Result<Vec<ExpiringCertificate>, CommandError> {
+    correlation::scope(expiring_certificates_inner(state, vault_uri, within_days)).await
+}
+
+async fn expiring_certificates_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    within_days: u32,
+) -> Result<Vec<ExpiringCertificate>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    if within_days > MAX_EXPIRY_WINDOW_DAYS {
+        return Err(format!(
+            "The expiry window must be at most {} days.",
+            MAX_EXPIRY_WINDOW_DAYS
+        )
+        .into());
+    }
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state
+        .azure
+        .list_certificates(&token, &vault_uri)
+        .await
+        .and_then(|listing| {
+            if listing.truncated {
+                return Err(format!(
+                    "Could not list all certificates in {}: {}",
+                    vault_name,
+                    listing.error.unwrap_or_default()
+                ));
+            }
+            Ok(expiring_within(
+                listing.items,
+                within_days,
+                chrono::Utc::now().timestamp(),
+            ))
+        });
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "expiring_certificates",
+            "certificate",
+            "*",
+            result_status(&result),
+            Some(&format!("within_days={}", within_days)),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Lists enabled secrets whose `not_before` is still in the future (so they
+/// cannot be read yet), soonest activation first. An incomplete listing
+/// fails the report rather than silently omitting secrets.
+#[tauri::command]
+pub async fn not_yet_active_secrets(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<Vec<SecretItem>, CommandError> {
+    correlation::scope(not_yet_active_secrets_inner(state, vault_uri)).await
+}
+
+async fn not_yet_active_secrets_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<Vec<SecretItem>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let result = state
+        .azure
+        .list_secrets(&token, &vault_uri)
+        .await
+        .and_then(|listing| {
+            if listing.truncated {
+                return Err(format!(
+                    "Could not list all secrets in {}: {}",
+                    vault_name,
+                    listing.error.unwrap_or_default()
+                ));
+            }
+            Ok(not_yet_active(
+                listing.items,
+                chrono::Utc::now().timestamp(),
+            ))
+        });
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "not_yet_active_secrets",
+            "secret",
+            "*",
+            result_status(&result),
+            result
+                .as_ref()
+                .ok()
+                .map(|secrets| format!("{} not yet active", secrets.len()))
+                .as_deref(),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Counts secrets, keys, and certificates of several vaults for the
+/// dashboard. Vaults are processed concurrently (bounded) and each vault's
+/// failure is reported in its own entry.
+#[tauri::command]
+pub async fn vault_item_counts(
+    state: State<'_, AppState>,
+    vault_uris: Vec<String>,
+) -> Result<Vec<VaultItemCounts>, CommandError> {
+    correlation::scope(vault_item_counts_inner(state, vault_uris)).await
+}
+
+async fn vault_item_counts_inner(
+    state: State<'_, AppState>,
+    vault_uris: Vec<String>,
+) -> Result<Vec<VaultItemCounts>, CommandError> {
+    validate_bulk_size(vault_uris.len())?;
+    let token = state.auth.get_vault_token().await?;
+
+    let app: &AppState = &state;
+    let token = &token;
+
+    let results: Vec<VaultItemCounts> = stream::iter(vault_uris)
+        .map(|vault_uri| async move {
+            let counts = match validate_vault_uri(&vault_uri) {
+                Ok(uri) => {
+                    let (secrets, keys, certificates) = tokio::join!(
+                        app.azure.list_secrets(token, &uri),
+                        app.azure.list_keys(token, &uri),
+                        app.azure.list_certificates(token, &uri)
+                    );
+                    listing_count(secrets).and_then(|secrets| {
+                        let keys = listing_count(keys)?;
+                        let certificates = listing_count(certificates)?;
+                        Ok((secrets, keys, certificates))
+                    })
+                }
+                Err(e) => Err(e),
+            };
+            app.audit
+                .log_action(
+                    &extract_vault_name(&vault_uri),
+                    "vault_item_counts",
+                    "vault",
+                    "*",
+                    result_status(&counts),
+                    counts.as_ref().err().map(|e| e.as_str()),
+                )
+                .await;
+
+            match counts {
+                Ok((secrets, keys, certificates)) => VaultItemCounts {
+                    vault_uri,
+                    secrets: Some(secrets),
+                    keys: Some(keys),
+                    certificates: Some(certificates),
+                    error: None,
+                },
+                Err(e) => VaultItemCounts {
+                    vault_uri,
+                    secrets: None,
+                    keys: None,
+                    certificates: None,
+                    error: Some(e),
+                },
+            }
+        })
+        .map(correlation::scope)
+        .buffered(BULK_CONCURRENCY)
+        .collect()
+        .await;
+
+    Ok(results)
+}
+
+/// Sets tags on many secrets at once (PATCH, no new versions).
+///
+/// With `merge`, the given tags are merged into each secret's existing tags
+/// (fetched first); otherwise they replace them, and an empty replace is
+/// rejected. Certificate-managed secrets, and updates that would remove a
+/// key required by the tag policy, fail with an error. Each secret is
+/// audited individually and reported in the per-item results.
+#[tauri::command]
+pub async fn set_secrets_tags(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    names: Vec<String>,
+    tags: HashMap<String, String>,
+    merge: bool,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    correlation::scope(set_secrets_tags_inner(
+        window, state, vault_uri, names, tags, merge,
+    ))
+    .await
+}
+
+async fn set_secrets_tags_inner(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    names: Vec<String>,
+    tags: HashMap<String, String>,
+    merge: bool,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_bulk_size(names.len())?;
+    validate_tags(&tags)?;
+    if !merge && tags.is_empty() {
+        return Err("Replacing with no tags would remove every tag. Give at least one tag.".into());
+    }
+    let required = get_required_tag_keys(window.app_handle().clone()).await?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let app: &AppState = &state;
+    let (token, vault_uri, vault_name, tags, required) =
+        (&token, &vault_uri, &vault_name, &tags, required.as_slice());
+
+    let mut progress = BulkProgress::new(&window, "set_secrets_tags", names.len());
+
+    let results: Vec<BulkItemResult> = stream::iter(names)
+        .map(|name| async move {
+            let result =
+                update_secret_tags(app, token, vault_uri, &name, tags, merge, required).await;
+            app.audit
+                .log_action(
+                    vault_name,
+                    "set_secret_tags",
+                    "secret",
+                    &name,
+                    result_status(&result),
+                    Some(if merge {
+                        "tags merged"
+                    } else {
+                        "tags replaced"
+                    }),
+                )
+                .await;
+            bulk_item_result(name, result)
+        })
+        .map(correlation::scope)
+        .buffer_unordered(BULK_CONCURRENCY)
+        .inspect(|result| progress.item_done(&result.name))
+        .collect()
+        .await;
+    progress.finish(&results);
+
+    Ok(results)
+}
+
+/// Applies a tag update to a single secret's current version (helper for
+/// `set_secrets_tags`), refusing certificate-managed secrets and updates
+/// that remove a `required` tag key.
+async fn update_secret_tags(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    name: &str,
+    tags: &HashMap<String, String>,
+    merge: bool,
+    required: &[String],
+) -> Result<SecretItem, String> {
+    validate_item_name(name)?;
+    let current = state
+        .azure
+        .get_current_secret_metadata(token, vault_uri, name)
+        .await?;
+    managed_guard(current.managed)?;
+
+    let tags = if merge {
+        merge_tags(current.tags.clone(), tags)
+    } else {
+        tags.clone()
+    };
+    validate_tags(&tags)?;
+    let removed = removed_required_tag_keys(current.tags.as_ref(), &tags, required);
+    if !removed.is_empty() {
+        return Err(format!(
+            "Update would remove required tags: {}.",
+            removed.join(", ")
+        ));
+    }
+
+    state
+        .azure
+        .update_secret(token, vault_uri, name, serde_json::json!({ "tags": tags }))
+        .await
+}
+
+/// Sets `contentType` on many secrets' current versions at once (PATCH, no
+/// new versions). Certificate-managed secrets are skipped with an error.
+/// Each secret is audited individually and reported in the per-item results.
+#[tauri::command]
+pub async fn set_secrets_content_type(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    names: Vec<String>,
+    content_type: String,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    correlation::scope(set_secrets_content_type_inner(
+        window,
+        state,
+        vault_uri,
+        names,
+        content_type,
+    ))
+    .await
+}
+
+async fn set_secrets_content_type_inner(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    names: Vec<String>,
+    content_type: String,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_bulk_size(names.len())?;
+    let content_type = validate_mime_type(&content_type)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let app: &AppState = &state;
+    let (token, vault_uri, vault_name, content_type) =
+        (&token, &vault_uri, &vault_name, content_type.as_str());
+    let details = format!("contentType: {}", content_type);
+    let details = details.as_str();
+
+    let mut progress = BulkProgress::new(&window, "set_secrets_content_type", names.len());
+
+    let results: Vec<BulkItemResult> = stream::iter(names)
+        .map(|name| async move {
+            let result =
+                update_secret_content_type(app, token, vault_uri, &name, content_type).await;
+            app.audit
+                .log_action(
+                    vault_name,
+                    "set_secret_content_type",
+                    "secret",
+                    &name,
+                    result_status(&result),
+                    Some(details),
+                )
+                .await;
+            bulk_item_result(name, result)
+        })
+        .map(correlation::scope)
+        .buffer_unordered(BULK_CONCURRENCY)
+        .inspect(|result| progress.item_done(&result.name))
+        .collect()
+        .await;
+    progress.finish(&results);
+
+    Ok(results)
+}
+
+/// Sets `contentType` on a single secret (helper for
+/// `set_secrets_content_type`), refusing certificate-managed secrets.
+async fn update_secret_content_type(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    name: &str,
+    content_type: &str,
+) -> Result<SecretItem, String> {
+    validate_item_name(name)?;
+    let current = state
+        .azure
+        .get_secret_metadata(token, vault_uri, name)
+        .await?;
+    managed_guard(current.managed)?;
+    state
+        .azure
+        .update_secret(
+            token,
+            vault_uri,
+            name,
+            serde_json::json!({ "contentType": content_type }),
+        )
+        .await
+}
+
+/// Creates secrets from a JSON array of `{name, value, tags?, contentType?}`
+/// objects or a CSV with a `name,value[,contentType]` header.
+///
+/// Without `overwrite`, secrets that already exist are skipped; with it, a
+/// new version is written (certificate-managed secrets are never touched).
+/// Each write is audited as `set_secret` and reported in the per-item
+/// results.
+///
+/// # Security
+/// - Input size is bounded to `MAX_IMPORT_INPUT_BYTES` and row count to
+///   `MAX_BULK_ITEMS`.
+/// - The raw input and each value are zeroized once processed.
+#[tauri::command]
+pub async fn import_secrets(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    data: String,
+    format: String,
+    overwrite: bool,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    correlation::scope(import_secrets_inner(
+        window, state, vault_uri, data, format, overwrite,
+    ))
+    .await
+}
+
+async fn import_secrets_inner(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    data: String,
+    format: String,
+    overwrite: bool,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let data = Zeroizing::new(data);
+    if data.len() > MAX_IMPORT_INPUT_BYTES {
+        return Err(format!(
+            "Import data too large (max {} bytes).",
+            MAX_IMPORT_INPUT_BYTES
+        )
+        .into());
+    }
+    let requests = parse_import(&data, &format)?;
+    drop(data);
+    validate_bulk_size(requests.len())?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let app: &AppState = &state;
+    let (token, vault_uri, vault_name) = (&token, &vault_uri, &vault_name);
+    let mut progress = BulkProgress::new(&window, "import_secrets", requests.len());
+
+    let results: Vec<BulkItemResult> = stream::iter(requests)
+        .map(|mut request| async move {
+            let result = import_secret(app, token, vault_uri, &request, overwrite).await;
+            request.value.zeroize();
+            let name = request.name;
+            match result {
+                Ok(None) => BulkItemResult {
+                    name,
+                    success: false,
+                    skipped: true,
+                    error: Some("Skipped: secret already exists.".to_string()),
+                },
+                result => {
+                    app.audit
+                        .log_action(
+                            vault_name,
+                            "set_secret",
+                            "secret",
+                            &name,
+                            result_status(&result),
+                            Some("[value imported - REDACTED]"),
+                        )
+                        .await;
+                    bulk_item_result(name, result)
+                }
+            }
+        })
+        .map(correlation::scope)
+        .buffer_unordered(BULK_CONCURRENCY)
+        .inspect(|result| progress.item_done(&result.name))
+        .collect()
+        .await;
+    progress.finish(&results);
+
+    Ok(results)
+}
+
+/// Writes one imported secret (helper for `import_secrets`). `Ok(None)`
+/// means it already exists and `overwrite` is off.
+async fn import_secret(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    request: &CreateSecretRequest,
+    overwrite: bool,
+) -> Result<Option<SecretItem>, String> {
+    validate_item_name(&request.name)?;
+    if request.value.is_empty() || request.value.len() > 25_000 {
+        return Err("Secret value must be between 1 and 25,000 characters.".to_string());
+    }
+    match state
+        .azure
+        .get_secret_metadata(token, vault_uri, &request.name)
+        .await
+    {
+        Ok(_) if !overwrite => return Ok(None),
+        Ok(current) => managed_guard(current.managed)?,
+        Err(e) if e.starts_with("[404]") => {}
+        Err(e) => return Err(e),
+    }
+
+    state
+        .azure
+        .set_secret(token, vault_uri, request)
+        .await
+        .map(Some)
+}
+
+/// Enables or disables many secrets at once (PATCH, no new versions).
+///
+/// Each secret's current state is read first so its audit entry records the
+/// old and new state; results are reported per item.
+#[tauri::command]
+pub async fn set_secrets_enabled(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    names: Vec<String>,
+    enabled: bool,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    correlation::scope(set_secrets_enabled_inner(
+        window, state, vault_uri, names, enabled,
+    ))
+    .await
+}
+
+async fn set_secrets_enabled_inner(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    names: Vec<String>,
+    enabled: bool,
+) -> Result<Vec<BulkItemResult>, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    validate_bulk_size(names.len())?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let app: &AppState = &state;
+    let (token, vault_uri, vault_name) = (&token, &vault_uri, &vault_name);
+
+    let mut progress = BulkProgress::new(&window, "set_secrets_enabled", names.len());
+
+    let results: Vec<BulkItemResult> = stream::iter(names)
+        .map(|name| async move {
+            let (previous, result) =
+                update_secret_enabled(app, token, vault_uri, &name, enabled).await;
+            app.audit
+                .log_action(
+                    vault_name,
+                    "set_secret_enabled",
+                    "secret",
+                    &name,
+                    result_status(&result),
+                    Some(&enabled_transition(previous, enabled)),
+                )
+                .await;
+            bulk_item_result(name, result)
+        })
+        .map(correlation::scope)
+        .buffer_unordered(BULK_CONCURRENCY)
+        .inspect(|result| progress.item_done(&result.name))
+        .collect()
+        .await;
+    progress.finish(&results);
+
+    Ok(results)
+}
+
+/// Sets `enabled` on a single secret (helper for `set_secrets_enabled`),
+/// returning the previous state when it could be read.
+async fn update_secret_enabled(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    name: &str,
+    enabled: bool,
+) -> (Option<bool>, Result<SecretItem, String>) {
+    if let Err(e) = validate_item_name(name) {
+        return (None, Err(e));
+    }
+    let previous = match state
+        .azure
+        .get_secret_metadata(token, vault_uri, name)
+        .await
+    {
+        Ok(current) => current.enabled,
+        Err(e) => return (None, Err(e)),
+    };
+    let result = state
+        .azure
+        .update_secret(
+            token,
+            vault_uri,
+            name,
+            serde_json::json!({ "attributes": { "enabled": enabled } }),
+        )
+        .await;
+    (Some(previous), result)
+}
+
+/// Audit detail for an enabled-state change, e.g. `enabled: true -> false`.
+fn enabled_transition(previous: Option<bool>, enabled: bool) -> String {
+    match previous {
+        Some(previous) => format!("enabled: {} -> {}", previous, enabled),
+        None => format!("enabled: ? -> {}", enabled),
+    }
+}
+
+/// Finds secrets in a vault that share the same value.
+///
+/// # Security
+/// Every enabled secret's value is read, hashed with a per-call random salt,
+/// and zeroized immediately. Neither values nor hashes leave this function;
+/// only groups of names are returned. Audited as a sensitive bulk read.
+#[tauri::command]
+pub async fn find_duplicate_secret_values(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<DuplicateValueReport, CommandError> {
+    correlation::scope(find_duplicate_secret_values_inner(state, vault_uri)).await
+}
+
+async fn find_duplicate_secret_values_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+) -> Result<DuplicateValueReport, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let token = state.auth.get_vault_token().await?;
+    let vault_name = extract_vault_name(&vault_uri);
+
+    let listing = state.azure.list_secrets(&token, &vault_uri).await?;
+    let names: Vec<String> = listing
+        .items
+        .into_iter()
+        .filter(|item| item.enabled)
+        .map(|item| item.name)
+        .collect();
+    if names.len() > MAX_VALUE_SCAN_SECRETS {
+        return Err(format!(
+            "Vault has {} enabled secrets; duplicate scans support at most {}.",
+            names.len(),
+            MAX_VALUE_SCAN_SECRETS
+        )
+        .into());
+    }
+
+    let app: &AppState = &state;
+    let (token, vault_uri) = (&token, &vault_uri);
+    let salt: [u8; 32] = rand::random();
+    let salt = &salt;
+
+    let hashed: Vec<(String, Result<[u8; 32], String>)> = stream::iter(names)
+        .map(|name| async move {
+            let digest = app
+                .azure
+                .get_secret_value(token, vault_uri, &name)
+                .await
+                .map(|secret| salted_value_hash(salt, secret.value));
+            (name, digest)
+        })
+        .buffer_unordered(BULK_CONCURRENCY)
+        .collect()
+        .await;
+
+    let scanned = hashed.iter().filter(|(_, digest)| digest.is_ok()).count();
+    let mut errors = Vec::new();
+    let mut digests = Vec::new();
+    for (name, digest) in hashed {
+        match digest {
+            Ok(digest) => digests.push((name, digest)),
+            Err(e) => errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    let report = DuplicateValueReport {
+        groups: group_by_digest(digests),
+        scanned,
+        truncated: listing.truncated,
+        errors,
+    };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "find_duplicate_secret_values",
+            "secret",
+            "*",
+            if report.errors.is_empty() {
+                "success"
+            } else {
+                "partial"
+            },
+            Some(&format!(
+                "scanned {} values, {} duplicate groups",
+                report.scanned,
+                report.groups.len()
+            )),
+        )
+        .await;
+
+    Ok(report)
+}
+
+/// Measures the total, average, and largest secret value size in a vault,
+/// e.g. for migration planning.
+///
+/// # Security
+/// - `confirm_phrase` must be the vault name, since every enabled secret's
+///   value is read.
+/// - Each value is measured and zeroized immediately; only sizes and names
+///   are returned. Audited as a sensitive bulk read.
+#[tauri::command]
+pub async fn vault_value_size(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    confirm_phrase: String,
+) -> Result<ValueSizeReport, CommandError> {
+    correlation::scope(vault_value_size_inner(state, vault_uri, confirm_phrase)).await
+}
+
+async fn vault_value_size_inner(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    confirm_phrase: String,
+) -> Result<ValueSizeReport, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let vault_name = extract_vault_name(&vault_uri);
+    if confirm_phrase.trim() != vault_name {
+        return Err("Type the vault name to confirm reading every secret value.".into());
+    }
+    let token = state.auth.get_vault_token().await?;
+
+    let listing = state.azure.list_secrets(&token, &vault_uri).await?;
+    let names: Vec<String> = listing
+        .items
+        .into_iter()
+        .filter(|item| item.enabled)
+        .map(|item| item.name)
+        .collect();
+    if names.len() > MAX_VALUE_SCAN_SECRETS {
+        return Err(format!(
+            "Vault has {} enabled secrets; size scans support at most {}.",
+            names.len(),
+            MAX_VALUE_SCAN_SECRETS
+        )
+        .into());
+    }
+
+    let app: &AppState = &state;
+    let (token, vault_uri) = (&token, &vault_uri);
+    let measured: Vec<(String, Result<usize, String>)> = stream::iter(names)
+        .map(|name| async move {
+            let size = app
+                .azure
+                .get_secret_value(token, vault_uri, &name)
+                .await
+                .map(|secret| Zeroizing::new(secret.value).len());
+            (name, size)
+        })
+        .buffer_unordered(BULK_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut sizes = Vec::new();
+    let mut errors = Vec::new();
+    for (name, size) in measured {
+        match size {
+            Ok(size) => sizes.push((name, size)),
+            Err(e) => errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    let report = ValueSizeReport {
+        truncated: listing.truncated,
+        errors,
+        ..summarize_value_sizes(sizes)
+    };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "vault_value_size",
+            "secret",
+            "*",
+            if report.errors.is_empty() {
+                "success"
+            } else {
+                "partial"
+            },
+            Some(&format!(
+                "measured {} values, {} bytes total",
+                report.measured, report.total_bytes
+            )),
+        )
+        .await;
+
+    Ok(report)
+}
+
+/// Exports secret values to a new passphrase-encrypted backup file for
+/// disaster recovery.
+///
+/// # Security
+/// - `confirm_phrase` must be the vault name, typed by the user.
+/// - Every value read is audited individually as sensitive.
+/// - Values are serialized, encrypted, and zeroized in memory; plaintext is
+///   never written to disk. The export fails as a whole if any read fails.
+/// - `output_path` must be absolute and inside the user's home directory
+///   (see `validate_export_path`); existing files are never overwritten.
+#[tauri::command]
+pub async fn export_secret_values(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    names: Vec<String>,
+    confirm_phrase: String,
+    passphrase: String,
+    output_path: String,
+) -> Result<SecretValueExport, CommandError> {
+    correlation::scope(export_secret_values_inner(
+        window,
+        state,
+        vault_uri,
+        names,
+        confirm_phrase,
+        passphrase,
+        output_path,
+    ))
+    .await
+}
+
+async fn export_secret_values_inner(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    vault_uri: String,
+    names: Vec<String>,
+    confirm_phrase: String,
+    passphrase: String,
+    output_path: String,
+) -> Result<SecretValueExport, CommandError> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    let vault_name = extract_vault_name(&vault_uri);
+    if confirm_phrase.trim() != vault_name {
+        return Err("Type the vault name to confirm exporting secret values.".into());
+    }
+    let passphrase = Zeroizing::new(passphrase);
+    if names.is_empty() {
+        return Err("Select at least one secret to export.".into());
+    }
+    validate_bulk_size(names.len())?;
+    for name in &names {
+        validate_item_name(name)?;
+    }
+    let home = window
+        .path()
+        .home_dir()
+        .map_err(|e| format!("Home directory is unavailable: {}", e))?;
+    let output_path = validate_export_path(&output_path, &home)?;
+    let token = state.auth.get_vault_token().await?;
+
+    let app: &AppState = &state;
+    let (token, vault_uri_ref, vault_name_ref) = (&token, &vault_uri, &vault_name);
+    let mut progress = BulkProgress::new(&window, "export_secret_values", names.len());
+    let fetched: Vec<(String, Result<SecretValue, String>)> = stream::iter(names)
+        .map(|name| async move {
+            let result = app
+                .azure
+                .get_secret_value(token, vault_uri_ref, &name)
+                .await;
+            app.audit
+                .log_action(
+                    vault_name_ref,
+                    "export_secret_value",
+                    "secret",
+                    &name,
+                    result_status(&result),
+                    Some("[value exported - encrypted]"),
+                )
+                .await;
+            (name, result)
+        })
+        .map(correlation::scope)
+        .buffered(BULK_CONCURRENCY)
+        .inspect(|(name, _)| progress.item_done(name))
+        .collect()
+        .await;
+    let reads: Vec<BulkItemResult> = fetched
+        .iter()
+        .map(|(name, result)| BulkItemResult {
+            name: name.clone(),
+            success: result.is_ok(),
+            skipped: false,
+            error: result.as_ref().err().cloned(),
+        })
+        .collect();
+    progress.finish(&reads);
+
+    let mut values = Vec::with_capacity(fetched.len());
+    let mut errors = Vec::new();
+    for (name, result) in fetched {
+        match result {
+            Ok(value) => values.push(value),
+            Err(e) => errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    let result = if errors.is_empty() {
+        write_value_backup(values, &passphrase, &output_path)
+    } else {
+        for value in &mut values {
+            value.value.zeroize();
+        }
+        Err(format!(
+            "Export aborted; {} secret(s) could not be read: {}",
+            errors.len(),
+            errors.join("; ")
+        ))
+    };
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            "export_secret_values",
+            "secret",
+            "*",
+            result_status(&result),
+            result
+                .as_ref()
+                .ok()
+                .map(|export| format!("{} values, encrypted", export.count))
+                .as_deref(),
+        )
+        .await;
+
+    result.map_err(CommandError::from)
+}
+
+/// Serializes, encrypts, and writes `values`, zeroizing them (and the
+/// serialized plaintext) before returning.
+fn write_value_backup(
+    mut values: Vec<SecretValue>,
+    passphrase: &str,
+    output_path: &Path,
+) -> Result<SecretValueExport, String> {
+    let plaintext = serde_json::to_vec(&values).map(Zeroizing::new);
+    for value in &mut values {
+        value.value.zeroize();
+    }
+    let envelope = backup::encrypt(&plaintext.map_err(|e| e.to_string())?, passphrase)?;
+    backup::write_new_file(output_path, &envelope)?;
+    Ok(SecretValueExport {
+        path: output_path.display().to_string(),
+        count: values.len(),
+    })
+}
+
+/// SHA-256 of `salt || value`; the value is zeroized when this returns.
+fn salted_value_hash(salt: &[u8; 32], value: String) -> [u8; 32] {
+    let value = Zeroizing::new(value);
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(value.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Groups names sharing a digest, keeping only groups of two or more.
+/// Names within a group and groups themselves are sorted.
+fn group_by_digest(digests: Vec<(String, [u8; 32])>) -> Vec<Vec<String>> {
+    let mut by_digest: HashMap<[u8; 32], Vec<String>> = HashMap::new();
+    for (name, digest) in digests {
+        by_digest.entry(digest).or_default().push(name);
+    }
+    let mut groups: Vec<Vec<String>> = by_digest
+        .into_values()
+        .filter(|names| names.len() > 1)
+        .map(|mut names| {
+            names.sort();
+            names
+        })
+        .collect();
+    groups.sort();
+    groups
+}
+
+/// Totals `(name, size)` pairs. Ties for the largest value go to the
+/// alphabetically first name so results do not depend on fetch order.
+fn summarize_value_sizes(sizes: Vec<(String, usize)>) -> ValueSizeReport {
+    let measured = sizes.len();
+    let total_bytes: u64 = sizes.iter().map(|(_, size)| *size as u64).sum();
+    let largest = sizes
+        .into_iter()
+        .max_by(|(a_name, a), (b_name, b)| a.cmp(b).then_with(|| b_name.cmp(a_name)));
+    ValueSizeReport {
+        measured,
+        total_bytes,
+        average_bytes: if measured == 0 {
+            0
+        } else {
+            total_bytes / measured as u64
+        },
+        max_bytes: largest.as_ref().map_or(0, |(_, size)| *size as u64),
+        largest: largest.map(|(name, _)| name),
+        ..ValueSizeReport::default()
+    }
+}
+
+// ─────────────────────────────────────────────
+// Offline Commands
+// ─────────────────────────────────────────────
+
+/// Store file and key holding the metadata cache opt-in.
+const SETTINGS_STORE: &str = "settings.json";
+const METADATA_CACHE_KEY: &str = "metadataCacheEnabled";
+
+/// Reads the persisted metadata cache opt-in (off when unset), so the
+/// setting survives restarts.
+pub fn stored_metadata_cache_enabled(app: &tauri::AppHandle) -> bool {
+    app.store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(METADATA_CACHE_KEY))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Enables or disables offline mode (listings served from the metadata cache).
+#[tauri::command]
+pub async fn set_offline_mode(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    state.offline.store(enabled, Ordering::Relaxed);
+    state
+        .audit
+        .log_action(
+            "system",
+            "set_offline_mode",
+            "cache",
+            "*",
+            if enabled { "enabled" } else { "disabled" },
+            None,
+        )
+        .await;
+    Ok(())
+}
+
+/// Enables or disables the on-disk metadata cache and persists the choice.
+/// Disabling clears it.
+#[tauri::command]
+pub async fn set_metadata_cache(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+    store.set(METADATA_CACHE_KEY, serde_json::json!(enabled));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save cache setting: {}", e))?;
+    state.cache.set_enabled(enabled);
+    state
+        .audit
+        .log_action(
+            "system",
+            "set_metadata_cache",
+            "cache",
+            "*",
+            if enabled { "enabled" } else { "disabled" },
+            None,
+        )
+        .await;
+    Ok(())
+}
+
+// ─────────────────────────────────────────────
+// Audit Commands
+// ─────────────────────────────────────────────
+
+/// Returns the most recent audit log entries, newest first. Pass the
+/// smallest `seq` seen as `before_seq` to fetch the next (older) page.
+#[tauri::command]
+pub async fn get_audit_log(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+    before_seq: Option<u64>,
+) -> Result<Vec<AuditEntry>, String> {
+    Ok(state.audit.get_entries(limit, before_seq).await)
+}
+
+/// Returns audit entries for one vault, newest first (see `get_audit_log`
+/// for `limit` and `before_seq`).
+#[tauri::command]
+pub async fn get_vault_audit(
+    state: State<'_, AppState>,
+    vault_uri: String,
+    limit: Option<usize>,
+    before_seq: Option<u64>,
+) -> Result<Vec<AuditEntry>, String> {
+    let vault_uri = validate_vault_uri(&vault_uri)?;
+    Ok(state
+        .audit
+        .get_vault_entries(&extract_vault_name(&vault_uri), limit, before_seq)
+        .await)
+}
+
+/// Alias for `get_audit_log` (backwards compatibility).
+#[tauri::command]
+pub async fn read_audit_log(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<AuditEntry>, String> {
+    get_audit_log(state, limit, None).await
+}
+
+/// Writes a custom audit log entry (all fields are truncated for safety).
+#[tauri::command]
+pub async fn write_audit_log(
+    state: State<'_, AppState>,
+    vault_name: String,
+    action: String,
+    item_type: String,
+    item_name: String,
+    result: String,
+    details: Option<String>,
+) -> Result<(), String> {
+    let vault_name = truncate_for_audit(vault_name);
+    let action = truncate_for_audit(action);
+    let item_type = truncate_for_audit(item_type);
+    let item_name = truncate_for_audit(item_name);
+    let result = truncate_for_audit(result);
+    let details = details.map(truncate_for_audit);
+
+    state
+        .audit
+        .log_action(
+            &vault_name,
+            &action,
+            &item_type,
+            &item_name,
+            &result,
+            details.as_deref(),
+        )
+        .await;
+    Ok(())
+}
+
+/// Returns the full audit log as sanitised JSON (suitable for export/clipboard),
+/// or as newline-delimited compact JSON with `compact`.
+#[tauri::command]
+pub async fn export_audit_log(
+    state: State<'_, AppState>,
+    compact: Option<bool>,
+) -> Result<String, String> {
+    Ok(state
+        .audit
+        .get_sanitized_export(compact.unwrap_or(false))
+        .await)
+}
+
+/// Writes the sanitised audit export (`json`, or `jsonl` for one compact
+/// entry per line) to a new file at `path`, so large logs need not pass
+/// through the webview.
+///
+/// # Security
+/// - `path` must be absolute and inside the user's home directory (checked
+///   after resolving symlinks in its folder).
+/// - Existing files are never overwritten; on Unix the file is created
+///   with `0o600` permissions.
+#[tauri::command]
+pub async fn export_audit_log_to_path(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    format: String,
+) -> Result<AuditExportFile, String> {
+    let compact = match format.as_str() {
+        "json" => false,
+        "jsonl" => true,
+        _ => {
+            return Err(format!(
+                "Unsupported audit export format: '{}'. Use 'json' or 'jsonl'.",
+                format
+            ))
+        }
+    };
+    let home = app
+        .path()
+        .home_dir()
+        .map_err(|e| format!("Home directory is unavailable: {}", e))?;
+    let path = validate_export_path(&path, &home)?;
+
+    let export = state.audit.get_sanitized_export(compact).await;
+    let result = backup::write_new_file(&path, export.as_bytes()).map(|_| AuditExportFile {
+        path: path.display().to_string(),
+        bytes: export.len(),
+    });
+
+    state
+        .audit
+        .log_action(
+            "system",
+            "export_audit_log_to_path",
+            "audit",
+            "log",
+            result_status(&result),
+            result
+                .as_ref()
+                .ok()
+                .map(|file| format!("{} bytes ({})", file.bytes, format))
+                .as_deref(),
+        )
+        .await;
+
+    result
+}
+
+/// Sets which actions are recorded from now on. The change itself is
+/// always audited.
+#[tauri::command]
+pub async fn set_audit_level(state: State<'_, AppState>, level: AuditLevel) -> Result<(), String> {
+    let previous = state.audit.level();
+    state.audit.set_level(level);
+    state
+        .audit
+        .log_action(
+            "system",
+            "set_audit_level",
+            "audit",
+            "level",
+            "success",
+            Some(&format!("{:?} -> {:?}", previous, level)),
+        )
+        .await;
+    Ok(())
+}
+
+/// Clears all audit log entries from memory and disk.
+#[tauri::command]
+pub async fn clear_audit_log(state: State<'_, AppState>) -> Result<(), String> {
+    state.audit.clear().await;
+    Ok(())
+}
+
+// ─────────────────────────────────────────────
+// Diagnostics Commands
+// ─────────────────────────────────────────────
+
+/// Returns non-sensitive runtime state (cloud, offline flags, audit file
+/// permissions) for troubleshooting.
+#[tauri::command]
+pub async fn get_diagnostics(state: State<'_, AppState>) -> Result<Diagnostics, String> {
+    Ok(Diagnostics {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        cloud: state.azure.cloud().cli_name().to_string(),
+        offline_mode: state.offline.load(Ordering::Relaxed),
+        metadata_cache_enabled: state.cache.is_enabled(),
+        audit_file: state.audit.file_status().clone(),
+    })
+}
+
+/// Returns the throttling headers (`x-ms-ratelimit-*` remaining budgets,
+/// `x-ms-keyvault-*`, `Retry-After`) last received from each Azure host,
+/// so bulk users can see how close they are to being throttled.
+#[tauri::command]
+pub async fn last_rate_limit_status(
+    state: State<'_, AppState>,
+) -> Result<Vec<RateLimitStatus>, String> {
+    Ok(state.azure.rate_limit_status())
+}
+
+/// Probes each cloud's OpenID configuration endpoint for `tenant_id` (a
+/// tenant GUID or verified domain) and reports which cloud it belongs to,
+/// so the app can suggest the right `AZVAULT_CLOUD`.
+#[tauri::command]
+pub async fn detect_cloud(
+    state: State<'_, AppState>,
+    tenant_id: String,
+) -> Result<CloudDetection, String> {
+    correlation::scope(detect_cloud_inner(state, tenant_id)).await
+}
+
+async fn detect_cloud_inner(
+    state: State<'_, AppState>,
+    tenant_id: String,
+) -> Result<CloudDetection, String> {
+    let tenant_id = tenant_id.trim().to_string();
+    validate_tenant_hint(&tenant_id)?;
+
+    let azure = &state.azure;
+    let tenant = tenant_id.as_str();
+    let probes: Vec<CloudProbe> = stream::iter(Cloud::ALL)
+        .map(|cloud| async move {
+            match azure.get_openid_configuration(cloud, tenant).await {
+                Ok(Some(config)) => CloudProbe {
+                    cloud,
+                    recognized: true,
+                    cloud_instance: config["cloud_instance_name"].as_str().map(str::to_string),
+                    error: None,
+                },
+                Ok(None) => CloudProbe {
+                    cloud,
+                    recognized: false,
+                    cloud_instance: None,
+                    error: None,
+                },
+                Err(e) => CloudProbe {
+                    cloud,
+                    recognized: false,
+                    cloud_instance: None,
+                    error: Some(e),
+                },
+            }
+        })
+        .buffered(Cloud::ALL.len())
+        .collect()
+        .await;
+
+    Ok(CloudDetection {
+        detected: detected_cloud(&probes),
+        tenant_id,
+        probes,
+    })
+}
+
+/// Picks the tenant's cloud from probe results. An authority may answer for
+/// tenants of other clouds, so the reported instance name wins over the cloud
+/// that was probed.
+fn detected_cloud(probes: &[CloudProbe]) -> Option<Cloud> {
+    probes
+        .iter()
+        .filter(|p| p.recognized)
+        .find_map(|p| match p.cloud_instance.as_deref() {
+            Some(instance) => Cloud::from_instance_name(instance),
+            None => Some(p.cloud),
+        })
+}
+
+/// Checks a tenant GUID or domain before it is embedded in an authority URL.
+fn validate_tenant_hint(tenant: &str) -> Result<(), String> {
+    let valid = !tenant.is_empty()
+        && tenant.len() <= 253
+        && tenant
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        && !tenant.starts_with('.')
+        && !tenant.contains("..");
+    if !valid {
+        return Err(
+            "Enter a tenant ID (GUID) or domain such as contoso.onmicrosoft.com.".to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Checks a URL against the outbound Azure allowlist and the vault URI
+/// rules (`validate_vault_uri`) so the UI can flag a pasted vault URL
+/// before invoking an operation with it.
+#[tauri::command]
+pub async fn validate_azure_url(
+    state: State<'_, AppState>,
+    url: String,
+) -> Result<UrlValidation, String> {
+    let url = url.trim();
+    let reason = state
+        .azure
+        .check_url(url)
+        .and_then(|_| validate_vault_uri(url).map(|_| ()))
+        .err();
+    Ok(UrlValidation {
+        allowed: reason.is_none(),
+        reason,
+    })
+}
+
+/// Lists the export formats, item types, and mutating operations this
+/// backend supports so the UI need not assume a fixed feature set.
+#[tauri::command]
+pub async fn capabilities(state: State<'_, AppState>) -> Result<Capabilities, String> {
+    let to_strings =
+        |values: &[&str]| -> Vec<String> { values.iter().map(|v| v.to_string()).collect() };
+    Ok(Capabilities {
+        export_formats: to_strings(&EXPORT_FORMATS),
+        item_types: to_strings(&ITEM_TYPES),
+        mutations: to_strings(&MUTATIONS),
+        offline_mode: state.offline.load(Ordering::Relaxed),
+    })
+}
+
+/// Returns the effective configuration (cloud, hosts, timeouts, retries)
+/// so users can confirm their environment overrides took effect.
+#[tauri::command]
+pub async fn export_config(state: State<'_, AppState>) -> Result<ConfigExport, String> {
+    let cloud = state.azure.cloud();
+    let (data_api_version, mgmt_api_version) = state.azure.keyvault_api_versions();
+    Ok(ConfigExport {
+        cloud: cloud.cli_name().to_string(),
+        tenant_id: state.auth.get_tenant().await,
+        authority_host: state.auth.authority().to_string(),
+        arm_host: cloud.arm_host().to_string(),
+        auth_provider: "Azure CLI".to_string(),
+        allowed_hosts: state.azure.allowed_hosts(),
+        extra_header_names: state.azure.static_header_names(),
+        connect_timeout_secs: azure::CONNECT_TIMEOUT.as_secs(),
+        request_timeout_secs: azure::REQUEST_TIMEOUT.as_secs(),
+        max_retries: azure::MAX_RETRIES,
+        max_pages: state.azure.max_pages(),
+        max_concurrent_requests: state.azure.max_concurrent_requests(),
+        keyvault_api_version: data_api_version.to_string(),
+        keyvault_mgmt_api_version: mgmt_api_version.to_string(),
+    })
+}
+
+// ─────────────────────────────────────────────
+// Export Commands
+// ─────────────────────────────────────────────
+
+/// Exports vault item metadata as JSON, CSV, or NDJSON (one compact JSON
+/// object per line, no enclosing array).
+///
+/// With `fields`, only those keys are kept (in the given order for CSV
+/// columns); requested fields an item lacks are omitted from JSON objects
+/// and exported as empty CSV cells. Without `fields`, CSV columns are the
+/// union of all item keys in first-seen order.
+///
+/// With `group_by_content_type`, items are grouped by their `contentType`
+/// (before projection; `(none)` when unset): JSON becomes an object of
+/// arrays keyed by content type, CSV gets one `# contentType: ...`
+/// section per group, and NDJSON lines are ordered by group.
+///
+/// # Security
+/// - Input size is bounded to `MAX_EXPORT_INPUT_BYTES`.
+/// - Row count is bounded to `MAX_EXPORT_ITEMS`.
+/// - Only metadata is exported; secret values are never included.
+#[tauri::command]
+pub async fn export_items(
+    items_json: String,
+    format: String,
+    fields: Option<Vec<String>>,
+    group_by_content_type: Option<bool>,
+) -> Result<String, String> {
+    if items_json.len() > MAX_EXPORT_INPUT_BYTES {
+        return Err(format!(
+            "Export payload too large (max {} bytes).",
+            MAX_EXPORT_INPUT_BYTES
+        ));
+    }
+
+    let items: Vec<serde_json::Value> =
+        serde_json::from_str(&items_json).map_err(|e| format!("Invalid JSON: {}", e))?;
+    if items.len() > MAX_EXPORT_ITEMS {
+        return Err(format!(
+            "Too many items to export (max {}).",
+            MAX_EXPORT_ITEMS
+        ));
+    }
+
+    let grouped = group_by_content_type.unwrap_or(false);
+    let mut groups = if grouped {
+        group_by_content_type_label(items)
+    } else {
+        vec![(String::new(), items)]
+    };
+    if let Some(fields) = &fields {
+        for (_, items) in groups.iter_mut() {
+            project_fields(items, fields);
+        }
+    }
+
+    match format.as_str() {
+        "json" => {
+            let result = if grouped {
+                let map: serde_json::Map<String, serde_json::Value> = groups
+                    .into_iter()
+                    .map(|(label, items)| (label, serde_json::Value::Array(items)))
+                    .collect();
+                serde_json::to_string_pretty(&map)
+            } else {
+                serde_json::to_string_pretty(&groups[0].1)
+            };
+            result.map_err(|e| format!("Export error: {}", e))
+        }
+        "csv" => {
+            // Use the projection (or every key seen) as CSV headers
+            let headers: Vec<String> = match fields {
+                Some(fields) => fields,
+                None => {
+                    let mut keys: Vec<String> = Vec::new();
+                    for obj in groups
+                        .iter()
+                        .flat_map(|(_, items)| items)
+                        .filter_map(|item| item.as_object())
+                    {
+                        for key in obj.keys() {
+                            if !keys.contains(key) {
+                                keys.push(key.clone());
+                            }
+                        }
+                    }
+                    keys
+                }
+            };
+            if headers.is_empty() || groups.iter().all(|(_, items)| items.is_empty()) {
+                return Ok(String::new());
+            }
+
+            let sections: Vec<String> = groups
+                .iter()
+                .map(|(label, items)| {
+                    let csv = items_to_csv(&headers, items);
+                    if grouped {
+                        format!("# contentType: {}\n{}", label, csv)
+                    } else {
+                        csv
+                    }
+                })
+                .collect();
+            Ok(sections.join("\n"))
+        }
+        "ndjson" => groups
+            .iter()
+            .flat_map(|(_, items)| items)
+            .map(|item| {
+                serde_json::to_string(item)
+                    .map(|line| line + "\n")
+                    .map_err(|e| format!("Export error: {}", e))
+            })
+            .collect(),
+        _ => Err(format!(
+            "Unsupported export format: '{}'. Use 'json', 'csv', or 'ndjson'.",
+            format
+        )),
+    }
+}
+
+// ─────────────────────────────────────────────
+// Validation Helpers
+// ─────────────────────────────────────────────
+
+/// Extracts the vault name from its URI (e.g., `https://my-vault.vault.azure.net` -> `my-vault`).
+fn extract_vault_name(vault_uri: &str) -> String {
+    vault_uri
+        .trim_start_matches("https://")
+        .split('.')
+        .next()
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Resolves `path` for a new file inside `root`: it must be absolute, name
+/// a file, and its folder (with symlinks resolved) must lie within `root`.
+fn validate_export_path(path: &str, root: &Path) -> Result<PathBuf, String> {
+    let path = Path::new(path);
+    if !path.is_absolute() {
+        return Err("Export path must be absolute.".to_string());
+    }
+    let (Some(folder), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Err("Export path must name a file.".to_string());
+    };
+    let folder = folder
+        .canonicalize()
+        .map_err(|_| "Export folder does not exist.".to_string())?;
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Home directory is unavailable: {}", e))?;
+    if !folder.starts_with(&root) {
+        return Err("Export path must be inside your home directory.".to_string());
+    }
+    Ok(folder.join(file_name))
+}
+
+/// `true` for GUID-like IDs (hex digits and dashes), as used for tenants
+/// and subscriptions.
+fn is_guid_like(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 64 && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// Checks that `location` looks like an Azure region name (e.g. `westeurope`).
+fn validate_location(location: &str) -> Result<(), String> {
+    if location.is_empty()
+        || location.len() > 64
+        || !location.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err("Invalid Azure location.".to_string());
+    }
+    Ok(())
+}
+
+/// Checks Key Vault naming rules: 3-24 letters, digits, and hyphens,
+/// starting with a letter.
+fn validate_vault_name(name: &str) -> Result<(), String> {
+    let valid = (3..=24).contains(&name.len())
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err("Invalid vault name.".to_string());
+    }
+    Ok(())
+}
+
+/// Checks that a subscription ID is GUID-like.
+fn validate_subscription_id(id: &str) -> Result<(), String> {
+    if !is_guid_like(id) {
+        return Err("Invalid subscription ID.".to_string());
+    }
+    Ok(())
+}
+
+/// Parses a persisted `UiContext`, dropping fields that no longer validate.
+fn stored_context(value: Option<serde_json::Value>) -> UiContext {
+    let context: UiContext = value
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    UiContext {
+        tenant_id: context
+            .tenant_id
+            .filter(|id| id == "organizations" || is_guid_like(id)),
+        subscription_id: context.subscription_id.filter(|id| is_guid_like(id)),
+        vault_uri: context
+            .vault_uri
+            .and_then(|uri| validate_vault_uri(&uri).ok()),
+    }
+}
+
+/// Trims, drops blank, and de-duplicates tag keys, keeping first-seen order.
+fn normalize_tag_keys(keys: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for key in keys {
+        let key = key.trim();
+        if !key.is_empty() && !normalized.iter().any(|k| k == key) {
+            normalized.push(key.to_string());
+        }
+    }
+    if normalized.len() > MAX_REQUIRED_TAG_KEYS {
+        return Err(format!(
+            "At most {} required tag keys are supported.",
+            MAX_REQUIRED_TAG_KEYS
+        ));
+    }
+    Ok(normalized)
+}
+
+/// Parses persisted required tag keys; anything invalid yields no policy.
+fn stored_tag_keys(value: Option<serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| serde_json::from_value(v).ok())
+        .and_then(|keys| normalize_tag_keys(keys).ok())
+        .unwrap_or_default()
+}
+
+/// Required keys absent from `tags` or present with a blank value. Keys
+/// match exactly, as in `tag_matches`.
+fn missing_tag_keys(tags: Option<&HashMap<String, String>>, required: &[String]) -> Vec<String> {
+    required
+        .iter()
+        .filter(|key| {
+            !tags
+                .and_then(|tags| tags.get(key.as_str()))
+                .is_some_and(|value| !value.trim().is_empty())
+        })
+        .cloned()
+        .collect()
+}
+
+/// `true` when `tags` contain `key` (with exactly `value`, if given).
+fn tag_matches(tags: &Option<HashMap<String, String>>, key: &str, value: Option<&str>) -> bool {
+    match tags.as_ref().and_then(|tags| tags.get(key)) {
+        Some(actual) => match value {
+            Some(wanted) => actual == wanted,
+            None => true,
+        },
+        None => false,
+    }
+}
+
+/// Keeps subscriptions whose state matches `state_filter` (all when `None`).
+fn filter_subscriptions(
+    subscriptions: Vec<Subscription>,
+    state_filter: Option<&str>,
+) -> Vec<Subscription> {
+    match state_filter {
+        Some(wanted) => subscriptions
+            .into_iter()
+            .filter(|s| s.state.eq_ignore_ascii_case(wanted.trim()))
+            .collect(),
+        None => subscriptions,
+    }
+}
+
+/// Maps token claims to an `Identity`, falling back to the v1 `upn` /
+/// `unique_name` claims for the username.
+fn identity_from_claims(claims: &serde_json::Value) -> Identity {
+    let claim = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| claims[*name].as_str())
+            .map(|v| v.to_string())
+    };
+    Identity {
+        object_id: claim(&["oid"]),
+        tenant_id: claim(&["tid"]),
+        preferred_username: claim(&["preferred_username", "upn", "unique_name"]),
+        app_id: claim(&["appid", "azp"]),
+    }
+}
+
+/// Buckets vaults by location: `home_region` first, then alphabetically.
+fn group_by_region(vaults: Vec<KeyVaultInfo>, home_region: Option<&str>) -> Vec<VaultRegionGroup> {
+    let mut groups: Vec<VaultRegionGroup> = Vec::new();
+    for vault in vaults {
+        match groups
+            .iter_mut()
+            .find(|g| g.location.eq_ignore_ascii_case(&vault.location))
+        {
+            Some(group) => group.vaults.push(vault),
+            None => groups.push(VaultRegionGroup {
+                location: vault.location.clone(),
+                vaults: vec![vault],
+            }),
+        }
+    }
+    groups.sort_by_key(|g| {
+        let is_home = home_region.is_some_and(|h| g.location.eq_ignore_ascii_case(h));
+        (!is_home, g.location.to_ascii_lowercase())
+    });
+    groups
+}
+
+/// Normalises a vault URI for use as a `vault_index` key.
+fn vault_index_key(vault_uri: &str) -> String {
+    vault_uri.trim_end_matches('/').to_ascii_lowercase()
+}
+
+/// Records discovered vaults in the URI → ARM resource index.
+async fn index_vaults(index: &RwLock<HashMap<String, KeyVaultInfo>>, vaults: &[KeyVaultInfo]) {
+    let mut index = index.write().await;
+    for vault in vaults {
+        index.insert(vault_index_key(&vault.vault_uri), vault.clone());
+    }
+}
+
+/// Replaces Azure's "soft delete not enabled" errors with `SOFT_DELETE_DISABLED`.
+fn map_soft_delete_error(error: String) -> String {
+    let lower = error.to_lowercase();
+    let mentions_soft_delete = ["soft delete", "soft-delete", "softdelete"]
+        .iter()
+        .any(|needle| lower.contains(needle));
+    if mentions_soft_delete && (lower.contains("not enabled") || lower.contains("disabled")) {
+        SOFT_DELETE_DISABLED.to_string()
+    } else {
+        error
+    }
+}
+
+/// Returns `"success"` or `"error"` based on the result variant.
+fn result_status<T>(result: &Result<T, String>) -> &'static str {
+    if result.is_ok() {
+        "success"
+    } else {
+        "error"
+    }
+}
+
+/// Validates a vault URI (HTTPS, known Key Vault host suffix) and returns
+/// its canonical origin form, `https://{host}`, with any path, query, or
+/// trailing slash removed so callers can safely append data-plane paths.
+fn validate_vault_uri(vault_uri: &str) -> Result<String, String> {
+    let parsed = Url::parse(vault_uri).map_err(|_| "Invalid vault URI.".to_string())?;
+    if parsed.scheme() != "https" {
+        return Err("Vault URI must use HTTPS.".to_string());
+    }
+
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "Vault URI must include a host.".to_string())?;
+    let allowed = Cloud::ALL
+        .iter()
+        .any(|cloud| host.ends_with(cloud.vault_suffix()));
+    if !allowed {
+        return Err("Vault URI must target an Azure Key Vault endpoint.".to_string());
+    }
+
+    Ok(format!("https://{}", host))
+}
+
+/// Validates an item name (secret/key/certificate):
+/// - Must be 1–127 characters
+/// - Only alphanumeric characters and hyphens
+fn validate_item_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > 127 {
+        return Err("Item name must be between 1 and 127 characters.".to_string());
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err("Item name may only contain letters, numbers, and hyphens.".to_string());
+    }
+    Ok(())
+}
+
+/// Validates an item version id (Key Vault uses 32 hex characters).
+fn validate_item_version(version: &str) -> Result<(), String> {
+    if version.is_empty()
+        || version.len() > 64
+        || !version.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err("Item version must be 1-64 letters or digits.".to_string());
+    }
+    Ok(())
+}
+
+/// Grant key for `name`, or for one version of it (`name/version`).
+fn versioned_name(name: &str, version: Option<&str>) -> String {
+    match version {
+        Some(version) => format!("{}/{}", name, version),
+        None => name.to_string(),
+    }
+}
+
+/// Reveal-grant key for a certificate, kept apart from secret grants that
+/// share the backing secret's name.
+fn certificate_grant_name(name: &str) -> String {
+    format!("certificate:{}", name)
+}
+
+/// Serves the last cached listing for `vault_uri`/`kind`, flagged `stale`.
+async fn serve_cached_listing<T: DeserializeOwned>(
+    state: &AppState,
+    vault_uri: &str,
+    kind: &str,
+    action: &str,
+    item_type: &str,
+) -> Result<ListResult<T>, String> {
+    let result = cached_listing(&state.cache, vault_uri, kind);
+
+    state
+        .audit
+        .log_action(
+            &extract_vault_name(vault_uri),
+            action,
+            item_type,
+            "*",
+            if result.is_ok() { "cached" } else { "error" },
+            Some("offline mode"),
+        )
+        .await;
+
+    result
+}
+
+/// Loads a cached listing, marked `stale`, for offline mode.
+fn cached_listing<T: DeserializeOwned>(
+    cache: &MetadataCache,
+    vault_uri: &str,
+    kind: &str,
+) -> Result<ListResult<T>, String> {
+    cache
+        .load(vault_uri, kind)
+        .map(|items| ListResult {
+            items,
+            truncated: false,
+            error: None,
+            stale: true,
+            from_cache: false,
+        })
+        .ok_or_else(|| "Offline mode: no cached listing is available for this vault.".to_string())
+}
+
+/// Splits a listing result into the value/error pair used by `VaultItems`.
+fn split_listing<T>(
+    result: Result<ListResult<T>, String>,
+) -> (Option<ListResult<T>>, Option<String>) {
+    match result {
+        Ok(list) => (Some(list), None),
+        Err(e) => (None, Some(e)),
+    }
+}
+
+/// Audit status and count summary for `list_all_items`: `"error"` when
+/// every type failed, `"partial"` when any failed or was truncated.
+fn all_items_audit_outcome<S, K, C>(
+    secrets: &Option<ListResult<S>>,
+    keys: &Option<ListResult<K>>,
+    certificates: &Option<ListResult<C>>,
+) -> (&'static str, String) {
+    fn describe<T>(kind: &str, list: &Option<ListResult<T>>) -> (String, bool, bool) {
+        match list {
+            Some(list) if list.truncated => (format!("{}={}+", kind, list.items.len()), true, true),
+            Some(list) => (format!("{}={}", kind, list.items.len()), true, false),
+            None => (format!("{}=error", kind), false, false),
+        }
+    }
+    let parts = [
+        describe("secrets", secrets),
+        describe("keys", keys),
+        describe("certificates", certificates),
+    ];
+    let status = if parts.iter().all(|(_, ok, _)| !ok) {
+        "error"
+    } else if parts.iter().any(|(_, ok, truncated)| !ok || *truncated) {
+        "partial"
+    } else {
+        "success"
+    };
+    let details: Vec<String> = parts.into_iter().map(|(text, _, _)| text).collect();
+    (status, details.join(" "))
+}
+
+/// Stores a complete (non-truncated) listing in the metadata cache.
+fn cache_listing<T: Serialize>(
+    cache: &MetadataCache,
+    vault_uri: &str,
+    kind: &str,
+    result: &Result<ListResult<T>, String>,
+) {
+    if let Ok(list) = result {
+        if !list.truncated {
+            cache.store(vault_uri, kind, &list.items);
+        }
+    }
+}
+
+/// Audit status and details for a listing: `"partial"` plus the truncation
+/// reason when a later page failed.
+fn list_audit_outcome<T>(result: &Result<ListResult<T>, String>) -> (&'static str, Option<String>) {
+    match result {
+        Ok(list) if list.truncated => (
+            "partial",
+            Some(format!(
+                "truncated after {} items: {}",
+                list.items.len(),
+                list.error.as_deref().unwrap_or("unknown error")
+            )),
+        ),
+        Ok(_) => ("success", None),
+        Err(_) => ("error", None),
+    }
+}
+
+/// Treats an empty prefix as none; otherwise it must be a valid item-name
+/// fragment.
+fn validate_name_prefix(prefix: Option<String>) -> Result<Option<String>, String> {
+    match prefix.filter(|p| !p.is_empty()) {
+        Some(prefix) => {
+            validate_item_name(&prefix)
+                .map_err(|_| "Name prefix may only contain letters, numbers, and hyphens.")?;
+            Ok(Some(prefix))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Keeps only items whose name starts with `prefix` (case-sensitive). This
+/// runs client-side after the full listing: the data plane has no prefix
+/// filter. Returns `details` with the filtered/total counts appended.
+fn filter_by_name_prefix<T>(
+    result: &mut Result<ListResult<T>, String>,
+    prefix: Option<&str>,
+    name: impl Fn(&T) -> &str,
+    details: Option<String>,
+) -> Option<String> {
+    let (Some(prefix), Ok(list)) = (prefix, result.as_mut()) else {
+        return details;
+    };
+    let total = list.items.len();
+    list.items.retain(|item| name(item).starts_with(prefix));
+    let summary = format!(
+        "prefix '{}': {} of {} items",
+        prefix,
+        list.items.len(),
+        total
+    );
+    Some(match details {
+        Some(details) => format!("{}; {}", details, summary),
+        None => summary,
+    })
+}
+
+/// Validates an encoded certificate: non-empty, within
+/// `MAX_CERTIFICATE_IMPORT_BYTES`, and either PEM or valid base64 (PKCS#12).
+fn validate_certificate_payload(payload: &str) -> Result<(), String> {
+    if payload.trim().is_empty() || payload.len() > MAX_CERTIFICATE_IMPORT_BYTES {
+        return Err(format!(
+            "Certificate payload must be between 1 and {} bytes.",
+            MAX_CERTIFICATE_IMPORT_BYTES
+        ));
+    }
+    if payload.trim_start().starts_with("-----BEGIN") {
+        return Ok(());
+    }
+    let compact: String = payload.chars().filter(|c| !c.is_whitespace()).collect();
+    STANDARD
+        .decode(compact)
+        .map(|_| ())
+        .map_err(|_| "Certificate must be base64-encoded PKCS#12 (PFX) or PEM.".to_string())
+}
+
+/// Minimum plausible size (bytes) of a decoded PKCS#12 archive.
+const MIN_PKCS12_BYTES: usize = 64;
+
+/// Checks that `content_type` is a plausible MIME type (`type/subtype` of
+/// RFC 6838 name characters, optionally followed by `; name=value`
+/// parameters) and returns it trimmed.
+fn validate_mime_type(content_type: &str) -> Result<String, String> {
+    let content_type = content_type.trim();
+    let invalid = || format!("'{}' is not a valid MIME type.", content_type);
+    if content_type.is_empty() || content_type.len() > 255 {
+        return Err(invalid());
+    }
+
+    let is_name = |part: &str| {
+        !part.is_empty()
+            && part.len() <= 127
+            && part.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+    };
+    let mut parts = content_type.split(';');
+    let essence = parts.next().unwrap_or_default().trim();
+    let valid_essence = essence
+        .split_once('/')
+        .is_some_and(|(kind, subtype)| is_name(kind) && is_name(subtype));
+    let valid_params = parts.all(|param| {
+        param.split_once('=').is_some_and(|(key, value)| {
+            is_name(key.trim())
+                && !value.trim().is_empty()
+                && value.chars().all(|c| c.is_ascii_graphic() || c == ' ')
+        })
+    });
+    if !valid_essence || !valid_params {
+        return Err(invalid());
+    }
+    Ok(content_type.to_string())
+}
+
+/// Opt-in pre-flight check that a secret value matches its content type:
+/// JSON types must parse, and PKCS#12 values must be base64 decoding to a
+/// DER archive of plausible size. Other content types are not checked.
+fn validate_secret_content(content_type: Option<&str>, value: &str) -> Result<(), String> {
+    let Some(content_type) = content_type else {
+        return Ok(());
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    if mime == "application/json" || mime.ends_with("+json") {
+        return serde_json::from_str::<serde_json::Value>(value)
+            .map(|_| ())
+            .map_err(|e| format!("Secret value is not valid JSON: {}", e));
+    }
+    if mime == "application/x-pkcs12" {
+        let compact: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+        let der = STANDARD
+            .decode(compact)
+            .map_err(|_| "PKCS#12 secret value must be base64-encoded.".to_string())?;
+        if der.len() < MIN_PKCS12_BYTES || der[0] != 0x30 {
+            return Err("PKCS#12 secret value does not look like a PFX archive.".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Validates the item count of a bulk request (1..=`MAX_BULK_ITEMS`).
+fn validate_bulk_size(count: usize) -> Result<(), String> {
+    if count == 0 || count > MAX_BULK_ITEMS {
+        return Err(format!(
+            "Bulk operations accept between 1 and {} items.",
+            MAX_BULK_ITEMS
+        ));
+    }
+    Ok(())
+}
+
+/// Reports a bulk operation's progress to the calling window: a
+/// `bulk-progress` event as each item finishes and a final `bulk-complete`
+/// summary. Emit failures (e.g. a closed window) are ignored.
+struct BulkProgress<'a> {
+    window: &'a tauri::Window,
+    operation: &'static str,
+    total: usize,
+    completed: usize,
+}
+
+impl<'a> BulkProgress<'a> {
+    fn new(window: &'a tauri::Window, operation: &'static str, total: usize) -> Self {
+        Self {
+            window,
+            operation,
+            total,
+            completed: 0,
+        }
+    }
+
+    fn item_done(&mut self, name: &str) {
+        self.completed += 1;
+        self.emit(
+            "bulk-progress",
+            BulkProgressEvent {
+                operation: self.operation.to_string(),
+                completed: self.completed,
+                total: self.total,
+                current_item: name.to_string(),
+            },
+        );
+    }
+
+    fn finish(&self, results: &[BulkItemResult]) {
+        self.emit("bulk-complete", bulk_summary(self.operation, results));
+    }
+
+    fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) {
+        if let Err(e) = self.window.emit(event, payload) {
+            log::debug!("Failed to emit {}: {}", event, e);
+        }
+    }
+}
+
+/// Tallies per-item results into the `bulk-complete` payload.
+fn bulk_summary(operation: &str, results: &[BulkItemResult]) -> BulkCompleteEvent {
+    BulkCompleteEvent {
+        operation: operation.to_string(),
+        total: results.len(),
+        succeeded: results.iter().filter(|r| r.success).count(),
+        skipped: results.iter().filter(|r| r.skipped).count(),
+        failed: results.iter().filter(|r| !r.success && !r.skipped).count(),
+    }
+}
+
+/// Audit status and counts for a bulk run: `"error"` when items failed and
+/// none succeeded, `"partial"` when some failed, else `"success"`.
+fn bulk_audit_outcome(verb: &str, results: &[BulkItemResult]) -> (&'static str, String) {
+    let summary = bulk_summary("", results);
+    let status = if summary.failed == 0 {
+        "success"
+    } else if summary.succeeded == 0 {
+        "error"
+    } else {
+        "partial"
+    };
+    let counts = format!(
+        "{} {} of {}; {} skipped, {} failed",
+        verb, summary.succeeded, summary.total, summary.skipped, summary.failed
+    );
+    (status, counts)
+}
+
+/// Lists every soft-deleted secret for a bulk recover/purge. A truncated
+/// listing is refused (and audited under `action`) so the run never
+/// silently covers only part of the vault.
+async fn complete_deleted_listing(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    action: &str,
+    verb: &str,
+) -> Result<Vec<DeletedSecretItem>, String> {
+    let listing = state.azure.list_deleted_secrets(token, vault_uri).await?;
+    if !listing.truncated {
+        return Ok(listing.items);
+    }
+
+    let reason = format!(
+        "Deleted secrets could not be listed completely ({}); nothing was {}.",
+        listing.error.as_deref().unwrap_or("unknown error"),
+        verb
+    );
+    state
+        .audit
+        .log_action(
+            &extract_vault_name(vault_uri),
+            action,
+            "secret",
+            "*",
+            "error",
+            Some(&reason),
+        )
+        .await;
+    Err(reason)
+}
+
+/// Rejects a mutation of a certificate-backed (`managed`) secret unless
+/// `allow_managed`. A failed lookup (e.g. the secret does not exist yet)
+/// does not block the mutation; Azure remains the final authority.
+async fn ensure_not_managed(
+    state: &AppState,
+    token: &str,
+    vault_uri: &str,
+    name: &str,
+    allow_managed: Option<bool>,
+) -> Result<(), String> {
+    if allow_managed.unwrap_or(false) {
+        return Ok(());
+    }
+    match state
+        .azure
+        .get_secret_metadata(token, vault_uri, name)
+        .await
+    {
+        Ok(current) => managed_guard(current.managed),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Maps a metadata lookup to `SecretExistence`; only a 404 means "free".
+fn secret_existence(result: Result<SecretItem, String>) -> Result<SecretExistence, String> {
+    match result {
+        Ok(item) => Ok(SecretExistence {
+            exists: true,
+            enabled: Some(item.enabled),
+        }),
+        Err(e) if e.starts_with("[404]") => Ok(SecretExistence {
+            exists: false,
+            enabled: None,
+        }),
+        Err(e) => Err(e),
+    }
+}
+
+/// Fails with `MANAGED_SECRET_READ_ONLY` for managed secrets.
+fn managed_guard(managed: Option<bool>) -> Result<(), String> {
+    if managed == Some(true) {
+        return Err(MANAGED_SECRET_READ_ONLY.to_string());
+    }
+    Ok(())
+}
+
+/// Converts a per-item result into a serializable bulk outcome.
+fn bulk_item_result<T>(name: String, result: Result<T, String>) -> BulkItemResult {
+    match result {
+        Ok(_) => BulkItemResult {
+            name,
+            success: true,
+            skipped: false,
+            error: None,
+        },
+        Err(e) => BulkItemResult {
+            name,
+            success: false,
+            skipped: false,
+            error: Some(e),
+        },
+    }
+}
+
+/// Returns `true` if a deleted item's recovery level allows purging
+/// (e.g. `Recoverable+Purgeable`). Unknown levels are attempted.
+fn is_purgeable(recovery_level: Option<&str>) -> bool {
+    match recovery_level {
+        Some(level) => level.contains("Purgeable"),
+        None => true,
+    }
+}
+
+/// Maps a `check_permission` action to the collection probed for it.
+fn probe_collection_for(action: &str) -> Result<&'static str, String> {
+    match action {
+        "list_secrets" => Ok("secrets"),
+        "list_keys" => Ok("keys"),
+        "list_certificates" => Ok("certificates"),
+        "recover" | "purge" => Ok("deletedsecrets"),
+        other => Err(format!(
+            "Unsupported action '{}'. Use list_secrets, list_keys, list_certificates, recover, or purge.",
+            other
+        )),
+    }
+}
+
+/// Classifies a probe failure: 401/403 mean denied, anything else unknown.
+fn permission_access_for(error: &str) -> PermissionAccess {
+    match CommandError::from(error).status {
+        Some(401) | Some(403) => PermissionAccess::Denied,
+        _ => PermissionAccess::Unknown,
+    }
+}
+
+/// Extracts the secret names of a complete listing of `vault_uri`.
+fn secret_names(
+    vault_uri: &str,
+    listing: Result<ListResult<SecretItem>, String>,
+) -> Result<BTreeSet<String>, String> {
+    let listing = listing?;
+    if listing.truncated {
+        return Err(format!(
+            "Could not list all secrets in {}: {}",
+            extract_vault_name(vault_uri),
+            listing.error.unwrap_or_default()
+        ));
+    }
+    Ok(listing.items.into_iter().map(|item| item.name).collect())
+}
+
+/// Keeps certificates expiring before `now + within_days`, soonest first.
+/// Certificates without an expiry date never qualify.
+fn expiring_within(
+    certificates: Vec<CertificateItem>,
+    within_days: u32,
+    now: i64,
+) -> Vec<ExpiringCertificate> {
+    let cutoff = now + i64::from(within_days) * 86_400;
+    let mut expiring: Vec<(i64, ExpiringCertificate)> = certificates
+        .into_iter()
+        .filter_map(|cert| {
+            let expires_at = cert.expires_epoch?;
+            if expires_at > cutoff {
+                return None;
+            }
+            let days_remaining = (expires_at - now).div_euclid(86_400);
+            Some((
+                expires_at,
+                ExpiringCertificate {
+                    id: cert.id,
+                    name: cert.name,
+                    subject: cert.subject,
+                    thumbprint: cert.thumbprint,
+                    expires: cert.expires,
+                    days_remaining,
+                    expired: expires_at <= now,
+                },
+            ))
+        })
+        .collect();
+    expiring.sort_by_key(|(expires_at, _)| *expires_at);
+    expiring.into_iter().map(|(_, cert)| cert).collect()
+}
+
+/// Enabled secrets with a `not_before` after `now`, soonest activation
+/// first (helper for `not_yet_active_secrets`).
+fn not_yet_active(secrets: Vec<SecretItem>, now: i64) -> Vec<SecretItem> {
+    let mut pending: Vec<(i64, SecretItem)> = secrets
+        .into_iter()
+        .filter(|secret| secret.enabled)
+        .filter_map(|secret| {
+            let activates_at = chrono::DateTime::parse_from_rfc3339(secret.not_before.as_deref()?)
+                .ok()?
+                .timestamp();
+            (activates_at > now).then_some((activates_at, secret))
+        })
+        .collect();
+    pending.sort_by_key(|(activates_at, _)| *activates_at);
+    pending.into_iter().map(|(_, secret)| secret).collect()
+}
+
+/// Counts a complete listing; truncated listings are an error so the
+/// dashboard never shows an undercount.
+fn listing_count<T>(listing: Result<ListResult<T>, String>) -> Result<usize, String> {
+    let listing = listing?;
+    if listing.truncated {
+        return Err(listing
+            .error
+            .unwrap_or_else(|| "Listing was incomplete.".to_string()));
+    }
+    Ok(listing.items.len())
+}
+
+/// Checks each lifetime action: a `Rotate`/`Notify` type and exactly one
+/// ISO 8601 duration trigger.
+fn validate_rotation_policy(policy: &KeyRotationPolicy) -> Result<(), String> {
+    for action in &policy.lifetime_actions {
+        if !["rotate", "notify"].contains(&action.action.to_ascii_lowercase().as_str()) {
+            return Err(format!(
+                "Unsupported rotation action '{}'. Use Rotate or Notify.",
+                action.action
+            ));
+        }
+        let triggers: Vec<&String> = [&action.time_after_create, &action.time_before_expiry]
+            .into_iter()
+            .flatten()
+            .collect();
+        if triggers.len() != 1 {
+            return Err(
+                "Each rotation action needs exactly one of timeAfterCreate or timeBeforeExpiry."
+                    .to_string(),
+            );
+        }
+        if !triggers.iter().all(|d| d.starts_with('P')) {
+            return Err("Rotation triggers must be ISO 8601 durations such as P90D.".to_string());
+        }
+    }
+    if let Some(expiry) = &policy.expiry_time {
+        if !expiry.starts_with('P') {
+            return Err("expiryTime must be an ISO 8601 duration such as P1Y.".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Splits two name sets into only-in-a, only-in-b, and shared names.
+fn compare_names(a: &BTreeSet<String>, b: &BTreeSet<String>) -> VaultComparison {
+    VaultComparison {
+        only_in_a: a.difference(b).cloned().collect(),
+        only_in_b: b.difference(a).cloned().collect(),
+        in_both: a.intersection(b).cloned().collect(),
+    }
+}
+
+/// Drops every key not listed in `fields` from each exported object.
+fn project_fields(items: &mut [serde_json::Value], fields: &[String]) {
+    for item in items.iter_mut() {
+        if let Some(obj) = item.as_object_mut() {
+            obj.retain(|key, _| fields.iter().any(|f| f == key));
+        }
+    }
+}
+
+/// Renders `items` as a CSV block with a header row of `headers`.
+fn items_to_csv(headers: &[String], items: &[serde_json::Value]) -> String {
+    let mut csv = headers.join(",");
+    csv.push('\n');
+
+    for item in items {
+        if let Some(obj) = item.as_object() {
+            let row: Vec<String> = headers
+                .iter()
+                .map(|h| {
+                    let val = obj.get(h).cloned().unwrap_or(serde_json::Value::Null);
+                    match val {
+                        serde_json::Value::String(s) => {
+                            // Escape double quotes in CSV values
+                            format!("\"{}\"", s.replace('"', "\"\""))
+                        }
+                        serde_json::Value::Null => String::new(),
+                        other => other.to_string(),
+                    }
+                })
+                .collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+    }
+
+    csv
+}
+
+/// Parses `import_secrets` input. Names repeated (ignoring case, as Key
+/// Vault does) are rejected, since only one value could win.
+fn parse_import(data: &str, format: &str) -> Result<Vec<CreateSecretRequest>, String> {
+    let requests: Vec<CreateSecretRequest> = match format {
+        "json" => serde_json::from_str(data).map_err(|e| format!("Invalid JSON: {}", e))?,
+        "csv" => csv_to_requests(parse_csv(data)?)?,
+        _ => {
+            return Err(format!(
+                "Unsupported import format: '{}'. Use 'json' or 'csv'.",
+                format
+            ))
+        }
+    };
+
+    let mut seen = BTreeSet::new();
+    for request in &requests {
+        if !seen.insert(request.name.to_ascii_lowercase()) {
+            return Err(format!(
+                "Secret '{}' appears more than once in the import.",
+                request.name
+            ));
+        }
+    }
+    Ok(requests)
+}
+
+/// Parses CSV as written by `items_to_csv`: quoted fields may contain
+/// commas, line breaks, and `""` escapes. Blank lines are skipped.
+fn parse_csv(data: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("CSV has an unterminated quoted field.".to_string());
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows.retain(|row| !(row.len() == 1 && row[0].trim().is_empty()));
+    Ok(rows)
+}
+
+/// Maps CSV rows to requests using the header row; `name` and `value`
+/// columns are required, `contentType` is optional, other columns are
+/// ignored.
+fn csv_to_requests(rows: Vec<Vec<String>>) -> Result<Vec<CreateSecretRequest>, String> {
+    let mut rows = rows.into_iter();
+    let header = rows.next().ok_or("CSV is empty.")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let (Some(name_col), Some(value_col)) = (column("name"), column("value")) else {
+        return Err("CSV header must include 'name' and 'value' columns.".to_string());
+    };
+    let content_type_col = column("contentType");
+
+    Ok(rows
+        .map(|mut row| {
+            let mut take = |col: usize| row.get_mut(col).map(std::mem::take).unwrap_or_default();
+            CreateSecretRequest {
+                name: take(name_col).trim().to_string(),
+                value: take(value_col),
+                content_type: content_type_col.map(&mut take).filter(|ct| !ct.is_empty()),
+                tags: None,
+                enabled: None,
+                expires: None,
+                not_before: None,
+            }
+        })
+        .collect())
+}
+
+/// Splits items into groups keyed by `contentType` (`(none)` when unset),
+/// ordered by label.
+fn group_by_content_type_label(
+    items: Vec<serde_json::Value>,
+) -> Vec<(String, Vec<serde_json::Value>)> {
+    let mut groups: BTreeMap<String, Vec<serde_json::Value>> = BTreeMap::new();
+    for item in items {
+        let label = item["contentType"]
+            .as_str()
+            .filter(|ct| !ct.is_empty())
+            .unwrap_or("(none)")
+            .to_string();
+        groups.entry(label).or_default().push(item);
+    }
+    groups.into_iter().collect()
+}
+
+/// Returns `true` when writing `request` would change anything besides the
+/// value. Omitted fields are compared as `set_secret` would write them.
+fn secret_attributes_differ(current: &SecretItem, request: &CreateSecretRequest) -> bool {
+    let epoch = |value: Option<&str>| {
+        value
+            .and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok())
+            .map(|dt| dt.timestamp())
+    };
+
+    current.content_type != request.content_type
+        || current.tags.clone().unwrap_or_default() != request.tags.clone().unwrap_or_default()
+        || current.enabled != request.enabled.unwrap_or(true)
+        || epoch(current.expires.as_deref()) != epoch(request.expires.as_deref())
+        || epoch(current.not_before.as_deref()) != epoch(request.not_before.as_deref())
+}
+
+/// Checks `tags` against Key Vault's tag limits: at most `MAX_ITEM_TAGS`
+/// tags, non-blank keys, and bounded key and value lengths.
+fn validate_tags(tags: &HashMap<String, String>) -> Result<(), String> {
+    if tags.len() > MAX_ITEM_TAGS {
+        return Err(format!("At most {} tags are supported.", MAX_ITEM_TAGS));
+    }
+    for (key, value) in tags {
+        if key.trim().is_empty() {
+            return Err("Tag keys must not be blank.".to_string());
+        }
+        if key.chars().count() > MAX_TAG_KEY_LEN {
+            return Err(format!(
+                "Tag keys must be at most {} characters.",
+                MAX_TAG_KEY_LEN
+            ));
+        }
+        if value.chars().count() > MAX_TAG_VALUE_LEN {
+            return Err(format!(
+                "Tag '{}' value must be at most {} characters.",
+                key, MAX_TAG_VALUE_LEN
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Required keys satisfied by `current` tags but missing from `updated`.
+/// Keys a secret already lacks are not reported, so a partial re-tag is
+/// not blocked by unrelated gaps.
+fn removed_required_tag_keys(
+    current: Option<&HashMap<String, String>>,
+    updated: &HashMap<String, String>,
+    required: &[String],
+) -> Vec<String> {
+    let already_missing = missing_tag_keys(current, required);
+    missing_tag_keys(Some(updated), required)
+        .into_iter()
+        .filter(|key| !already_missing.contains(key))
+        .collect()
+}
+
+/// Merges `updates` into `existing` tags (updates win on key conflicts).
+fn merge_tags(
+    existing: Option<HashMap<String, String>>,
+    updates: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut merged = existing.unwrap_or_default();
+    merged.extend(updates.iter().map(|(k, v)| (k.clone(), v.clone())));
+    merged
+}
+
+/// Truncates a string to the audit field length limit.
+fn truncate_for_audit(value: String) -> String {
+    value.chars().take(MAX_AUDIT_FIELD_LEN).collect()
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── Vault URI validation ──
+
+    #[test]
+    fn accepts_valid_azure_public_vault_uri() {
+        assert!(validate_vault_uri("https://demo.vault.azure.net").is_ok());
+    }
+
+    #[test]
+    fn accepts_valid_us_gov_vault_uri() {
+        assert!(validate_vault_uri("https://demo.vault.usgovcloudapi.net").is_ok());
+    }
+
+    #[test]
+    fn accepts_valid_china_vault_uri() {
+        assert!(validate_vault_uri("https://demo.vault.azure.cn").is_ok());
+    }
+
+    #[test]
+    fn rejects_http_vault_uri() {
+        assert!(validate_vault_uri("http://demo.vault.azure.net").is_err());
+    }
+
+    #[test]
+    fn rejects_non_azure_vault_uri() {
+        assert!(validate_vault_uri("https://evil.example.com").is_err());
+    }
+
+    #[test]
+    fn rejects_empty_vault_uri() {
+        assert!(validate_vault_uri("").is_err());
+    }
+
+    #[test]
+    fn rejects_vault_uri_without_host() {
+        assert!(validate_vault_uri("https://").is_err());
+    }
+
+    #[test]
+    fn vault_uri_normalizes_trailing_slash() {
+        assert_eq!(
+            validate_vault_uri("https://demo.vault.azure.net/").unwrap(),
+            "https://demo.vault.azure.net"
+        );
+    }
+
+    #[test]
+    fn vault_uri_normalizes_path_and_query() {
+        assert_eq!(
+            validate_vault_uri("https://demo.vault.azure.net/secrets/foo?api-version=7.5").unwrap(),
+            "https://demo.vault.azure.net"
+        );
+    }
+
+    #[test]
+    fn vault_uri_normalizes_host_case() {
+        assert_eq!(
+            validate_vault_uri("https://Demo.Vault.Azure.Net").unwrap(),
+            "https://demo.vault.azure.net"
+        );
+    }
+
+    // ── Item name validation ──
+
+    #[test]
+    fn accepts_valid_item_name() {
+        assert!(validate_item_name("valid-name-01").is_ok());
+    }
+
+    #[test]
+    fn accepts_single_char_name() {
+        assert!(validate_item_name("a").is_ok());
+    }
+
+    #[test]
+    fn rejects_empty_item_name() {
+        assert!(validate_item_name("").is_err());
+    }
+
+    #[test]
+    fn rejects_item_name_with_underscores() {
+        assert!(validate_item_name("bad_name").is_err());
+    }
+
+    #[test]
+    fn rejects_item_name_with_spaces() {
+        assert!(validate_item_name("bad name").is_err());
+    }
+
+    #[test]
+    fn rejects_item_name_with_dots() {
+        assert!(validate_item_name("bad.name").is_err());
+    }
+
+    #[test]
+    fn rejects_overly_long_item_name() {
+        let long_name = "a".repeat(128);
+        assert!(validate_item_name(&long_name).is_err());
+    }
+
+    #[test]
+    fn accepts_max_length_item_name() {
+        let name = "a".repeat(127);
+        assert!(validate_item_name(&name).is_ok());
+    }
+
+    // ── Item version validation ──
+
+    #[test]
+    fn validates_item_versions() {
+        assert!(validate_item_version(&"a".repeat(32)).is_ok());
+        assert!(validate_item_version("").is_err());
+        assert!(validate_item_version("v1/../x").is_err());
+    }
+
+    #[test]
+    fn versioned_grant_names_differ_from_latest() {
+        assert_eq!(versioned_name("db", None), "db");
+        assert_eq!(versioned_name("db", Some("v1")), "db/v1");
+    }
+
+    // ── Deleted vault recovery ──
+
+    #[test]
+    fn validates_vault_names_and_locations() {
+        assert!(validate_vault_name("kv-app-01").is_ok());
+        assert!(validate_vault_name("kv").is_err());
+        assert!(validate_vault_name("1vault").is_err());
+        assert!(validate_vault_name("kv/../other").is_err());
+        assert!(validate_vault_name(&"k".repeat(25)).is_err());
+
+        assert!(validate_location("westeurope").is_ok());
+        assert!(validate_location("").is_err());
+        assert!(validate_location("west europe").is_err());
+        assert!(validate_location("westeurope/deletedVaults").is_err());
+    }
+
+    // ── Audit truncation ──
+
+    #[test]
+    fn truncates_long_audit_field() {
+        let long = "a".repeat(2048);
+        let truncated = truncate_for_audit(long);
+        assert_eq!(truncated.len(), MAX_AUDIT_FIELD_LEN);
+    }
+
+    #[test]
+    fn preserves_short_audit_field() {
+        let short = "hello".to_string();
+        assert_eq!(truncate_for_audit(short.clone()), short);
+    }
+
+    // ── Export path validation ──
+
+    #[test]
+    fn export_path_must_stay_inside_root() {
+        let base =
+            std::env::temp_dir().join(format!("azvault-export-test-{}", uuid::Uuid::new_v4()));
+        let root = base.join("home");
+        std::fs::create_dir_all(&root).unwrap();
+
+        let inside = root.join("audit.json");
+        assert_eq!(
+            validate_export_path(inside.to_str().unwrap(), &root).unwrap(),
+            root.canonicalize().unwrap().join("audit.json")
+        );
+        let escaped = root.join("..").join("audit.json");
+        assert!(validate_export_path(escaped.to_str().unwrap(), &root).is_err());
+        assert!(validate_export_path("audit.json", &root).is_err());
+        let missing = root.join("missing").join("audit.json");
+        assert!(validate_export_path(missing.to_str().unwrap(), &root).is_err());
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    // ── Vault name extraction ──
+
+    #[test]
+    fn extracts_vault_name_from_uri() {
+        assert_eq!(
+            extract_vault_name("https://my-vault.vault.azure.net"),
+            "my-vault"
+        );
+    }
+
+    #[test]
+    fn extracts_vault_name_from_govcloud_uri() {
+        assert_eq!(
+            extract_vault_name("https://gov-vault.vault.usgovcloudapi.net"),
+            "gov-vault"
+        );
+    }
+
+    #[test]
+    fn extracts_vault_name_handles_trailing_slash() {
+        assert_eq!(
+            extract_vault_name("https://my-vault.vault.azure.net/"),
+            "my-vault"
+        );
+    }
+
+    // ── Vault index ──
+
+    #[test]
+    fn vault_index_key_ignores_case_and_trailing_slash() {
+        assert_eq!(
+            vault_index_key("https://My-Vault.vault.azure.net/"),
+            vault_index_key("https://my-vault.vault.azure.net")
+        );
+    }
+
+    #[tokio::test]
+    async fn index_vaults_maps_uri_to_arm_resource() {
+        let index = RwLock::new(HashMap::new());
+        let vault = KeyVaultInfo {
+            id: "/subscriptions/s/resourceGroups/rg/providers/Microsoft.KeyVault/vaults/kv"
+                .to_string(),
+            name: "kv".to_string(),
+            location: "westeurope".to_string(),
+            resource_group: "rg".to_string(),
+            vault_uri: "https://kv.vault.azure.net".to_string(),
+            tags: None,
+            soft_delete_enabled: None,
+            public_network_access: None,
+            network_default_deny: None,
+        };
+        index_vaults(&index, &[vault]).await;
+
+        let index = index.read().await;
+        let resolved = index.get(&vault_index_key("https://kv.vault.azure.net/"));
+        assert_eq!(resolved.map(|v| v.resource_group.as_str()), Some("rg"));
+    }
+
+    // ── Soft-delete errors ──
+
+    #[test]
+    fn maps_soft_delete_disabled_error() {
+        let azure = "[400] BadParameter: Soft delete is not enabled for this vault.".to_string();
+        assert_eq!(map_soft_delete_error(azure), SOFT_DELETE_DISABLED);
+    }
+
+    #[test]
+    fn keeps_unrelated_errors() {
+        let azure = "[403] Forbidden: Caller is not authorized.".to_string();
+        assert_eq!(map_soft_delete_error(azure.clone()), azure);
+    }
+
+    // ── Cloud detection ──
+
+    fn probe(cloud: Cloud, recognized: bool, instance: Option<&str>) -> CloudProbe {
+        CloudProbe {
+            cloud,
+            recognized,
+            cloud_instance: instance.map(str::to_string),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn detected_cloud_prefers_reported_instance() {
+        let probes = vec![
+            probe(Cloud::Public, true, Some("microsoftonline.us")),
+            probe(Cloud::UsGov, true, Some("microsoftonline.us")),
+            probe(Cloud::China, false, None),
+        ];
+        assert_eq!(detected_cloud(&probes), Some(Cloud::UsGov));
+        assert_eq!(
+            detected_cloud(&[probe(Cloud::China, true, None)]),
+            Some(Cloud::China)
+        );
+        assert_eq!(detected_cloud(&[probe(Cloud::Public, false, None)]), None);
+    }
+
+    #[test]
+    fn validates_tenant_hints() {
+        assert!(validate_tenant_hint("00000000-0000-0000-0000-000000000001").is_ok());
+        assert!(validate_tenant_hint("contoso.onmicrosoft.com").is_ok());
+        assert!(validate_tenant_hint("../common").is_err());
+        assert!(validate_tenant_hint("a/b").is_err());
+        assert!(validate_tenant_hint("").is_err());
+    }
+
+    // ── UI context ──
+
+    #[test]
+    fn stored_context_keeps_valid_fields() {
+        let context = stored_context(Some(serde_json::json!({
+            "tenantId": "0000-aaaa",
+            "subscriptionId": "1111-bbbb",
+            "vaultUri": "https://kv-a.vault.azure.net/"
+        })));
+        assert_eq!(context.tenant_id.as_deref(), Some("0000-aaaa"));
+        assert_eq!(context.subscription_id.as_deref(), Some("1111-bbbb"));
+        assert!(context.vault_uri.is_some());
+    }
+
+    #[test]
+    fn stored_context_drops_invalid_fields() {
+        let context = stored_context(Some(serde_json::json!({
+            "subscriptionId": "../etc",
+            "vaultUri": "http://kv-a.vault.azure.net"
+        })));
+        assert!(context.subscription_id.is_none());
+        assert!(context.vault_uri.is_none());
+
+        let empty = stored_context(Some(serde_json::json!("garbage")));
+        assert!(empty.tenant_id.is_none());
+        assert!(stored_context(None).vault_uri.is_none());
+    }
+
+    // ── Duplicate values ──
+
+    #[test]
+    fn salted_hash_depends_on_salt_and_value() {
+        let (salt, other_salt) = ([1u8; 32], [2u8; 32]);
+        let same = salted_value_hash(&salt, "alpha".to_string());
+        assert_eq!(same, salted_value_hash(&salt, "alpha".to_string()));
+        assert_ne!(same, salted_value_hash(&salt, "beta".to_string()));
+        assert_ne!(same, salted_value_hash(&other_salt, "alpha".to_string()));
+    }
+
+    #[test]
+    fn groups_only_shared_digests() {
+        let salt = [7u8; 32];
+        let digests = vec![
+            ("c".to_string(), salted_value_hash(&salt, "x".to_string())),
+            ("a".to_string(), salted_value_hash(&salt, "x".to_string())),
+            ("b".to_string(), salted_value_hash(&salt, "y".to_string())),
+        ];
+        assert_eq!(group_by_digest(digests), vec![vec!["a", "c"]]);
+    }
+
+    // ── Value sizes ──
+
+    #[test]
+    fn summarizes_value_sizes() {
+        let report = summarize_value_sizes(vec![
+            ("b".to_string(), 10),
+            ("c".to_string(), 3),
+            ("a".to_string(), 10),
+        ]);
+        assert_eq!(report.measured, 3);
+        assert_eq!(report.total_bytes, 23);
+        assert_eq!(report.average_bytes, 7);
+        assert_eq!(report.max_bytes, 10);
+        assert_eq!(report.largest.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn summarizes_empty_value_sizes() {
+        let report = summarize_value_sizes(Vec::new());
+        assert_eq!(report.measured, 0);
+        assert_eq!(report.average_bytes, 0);
+        assert!(report.largest.is_none());
+    }
+
+    // ── All items ──
+
+    fn listing<T>(items: Vec<T>, truncated: bool) -> Option<ListResult<T>> {
+        Some(ListResult {
+            items,
+            truncated,
+            error: None,
+            stale: false,
+            from_cache: false,
+        })
+    }
+
+    #[test]
+    fn all_items_audit_summarises_counts_and_failures() {
+        let mut secrets = listing(vec![1, 2], false);
+        let mut keys = listing(vec![1], true);
+        let certificates: Option<ListResult<i32>> = None;
+        assert_eq!(
+            all_items_audit_outcome(&secrets, &keys, &certificates),
+            (
+                "partial",
+                "secrets=2 keys=1+ certificates=error".to_string()
+            )
+        );
+
+        secrets = None;
+        keys = None;
+        assert_eq!(
+            all_items_audit_outcome(&secrets, &keys, &certificates).0,
+            "error"
+        );
+    }
+
+    // ── Bulk enable ──
+
+    #[test]
+    fn enabled_transition_records_old_and_new_state() {
+        assert_eq!(
+            enabled_transition(Some(true), false),
+            "enabled: true -> false"
+        );
+        assert_eq!(enabled_transition(None, true), "enabled: ? -> true");
+    }
+
+    // ── Tag policy ──
+
+    #[test]
+    fn normalizes_required_tag_keys() {
+        let keys = vec![" owner ".into(), "env".into(), "".into(), "owner".into()];
+        assert_eq!(normalize_tag_keys(keys).unwrap(), vec!["owner", "env"]);
+        let too_many = (0..=MAX_REQUIRED_TAG_KEYS)
+            .map(|i| format!("k{}", i))
+            .collect();
+        assert!(normalize_tag_keys(too_many).is_err());
+    }
+
+    #[test]
+    fn reports_missing_and_blank_required_tags() {
+        let tags: HashMap<String, String> = [("owner", "team-a"), ("env", " ")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let required = vec!["owner".to_string(), "env".to_string(), "app".to_string()];
+        assert_eq!(missing_tag_keys(Some(&tags), &required), vec!["env", "app"]);
+        assert_eq!(missing_tag_keys(None, &required), required);
+    }
+
+    #[test]
+    fn stored_tag_keys_ignores_invalid_values() {
+        assert_eq!(
+            stored_tag_keys(Some(serde_json::json!(["owner"]))),
+            vec!["owner"]
+        );
+        assert!(stored_tag_keys(Some(serde_json::json!("owner"))).is_empty());
+        assert!(stored_tag_keys(None).is_empty());
+    }
+
+    // ── Tag search ──
+
+    #[test]
+    fn tag_matches_key_and_optional_value() {
+        let tags = Some(HashMap::from([("app".to_string(), "billing".to_string())]));
+        assert!(tag_matches(&tags, "app", Some("billing")));
+        assert!(tag_matches(&tags, "app", None));
+        assert!(!tag_matches(&tags, "app", Some("payments")));
+        assert!(!tag_matches(&tags, "team", None));
+        assert!(!tag_matches(&None, "app", None));
+    }
+
+    // ── Subscription filter ──
+
+    fn subscription_in(id: &str, state: &str) -> Subscription {
+        Subscription {
+            subscription_id: id.to_string(),
+            display_name: id.to_string(),
+            state: state.to_string(),
+            tenant_id: "tenant".to_string(),
+        }
+    }
+
+    #[test]
+    fn filters_subscriptions_by_state_case_insensitively() {
+        let subscriptions = vec![
+            subscription_in("a", "Enabled"),
+            subscription_in("b", "Disabled"),
+            subscription_in("c", "Warned"),
+        ];
+        let enabled = filter_subscriptions(subscriptions.clone(), Some("enabled"));
+        assert_eq!(enabled.len(), 1);
+        assert_eq!(enabled[0].subscription_id, "a");
+        assert_eq!(filter_subscriptions(subscriptions, None).len(), 3);
+    }
+
+    // ── Identity claims ──
+
+    #[test]
+    fn identity_reads_v1_and_v2_claims() {
+        let v1 = identity_from_claims(&serde_json::json!({
+            "oid": "object",
+            "tid": "tenant",
+            "upn": "user@example.com",
+            "appid": "cli-app",
+        }));
+        assert_eq!(v1.object_id.as_deref(), Some("object"));
+        assert_eq!(v1.tenant_id.as_deref(), Some("tenant"));
+        assert_eq!(v1.preferred_username.as_deref(), Some("user@example.com"));
+        assert_eq!(v1.app_id.as_deref(), Some("cli-app"));
+
+        let v2 = identity_from_claims(&serde_json::json!({
+            "preferred_username": "user@example.com",
+            "azp": "cli-app",
+        }));
+        assert_eq!(v2.preferred_username.as_deref(), Some("user@example.com"));
+        assert_eq!(v2.app_id.as_deref(), Some("cli-app"));
+        assert_eq!(v2.object_id, None);
+    }
+
+    // ── Region grouping ──
+
+    fn vault_in(name: &str, location: &str) -> KeyVaultInfo {
+        KeyVaultInfo {
+            id: format!("/vaults/{}", name),
+            name: name.to_string(),
+            location: location.to_string(),
+            resource_group: "rg".to_string(),
+            vault_uri: format!("https://{}.vault.azure.net", name),
+            tags: None,
+            soft_delete_enabled: None,
+            public_network_access: None,
+            network_default_deny: None,
+        }
+    }
+
+    #[test]
+    fn groups_vaults_by_region_alphabetically() {
+        let groups = group_by_region(
+            vec![
+                vault_in("a", "westeurope"),
+                vault_in("b", "eastus"),
+                vault_in("c", "westeurope"),
+            ],
+            None,
+        );
+        let locations: Vec<&str> = groups.iter().map(|g| g.location.as_str()).collect();
+        assert_eq!(locations, vec!["eastus", "westeurope"]);
+        assert_eq!(groups[1].vaults.len(), 2);
+    }
+
+    #[test]
+    fn groups_home_region_first() {
+        let groups = group_by_region(
+            vec![vault_in("a", "eastus"), vault_in("b", "westeurope")],
+            Some("WestEurope"),
+        );
+        assert_eq!(groups[0].location, "westeurope");
+    }
+
+    // ── Result status helper ──
+
+    #[test]
+    fn result_status_success() {
+        let ok: Result<(), String> = Ok(());
+        assert_eq!(result_status(&ok), "success");
+    }
+
+    #[test]
+    fn result_status_error() {
+        let err: Result<(), String> = Err("fail".to_string());
+        assert_eq!(result_status(&err), "error");
+    }
+
+    // ── Certificate import ──
+
+    #[test]
+    fn accepts_base64_and_pem_certificate_payloads() {
+        assert!(validate_certificate_payload("QUJDREVGR0g=").is_ok());
+        assert!(validate_certificate_payload("QUJD\nREVG").is_ok());
+        assert!(validate_certificate_payload(
+            "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_certificate_payloads() {
+        assert!(validate_certificate_payload("").is_err());
+        assert!(validate_certificate_payload("not base64 !!").is_err());
+        let huge = "A".repeat(MAX_CERTIFICATE_IMPORT_BYTES + 4);
+        assert!(validate_certificate_payload(&huge).is_err());
     }
 
-    match format.as_str() {
-        "json" => serde_json::to_string_pretty(&items).map_err(|e| format!("Export error: {}", e)),
-        "csv" => {
-            if items.is_empty() {
-                return Ok(String::new());
-            }
+    // ── Listing audit outcome ──
 
-            let mut csv = String::new();
-
-            // Use the first item's keys as CSV headers
-            if let Some(first) = items.first() {
-                if let Some(obj) = first.as_object() {
-                    let headers: Vec<&String> = obj.keys().collect();
-                    csv.push_str(
-                        &headers
-                            .iter()
-                            .map(|h| h.as_str())
-                            .collect::<Vec<_>>()
-                            .join(","),
-                    );
-                    csv.push('\n');
-
-                    for item in &items {
-                        if let Some(obj) = item.as_object() {
-                            let row: Vec<String> = headers
-                                .iter()
-                                .map(|h| {
-                                    let val =
-                                        obj.get(*h).cloned().unwrap_or(serde_json::Value::Null);
-                                    match val {
-                                        serde_json::Value::String(s) => {
-                                            // Escape double quotes in CSV values
-                                            format!("\"{}\"", s.replace('"', "\"\""))
-                                        }
-                                        serde_json::Value::Null => String::new(),
-                                        other => other.to_string(),
-                                    }
-                                })
-                                .collect();
-                            csv.push_str(&row.join(","));
-                            csv.push('\n');
-                        }
-                    }
-                }
-            }
+    #[test]
+    fn list_audit_outcome_reports_truncation() {
+        let partial: Result<ListResult<u8>, String> = Ok(ListResult {
+            items: vec![1, 2],
+            truncated: true,
+            error: Some("[500] InternalServerError: boom".to_string()),
+            stale: false,
+            from_cache: false,
+        });
+        let (status, details) = list_audit_outcome(&partial);
+        assert_eq!(status, "partial");
+        assert!(details.unwrap().contains("truncated after 2 items"));
 
-            Ok(csv)
-        }
-        _ => Err(format!(
-            "Unsupported export format: '{}'. Use 'json' or 'csv'.",
-            format
-        )),
-    }
-}
+        let complete: Result<ListResult<u8>, String> = Ok(ListResult {
+            items: vec![],
+            truncated: false,
+            error: None,
+            stale: false,
+            from_cache: false,
+        });
+        assert_eq!(list_audit_outcome(&complete), ("success", None));
 
-// ─────────────────────────────────────────────
-// Validation Helpers
-// ─────────────────────────────────────────────
+        let failed: Result<ListResult<u8>, String> = Err("fail".to_string());
+        assert_eq!(list_audit_outcome(&failed).0, "error");
+    }
 
-/// Extracts the vault name from its URI (e.g., `https://my-vault.vault.azure.net` -> `my-vault`).
-fn extract_vault_name(vault_uri: &str) -> String {
-    vault_uri
-        .trim_start_matches("https://")
-        .split('.')
-        .next()
-        .unwrap_or("unknown")
-        .to_string()
-}
+    // ── Name prefix filter ──
 
-/// Returns `"success"` or `"error"` based on the result variant.
-fn result_status<T>(result: &Result<T, String>) -> &'static str {
-    if result.is_ok() {
-        "success"
-    } else {
-        "error"
+    #[test]
+    fn filters_listing_by_case_sensitive_prefix() {
+        let mut result: Result<ListResult<String>, String> = Ok(ListResult {
+            items: vec!["billing-a".into(), "Billing-b".into(), "infra-c".into()],
+            truncated: false,
+            error: None,
+            stale: false,
+            from_cache: false,
+        });
+        let details = filter_by_name_prefix(&mut result, Some("billing-"), |s| s.as_str(), None);
+        assert_eq!(result.unwrap().items, vec!["billing-a"]);
+        assert_eq!(details.as_deref(), Some("prefix 'billing-': 1 of 3 items"));
     }
-}
 
-/// Validates that a vault URI uses HTTPS and targets an Azure Key Vault endpoint.
-fn validate_vault_uri(vault_uri: &str) -> Result<(), String> {
-    let parsed = Url::parse(vault_uri).map_err(|_| "Invalid vault URI.".to_string())?;
-    if parsed.scheme() != "https" {
-        return Err("Vault URI must use HTTPS.".to_string());
+    #[test]
+    fn name_prefix_filter_keeps_existing_details() {
+        let mut result: Result<ListResult<String>, String> = Ok(ListResult {
+            items: vec!["a".into()],
+            truncated: true,
+            error: None,
+            stale: false,
+            from_cache: false,
+        });
+        let details = filter_by_name_prefix(
+            &mut result,
+            Some("a"),
+            |s| s.as_str(),
+            Some("truncated after 1 items".into()),
+        );
+        assert_eq!(
+            details.as_deref(),
+            Some("truncated after 1 items; prefix 'a': 1 of 1 items")
+        );
+        assert_eq!(
+            filter_by_name_prefix(&mut result, None, |s| s.as_str(), None),
+            None
+        );
     }
 
-    let host = parsed
-        .host_str()
-        .ok_or_else(|| "Vault URI must include a host.".to_string())?;
-    let allowed = host.ends_with(".vault.azure.net")
-        || host.ends_with(".vault.usgovcloudapi.net")
-        || host.ends_with(".vault.azure.cn");
-    if !allowed {
-        return Err("Vault URI must target an Azure Key Vault endpoint.".to_string());
+    #[test]
+    fn validates_name_prefix() {
+        assert_eq!(validate_name_prefix(Some(String::new())).unwrap(), None);
+        assert!(validate_name_prefix(Some("billing/".into())).is_err());
     }
 
-    Ok(())
-}
+    // ── Bulk helpers ──
 
-/// Validates an item name (secret/key/certificate):
-/// - Must be 1–127 characters
-/// - Only alphanumeric characters and hyphens
-fn validate_item_name(name: &str) -> Result<(), String> {
-    if name.is_empty() || name.len() > 127 {
-        return Err("Item name must be between 1 and 127 characters.".to_string());
+    #[test]
+    fn validates_bulk_size_bounds() {
+        assert!(validate_bulk_size(0).is_err());
+        assert!(validate_bulk_size(1).is_ok());
+        assert!(validate_bulk_size(MAX_BULK_ITEMS).is_ok());
+        assert!(validate_bulk_size(MAX_BULK_ITEMS + 1).is_err());
     }
-    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
-        return Err("Item name may only contain letters, numbers, and hyphens.".to_string());
+
+    #[test]
+    fn bulk_item_result_maps_outcomes() {
+        let ok = bulk_item_result("a".to_string(), Ok::<(), String>(()));
+        assert!(ok.success && ok.error.is_none());
+
+        let err = bulk_item_result::<()>("b".to_string(), Err("boom".to_string()));
+        assert!(!err.success);
+        assert_eq!(err.error.as_deref(), Some("boom"));
     }
-    Ok(())
-}
 
-/// Truncates a string to the audit field length limit.
-fn truncate_for_audit(value: String) -> String {
-    value.chars().take(MAX_AUDIT_FIELD_LEN).collect()
-}
+    #[test]
+    fn managed_guard_rejects_only_managed_secrets() {
+        assert_eq!(
+            managed_guard(Some(true)).unwrap_err(),
+            MANAGED_SECRET_READ_ONLY
+        );
+        assert!(managed_guard(Some(false)).is_ok());
+        assert!(managed_guard(None).is_ok());
+    }
 
-// ── Tests ──
+    #[test]
+    fn bulk_summary_tallies_outcomes() {
+        let results = vec![
+            bulk_item_result("a".to_string(), Ok::<(), String>(())),
+            bulk_item_result::<()>("b".to_string(), Err("boom".to_string())),
+            BulkItemResult {
+                name: "c".to_string(),
+                success: false,
+                skipped: true,
+                error: Some("Skipped".to_string()),
+            },
+        ];
+        let summary = bulk_summary("set_secrets_tags", &results);
+        assert_eq!(summary.total, 3);
+        assert_eq!(
+            (summary.succeeded, summary.failed, summary.skipped),
+            (1, 1, 1)
+        );
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn bulk_audit_outcome_reports_status_and_counts() {
+        let ok = bulk_item_result("a".to_string(), Ok::<(), String>(()));
+        let failed = bulk_item_result::<()>("b".to_string(), Err("boom".to_string()));
+        let skipped = BulkItemResult {
+            name: "c".to_string(),
+            success: false,
+            skipped: true,
+            error: Some("Skipped".to_string()),
+        };
 
-    // ── Vault URI validation ──
+        assert_eq!(
+            bulk_audit_outcome("purged", &[ok.clone(), skipped.clone()]),
+            ("success", "purged 1 of 2; 1 skipped, 0 failed".to_string())
+        );
+        assert_eq!(
+            bulk_audit_outcome("purged", &[ok, failed.clone()]).0,
+            "partial"
+        );
+        assert_eq!(bulk_audit_outcome("purged", &[failed, skipped]).0, "error");
+        assert_eq!(bulk_audit_outcome("purged", &[]).0, "success");
+    }
 
     #[test]
-    fn accepts_valid_azure_public_vault_uri() {
-        assert!(validate_vault_uri("https://demo.vault.azure.net").is_ok());
+    fn purgeable_recovery_levels() {
+        assert!(is_purgeable(Some("Recoverable+Purgeable")));
+        assert!(is_purgeable(Some("CustomizedRecoverable+Purgeable")));
+        assert!(!is_purgeable(Some("Recoverable")));
+        assert!(!is_purgeable(Some("Recoverable+ProtectedSubscription")));
+        assert!(is_purgeable(None));
     }
 
+    // ── Permission checks ──
+
     #[test]
-    fn accepts_valid_us_gov_vault_uri() {
-        assert!(validate_vault_uri("https://demo.vault.usgovcloudapi.net").is_ok());
+    fn probe_collection_for_known_actions() {
+        assert_eq!(probe_collection_for("list_keys").unwrap(), "keys");
+        assert_eq!(probe_collection_for("purge").unwrap(), "deletedsecrets");
+        assert!(probe_collection_for("delete_vault").is_err());
     }
 
     #[test]
-    fn accepts_valid_china_vault_uri() {
-        assert!(validate_vault_uri("https://demo.vault.azure.cn").is_ok());
+    fn permission_access_maps_status_codes() {
+        assert_eq!(
+            permission_access_for("[403] Forbidden: denied | Hint: Check your role."),
+            PermissionAccess::Denied
+        );
+        assert_eq!(
+            permission_access_for("[500] InternalServerError: boom"),
+            PermissionAccess::Unknown
+        );
+        assert_eq!(
+            permission_access_for("Network error: timed out"),
+            PermissionAccess::Unknown
+        );
     }
 
+    // ── Secret content validation ──
+
     #[test]
-    fn rejects_http_vault_uri() {
-        assert!(validate_vault_uri("http://demo.vault.azure.net").is_err());
+    fn content_validation_rejects_malformed_json() {
+        let err = validate_secret_content(Some("application/json"), "{\"a\": ").unwrap_err();
+        assert!(err.contains("not valid JSON"));
+        assert!(validate_secret_content(Some("application/json; charset=utf-8"), "{}").is_ok());
     }
 
     #[test]
-    fn rejects_non_azure_vault_uri() {
-        assert!(validate_vault_uri("https://evil.example.com").is_err());
+    fn content_validation_checks_pkcs12_shape() {
+        assert!(validate_secret_content(Some("application/x-pkcs12"), "not base64!").is_err());
+        assert!(validate_secret_content(Some("application/x-pkcs12"), "MAAA").is_err());
+        let der = STANDARD.encode([0x30u8; MIN_PKCS12_BYTES]);
+        assert!(validate_secret_content(Some("application/x-pkcs12"), &der).is_ok());
     }
 
     #[test]
-    fn rejects_empty_vault_uri() {
-        assert!(validate_vault_uri("").is_err());
+    fn mime_types_must_be_plausible() {
+        assert_eq!(
+            validate_mime_type(" application/json ").unwrap(),
+            "application/json"
+        );
+        assert!(validate_mime_type("text/plain; charset=utf-8").is_ok());
+        assert!(validate_mime_type("application/vnd.ms-excel+xml").is_ok());
+        assert!(validate_mime_type("connection string").is_err());
+        assert!(validate_mime_type("text/").is_err());
+        assert!(validate_mime_type("text/plain; charset").is_err());
+        assert!(validate_mime_type("text/plain\nx").is_err());
+        assert!(validate_mime_type("").is_err());
     }
 
     #[test]
-    fn rejects_vault_uri_without_host() {
-        assert!(validate_vault_uri("https://").is_err());
+    fn content_validation_ignores_other_types() {
+        assert!(validate_secret_content(Some("text/plain"), "{").is_ok());
+        assert!(validate_secret_content(None, "{").is_ok());
     }
 
-    // ── Item name validation ──
+    // ── Key rotation policy ──
 
-    #[test]
-    fn accepts_valid_item_name() {
-        assert!(validate_item_name("valid-name-01").is_ok());
+    fn rotate_after(
+        duration: Option<&str>,
+        before: Option<&str>,
+        action: &str,
+    ) -> KeyRotationPolicy {
+        KeyRotationPolicy {
+            id: None,
+            lifetime_actions: vec![LifetimeAction {
+                action: action.to_string(),
+                time_after_create: duration.map(|d| d.to_string()),
+                time_before_expiry: before.map(|d| d.to_string()),
+            }],
+            expiry_time: None,
+        }
     }
 
     #[test]
-    fn accepts_single_char_name() {
-        assert!(validate_item_name("a").is_ok());
+    fn validates_rotation_policy_actions() {
+        assert!(validate_rotation_policy(&rotate_after(Some("P90D"), None, "Rotate")).is_ok());
+        assert!(validate_rotation_policy(&rotate_after(None, Some("P30D"), "notify")).is_ok());
+        assert!(validate_rotation_policy(&rotate_after(None, None, "Rotate")).is_err());
+        assert!(
+            validate_rotation_policy(&rotate_after(Some("P90D"), Some("P30D"), "Rotate")).is_err()
+        );
+        assert!(validate_rotation_policy(&rotate_after(Some("90 days"), None, "Rotate")).is_err());
+        assert!(validate_rotation_policy(&rotate_after(Some("P90D"), None, "Delete")).is_err());
     }
 
+    // ── Item counts ──
+
     #[test]
-    fn rejects_empty_item_name() {
-        assert!(validate_item_name("").is_err());
+    fn listing_count_rejects_truncated_listings() {
+        let complete = ListResult {
+            items: vec![1, 2, 3],
+            truncated: false,
+            error: None,
+            stale: false,
+            from_cache: false,
+        };
+        assert_eq!(listing_count(Ok(complete)), Ok(3));
+
+        let partial = ListResult {
+            items: vec![1],
+            truncated: true,
+            error: Some("[503] page 2 failed".to_string()),
+            stale: false,
+            from_cache: false,
+        };
+        assert_eq!(
+            listing_count(Ok(partial)),
+            Err("[503] page 2 failed".to_string())
+        );
     }
 
-    #[test]
-    fn rejects_item_name_with_underscores() {
-        assert!(validate_item_name("bad_name").is_err());
+    // ── Certificate expiry ──
+
+    fn certificate_expiring_at(name: &str, expires_epoch: Option<i64>) -> CertificateItem {
+        CertificateItem {
+            id: format!("https://kv.vault.azure.net/certificates/{}", name),
+            name: name.to_string(),
+            enabled: true,
+            created: None,
+            updated: None,
+            expires: None,
+            not_before: None,
+            created_epoch: None,
+            updated_epoch: None,
+            expires_epoch,
+            recovery_level: None,
+            subject: Some(format!("CN={}", name)),
+            thumbprint: None,
+            tags: None,
+        }
     }
 
     #[test]
-    fn rejects_item_name_with_spaces() {
-        assert!(validate_item_name("bad name").is_err());
+    fn expiring_within_keeps_window_and_expired_soonest_first() {
+        let now = 1_700_000_000;
+        let day = 86_400;
+        let certs = vec![
+            certificate_expiring_at("later", Some(now + 60 * day)),
+            certificate_expiring_at("soon", Some(now + 10 * day)),
+            certificate_expiring_at("expired", Some(now - 2 * day)),
+            certificate_expiring_at("no-expiry", None),
+        ];
+
+        let expiring = expiring_within(certs, 30, now);
+        let names: Vec<&str> = expiring.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["expired", "soon"]);
+        assert!(expiring[0].expired);
+        assert_eq!(expiring[0].days_remaining, -2);
+        assert_eq!(expiring[1].days_remaining, 10);
+        assert_eq!(expiring[1].subject.as_deref(), Some("CN=soon"));
     }
 
+    // ── Vault comparison ──
+
     #[test]
-    fn rejects_item_name_with_dots() {
-        assert!(validate_item_name("bad.name").is_err());
+    fn compare_names_splits_inventories() {
+        let a: BTreeSet<String> = ["db", "api", "shared"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let b: BTreeSet<String> = ["shared", "cache"].iter().map(|s| s.to_string()).collect();
+        let diff = compare_names(&a, &b);
+        assert_eq!(diff.only_in_a, vec!["api", "db"]);
+        assert_eq!(diff.only_in_b, vec!["cache"]);
+        assert_eq!(diff.in_both, vec!["shared"]);
     }
 
     #[test]
-    fn rejects_overly_long_item_name() {
-        let long_name = "a".repeat(128);
-        assert!(validate_item_name(&long_name).is_err());
+    fn secret_names_rejects_truncated_listing() {
+        let partial = Ok(ListResult {
+            items: vec![],
+            truncated: true,
+            error: Some("[500] InternalServerError: boom".to_string()),
+            stale: false,
+            from_cache: false,
+        });
+        let err = secret_names("https://kv.vault.azure.net", partial).unwrap_err();
+        assert!(err.contains("Could not list all secrets in kv"));
     }
 
     #[test]
-    fn accepts_max_length_item_name() {
-        let name = "a".repeat(127);
-        assert!(validate_item_name(&name).is_ok());
+    fn merge_tags_overrides_conflicting_keys() {
+        let existing = HashMap::from([
+            ("env".to_string(), "dev".to_string()),
+            ("team".to_string(), "core".to_string()),
+        ]);
+        let updates = HashMap::from([("env".to_string(), "prod".to_string())]);
+        let merged = merge_tags(Some(existing), &updates);
+        assert_eq!(merged.get("env").unwrap(), "prod");
+        assert_eq!(merged.get("team").unwrap(), "core");
+
+        assert_eq!(merge_tags(None, &updates), updates);
     }
 
-    // ── Audit truncation ──
+    #[test]
+    fn validate_tags_enforces_key_vault_limits() {
+        let tags = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        assert!(validate_tags(&tags(&[("env", "prod")])).is_ok());
+        assert!(validate_tags(&tags(&[("  ", "x")])).is_err());
+        let (long_key, long_value) = (
+            "k".repeat(MAX_TAG_KEY_LEN + 1),
+            "v".repeat(MAX_TAG_VALUE_LEN + 1),
+        );
+        assert!(validate_tags(&tags(&[("env", long_value.as_str())])).is_err());
+        assert!(validate_tags(&tags(&[(long_key.as_str(), "v")])).is_err());
+
+        let many: HashMap<String, String> = (0..=MAX_ITEM_TAGS)
+            .map(|i| (format!("k{}", i), "v".to_string()))
+            .collect();
+        assert!(validate_tags(&many).is_err());
+    }
 
     #[test]
-    fn truncates_long_audit_field() {
-        let long = "a".repeat(2048);
-        let truncated = truncate_for_audit(long);
-        assert_eq!(truncated.len(), MAX_AUDIT_FIELD_LEN);
+    fn removed_required_tag_keys_reports_only_stripped_keys() {
+        let required = vec!["owner".to_string(), "env".to_string()];
+        let current = HashMap::from([("owner".to_string(), "team-a".to_string())]);
+        let replaced = HashMap::from([("cost".to_string(), "42".to_string())]);
+        assert_eq!(
+            removed_required_tag_keys(Some(&current), &replaced, &required),
+            vec!["owner".to_string()]
+        );
+
+        // `env` was already missing, so adding other tags is not blocked
+        let merged = merge_tags(Some(current.clone()), &replaced);
+        assert!(removed_required_tag_keys(Some(&current), &merged, &required).is_empty());
+        assert!(removed_required_tag_keys(Some(&current), &replaced, &[]).is_empty());
     }
 
+    // ── Ensure secret ──
+
     #[test]
-    fn preserves_short_audit_field() {
-        let short = "hello".to_string();
-        assert_eq!(truncate_for_audit(short.clone()), short);
+    fn secret_existence_treats_only_404_as_free() {
+        assert_eq!(
+            secret_existence(Ok(current_secret())).unwrap(),
+            SecretExistence {
+                exists: true,
+                enabled: Some(true)
+            }
+        );
+        assert_eq!(
+            secret_existence(Err("[404] SecretNotFound: gone".to_string())).unwrap(),
+            SecretExistence {
+                exists: false,
+                enabled: None
+            }
+        );
+        assert!(secret_existence(Err("[403] Forbidden: denied".to_string())).is_err());
     }
 
-    // ── Vault name extraction ──
+    // ── Key roundtrip ──
+
+    fn rsa_key(ops: &[&str]) -> KeyItem {
+        KeyItem {
+            id: "https://kv.vault.azure.net/keys/app/v1".to_string(),
+            name: "app".to_string(),
+            enabled: true,
+            created: None,
+            updated: None,
+            expires: None,
+            not_before: None,
+            created_epoch: None,
+            updated_epoch: None,
+            expires_epoch: None,
+            recovery_level: None,
+            key_type: Some("RSA-HSM".to_string()),
+            key_ops: Some(ops.iter().map(|op| op.to_string()).collect()),
+            tags: None,
+            managed: None,
+        }
+    }
 
     #[test]
-    fn extracts_vault_name_from_uri() {
+    fn roundtrip_algorithm_is_canonicalised() {
         assert_eq!(
-            extract_vault_name("https://my-vault.vault.azure.net"),
-            "my-vault"
+            validate_roundtrip_algorithm(" rsa-oaep-256 ").unwrap(),
+            "RSA-OAEP-256"
         );
+        assert!(validate_roundtrip_algorithm("A256GCM").is_err());
     }
 
     #[test]
-    fn extracts_vault_name_from_govcloud_uri() {
+    fn roundtrip_requires_usable_rsa_key_version() {
         assert_eq!(
-            extract_vault_name("https://gov-vault.vault.usgovcloudapi.net"),
-            "gov-vault"
+            roundtrip_key_version(&rsa_key(&["encrypt", "decrypt"])).unwrap(),
+            "v1"
         );
+        assert!(roundtrip_key_version(&rsa_key(&["encrypt"]))
+            .unwrap_err()
+            .contains("decrypt"));
+
+        let mut ec = rsa_key(&["encrypt", "decrypt"]);
+        ec.key_type = Some("EC".to_string());
+        assert!(roundtrip_key_version(&ec).is_err());
+
+        let mut unversioned = rsa_key(&["encrypt", "decrypt"]);
+        unversioned.id = "https://kv.vault.azure.net/keys/app".to_string();
+        assert!(roundtrip_key_version(&unversioned).is_err());
     }
 
     #[test]
-    fn extracts_vault_name_handles_trailing_slash() {
+    fn not_yet_active_lists_future_enabled_secrets_soonest_first() {
+        let now = chrono::DateTime::parse_from_rfc3339("2030-01-01T00:00:00+00:00")
+            .unwrap()
+            .timestamp();
+        let secret = |name: &str, not_before: Option<&str>, enabled: bool| SecretItem {
+            name: name.to_string(),
+            not_before: not_before.map(|nbf| nbf.to_string()),
+            enabled,
+            ..current_secret()
+        };
+        let secrets = vec![
+            secret("later", Some("2030-03-01T00:00:00+00:00"), true),
+            secret("active", Some("2029-12-31T00:00:00+00:00"), true),
+            secret("soon", Some("2030-01-02T00:00:00+00:00"), true),
+            secret("disabled", Some("2030-01-02T00:00:00+00:00"), false),
+            secret("unset", None, true),
+        ];
+
+        let names: Vec<String> = not_yet_active(secrets, now)
+            .into_iter()
+            .map(|secret| secret.name)
+            .collect();
+        assert_eq!(names, vec!["soon", "later"]);
+    }
+
+    fn current_secret() -> SecretItem {
+        SecretItem {
+            id: "https://kv.vault.azure.net/secrets/app/v1".to_string(),
+            name: "app".to_string(),
+            version: Some("v1".to_string()),
+            enabled: true,
+            created: None,
+            updated: None,
+            expires: Some("2030-01-01T00:00:00+00:00".to_string()),
+            not_before: None,
+            created_epoch: None,
+            updated_epoch: None,
+            expires_epoch: None,
+            recovery_level: None,
+            content_type: Some("text/plain".to_string()),
+            tags: Some(HashMap::from([("env".to_string(), "prod".to_string())])),
+            managed: None,
+        }
+    }
+
+    fn desired_secret() -> CreateSecretRequest {
+        CreateSecretRequest {
+            name: "app".to_string(),
+            value: "value".to_string(),
+            content_type: Some("text/plain".to_string()),
+            tags: Some(HashMap::from([("env".to_string(), "prod".to_string())])),
+            enabled: None,
+            expires: Some("2030-01-01T00:00:00Z".to_string()),
+            not_before: None,
+        }
+    }
+
+    #[test]
+    fn matching_attributes_do_not_differ() {
+        assert!(!secret_attributes_differ(
+            &current_secret(),
+            &desired_secret()
+        ));
+    }
+
+    #[test]
+    fn tag_or_attribute_changes_differ() {
+        let mut request = desired_secret();
+        request.tags = None;
+        assert!(secret_attributes_differ(&current_secret(), &request));
+
+        let mut request = desired_secret();
+        request.enabled = Some(false);
+        assert!(secret_attributes_differ(&current_secret(), &request));
+
+        let mut request = desired_secret();
+        request.expires = Some("2031-01-01T00:00:00Z".to_string());
+        assert!(secret_attributes_differ(&current_secret(), &request));
+    }
+
+    // ── Import ──
+
+    #[test]
+    fn parses_quoted_csv_fields() {
+        let rows = parse_csv("name,value\r\n\"a\",\"x, \"\"y\"\"\nz\"\n\nb,plain").unwrap();
         assert_eq!(
-            extract_vault_name("https://my-vault.vault.azure.net/"),
-            "my-vault"
+            rows,
+            vec![
+                vec!["name", "value"],
+                vec!["a", "x, \"y\"\nz"],
+                vec!["b", "plain"],
+            ]
         );
+        assert!(parse_csv("name,value\n\"open").is_err());
     }
 
-    // ── Result status helper ──
+    #[test]
+    fn maps_csv_columns_by_header() {
+        let requests =
+            parse_import("contentType,value,name\ntext/plain,one,a\n,two,b", "csv").unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].name, "a");
+        assert_eq!(requests[0].value, "one");
+        assert_eq!(requests[0].content_type.as_deref(), Some("text/plain"));
+        assert!(requests[1].content_type.is_none());
+
+        assert!(parse_import("name,data\na,one", "csv").is_err());
+    }
 
     #[test]
-    fn result_status_success() {
-        let ok: Result<(), String> = Ok(());
-        assert_eq!(result_status(&ok), "success");
+    fn parses_json_import_with_tags() {
+        let requests = parse_import(
+            r#"[{"name":"a","value":"one","tags":{"env":"dev"},"contentType":"text/plain"}]"#,
+            "json",
+        )
+        .unwrap();
+        assert_eq!(requests[0].tags.as_ref().unwrap()["env"], "dev");
+        assert_eq!(requests[0].content_type.as_deref(), Some("text/plain"));
     }
 
     #[test]
-    fn result_status_error() {
-        let err: Result<(), String> = Err("fail".to_string());
-        assert_eq!(result_status(&err), "error");
+    fn rejects_duplicate_names_and_unknown_formats() {
+        assert!(parse_import("name,value\na,one\nA,two", "csv").is_err());
+        assert!(parse_import("[]", "yaml").is_err());
     }
 
     // ── Export ──
@@ -758,7 +6500,7 @@ mod tests {
     #[tokio::test]
     async fn exports_items_as_json() {
         let input = r#"[{"name":"secret-1"},{"name":"secret-2"}]"#.to_string();
-        let out = export_items(input, "json".to_string())
+        let out = export_items(input, "json".to_string(), None, None)
             .await
             .expect("json export should succeed");
         assert!(out.contains("secret-1"));
@@ -768,7 +6510,7 @@ mod tests {
     #[tokio::test]
     async fn exports_items_as_csv() {
         let input = r#"[{"name":"n1","enabled":true},{"name":"n2","enabled":false}]"#.to_string();
-        let out = export_items(input, "csv".to_string())
+        let out = export_items(input, "csv".to_string(), None, None)
             .await
             .expect("csv export should succeed");
         assert!(out.lines().count() >= 2, "should have header + data rows");
@@ -779,7 +6521,7 @@ mod tests {
     #[tokio::test]
     async fn exports_csv_escapes_quotes_and_nulls() {
         let input = r#"[{"name":"db\"prod","enabled":null,"count":3}]"#.to_string();
-        let out = export_items(input, "csv".to_string())
+        let out = export_items(input, "csv".to_string(), None, None)
             .await
             .expect("csv export should succeed");
         assert!(
@@ -792,10 +6534,104 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn export_projection_keeps_only_requested_fields() {
+        let input = r#"[{"id":"https://kv.vault.azure.net/secrets/a","name":"a","enabled":true}]"#
+            .to_string();
+        let fields = vec!["name".to_string(), "enabled".to_string()];
+        let out = export_items(input, "json".to_string(), Some(fields), None)
+            .await
+            .expect("json export should succeed");
+        assert!(out.contains("\"name\""));
+        assert!(out.contains("\"enabled\""));
+        assert!(!out.contains("kv.vault.azure.net"), "id must be dropped");
+    }
+
+    #[tokio::test]
+    async fn export_projection_orders_csv_columns_and_blanks_unknown_fields() {
+        let input = r#"[{"name":"a","enabled":true,"expires":null}]"#.to_string();
+        let fields = vec![
+            "enabled".to_string(),
+            "name".to_string(),
+            "owner".to_string(),
+        ];
+        let out = export_items(input, "csv".to_string(), Some(fields), None)
+            .await
+            .expect("csv export should succeed");
+        assert_eq!(out, "enabled,name,owner\ntrue,\"a\",\n");
+    }
+
+    #[tokio::test]
+    async fn csv_columns_include_keys_missing_from_first_item() {
+        let input = r#"[{"name":"a"},{"name":"b","contentType":"text/plain"}]"#.to_string();
+        let out = export_items(input, "csv".to_string(), None, None)
+            .await
+            .expect("csv export should succeed");
+        assert_eq!(out, "name,contentType\n\"a\",\n\"b\",\"text/plain\"\n");
+    }
+
+    #[tokio::test]
+    async fn groups_json_export_by_content_type() {
+        let input = r#"[{"name":"a","contentType":"application/json"},{"name":"b","contentType":null},{"name":"c","contentType":"application/json"}]"#
+            .to_string();
+        let out = export_items(input, "json".to_string(), None, Some(true))
+            .await
+            .expect("json export should succeed");
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["application/json"].as_array().unwrap().len(), 2);
+        assert_eq!(parsed["(none)"][0]["name"], "b");
+    }
+
+    #[tokio::test]
+    async fn groups_csv_export_into_sections() {
+        let input = r#"[{"name":"a","contentType":"text/plain"},{"name":"b"}]"#.to_string();
+        let fields = vec!["name".to_string()];
+        let out = export_items(input, "csv".to_string(), Some(fields), Some(true))
+            .await
+            .expect("csv export should succeed");
+        assert_eq!(
+            out,
+            "# contentType: (none)\nname\n\"b\"\n\n# contentType: text/plain\nname\n\"a\"\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn exports_items_as_ndjson_lines() {
+        let input =
+            r#"[{"id":"x","name":"a","enabled":true},{"id":"y","name":"b\nc"}]"#.to_string();
+        let fields = vec!["name".to_string(), "enabled".to_string()];
+        let out = export_items(input, "ndjson".to_string(), Some(fields), None)
+            .await
+            .expect("ndjson export should succeed");
+        assert!(out.ends_with('\n'));
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line is JSON"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.is_object()));
+        assert_eq!(
+            lines[0],
+            serde_json::json!({ "name": "a", "enabled": true })
+        );
+        assert_eq!(lines[1]["name"], "b\nc");
+    }
+
+    #[tokio::test]
+    async fn advertised_export_formats_are_accepted() {
+        for format in EXPORT_FORMATS {
+            assert!(
+                export_items("[]".to_string(), format.to_string(), None, None)
+                    .await
+                    .is_ok()
+            );
+        }
+    }
+
     #[tokio::test]
     async fn exports_empty_csv() {
         let input = "[]".to_string();
-        let out = export_items(input, "csv".to_string())
+        let out = export_items(input, "csv".to_string(), None, None)
             .await
             .expect("empty csv should succeed");
         assert_eq!(out, "");
@@ -804,7 +6640,7 @@ mod tests {
     #[tokio::test]
     async fn rejects_oversized_export_payload() {
         let huge = "a".repeat(MAX_EXPORT_INPUT_BYTES + 10);
-        let err = export_items(huge, "json".to_string())
+        let err = export_items(huge, "json".to_string(), None, None)
             .await
             .expect_err("should reject oversized payload");
         assert!(err.contains("too large"));
@@ -813,7 +6649,7 @@ mod tests {
     #[tokio::test]
     async fn rejects_unsupported_export_format() {
         let input = r#"[{"name":"test"}]"#.to_string();
-        let err = export_items(input, "xml".to_string())
+        let err = export_items(input, "xml".to_string(), None, None)
             .await
             .expect_err("should reject xml format");
         assert!(err.contains("Unsupported"));
@@ -821,7 +6657,7 @@ mod tests {
 
     #[tokio::test]
     async fn rejects_invalid_json_export() {
-        let err = export_items("not json".to_string(), "json".to_string())
+        let err = export_items("not json".to_string(), "json".to_string(), None, None)
             .await
             .expect_err("should reject invalid json");
         assert!(err.contains("Invalid JSON"));
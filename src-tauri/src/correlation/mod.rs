@@ -0,0 +1,54 @@
+//! Per-operation correlation ids.
+//!
+//! A command runs its work inside `scope`, which assigns one GUID to the
+//! operation. `AzureClient` sends it as `x-ms-client-request-id` on every
+//! request of the operation, and `AuditLogger` records it on the resulting
+//! entry, so an audit entry can be matched to Azure's server-side logs.
+//! Requests made outside a scope get a fresh id each.
+
+use std::future::Future;
+
+tokio::task_local! {
+    static OPERATION_ID: String;
+}
+
+/// Runs `operation` under a fresh correlation id. Nested scopes (e.g. one
+/// per item of a bulk command) take precedence over the enclosing one.
+pub async fn scope<F: Future>(operation: F) -> F::Output {
+    OPERATION_ID
+        .scope(uuid::Uuid::new_v4().to_string(), operation)
+        .await
+}
+
+/// Returns the correlation id of the current operation, if any.
+pub fn current() -> Option<String> {
+    OPERATION_ID.try_with(|id| id.clone()).ok()
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn scope_assigns_one_id_per_operation() {
+        assert!(current().is_none());
+        let (first, again) = scope(async { (current(), current()) }).await;
+        assert!(first.is_some());
+        assert_eq!(first, again);
+        assert_ne!(scope(async { current() }).await, first);
+    }
+
+    #[tokio::test]
+    async fn nested_scope_overrides_outer_id() {
+        let (outer, inner, restored) = scope(async {
+            let outer = current();
+            let inner = scope(async { current() }).await;
+            (outer, inner, current())
+        })
+        .await;
+        assert_ne!(outer, inner);
+        assert_eq!(outer, restored);
+    }
+}
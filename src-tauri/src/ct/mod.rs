@@ -0,0 +1,32 @@
+//! Constant-time comparison for secret values and security tokens.
+//!
+//! Call sites:
+//! - `ItemGrants::redeem`: reveal and purge confirmation tokens.
+//! - `ensure_secret`: a secret's current value against the requested one.
+//! - `roundtrip_key_test`: a decrypted probe against the original.
+//!
+//! Confirmation phrases (vault names) are not secret and use `==`.
+
+/// Compares two byte strings in time independent of where they differ.
+/// Lengths are not hidden.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_bytes() {
+        assert!(constant_time_eq(b"same", b"same"));
+        assert!(!constant_time_eq(b"same", b"sane"));
+        assert!(!constant_time_eq(b"same", b"same-longer"));
+        assert!(constant_time_eq(b"", b""));
+    }
+}
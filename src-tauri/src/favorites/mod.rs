@@ -0,0 +1,119 @@
+//! Locally persisted list of favorite (pinned) vault URIs.
+//!
+//! Favorites are stored as a JSON array in the app data directory. Only
+//! vault URIs are kept; callers validate them before they are added.
+
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+
+/// Maximum number of favorite vaults kept.
+const MAX_FAVORITES: usize = 100;
+
+/// In-memory favorites backed by `favorites.json`.
+pub struct FavoritesStore {
+    uris: RwLock<Vec<String>>,
+    path: PathBuf,
+}
+
+impl FavoritesStore {
+    /// Loads any previously persisted favorites from the app data directory.
+    pub fn new(app_data_dir: PathBuf) -> Self {
+        let path = app_data_dir.join("favorites.json");
+        let uris = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            uris: RwLock::new(uris),
+            path,
+        }
+    }
+
+    /// Returns the favorite vault URIs in the order they were added.
+    pub async fn list(&self) -> Vec<String> {
+        self.uris.read().await.clone()
+    }
+
+    /// Adds a vault URI (ignoring case and trailing slashes for duplicates).
+    pub async fn add(&self, vault_uri: &str) -> Result<Vec<String>, String> {
+        let vault_uri = vault_uri.trim_end_matches('/').to_string();
+        let mut uris = self.uris.write().await;
+        if !uris.iter().any(|u| u.eq_ignore_ascii_case(&vault_uri)) {
+            if uris.len() >= MAX_FAVORITES {
+                return Err(format!(
+                    "At most {} favorite vaults are supported.",
+                    MAX_FAVORITES
+                ));
+            }
+            uris.push(vault_uri);
+            self.save(&uris)?;
+        }
+        Ok(uris.clone())
+    }
+
+    /// Removes a vault URI if present.
+    pub async fn remove(&self, vault_uri: &str) -> Result<Vec<String>, String> {
+        let vault_uri = vault_uri.trim_end_matches('/');
+        let mut uris = self.uris.write().await;
+        let before = uris.len();
+        uris.retain(|u| !u.eq_ignore_ascii_case(vault_uri));
+        if uris.len() != before {
+            self.save(&uris)?;
+        }
+        Ok(uris.clone())
+    }
+
+    /// Writes the favorites to disk.
+    fn save(&self, uris: &[String]) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        let json = serde_json::to_string_pretty(uris).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, json).map_err(|e| format!("Failed to save favorites: {}", e))
+    }
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("azvault-favorites-test-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn add_deduplicates_and_persists() {
+        let dir = temp_dir();
+        {
+            let store = FavoritesStore::new(dir.clone());
+            store.add("https://kv-a.vault.azure.net").await.unwrap();
+            store.add("https://KV-A.vault.azure.net/").await.unwrap();
+            store.add("https://kv-b.vault.azure.net/").await.unwrap();
+        }
+
+        let store = FavoritesStore::new(dir.clone());
+        assert_eq!(
+            store.list().await,
+            vec![
+                "https://kv-a.vault.azure.net",
+                "https://kv-b.vault.azure.net"
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn remove_drops_matching_uri() {
+        let dir = temp_dir();
+        let store = FavoritesStore::new(dir.clone());
+        store.add("https://kv-a.vault.azure.net").await.unwrap();
+
+        let remaining = store.remove("https://kv-a.vault.azure.net/").await.unwrap();
+        assert!(remaining.is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
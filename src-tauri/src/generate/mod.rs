@@ -0,0 +1,188 @@
+//! Random secret value generation.
+//!
+//! Security guarantees:
+//! - Values are drawn from the operating system CSPRNG (`OsRng`).
+//! - Characters are chosen by uniform sampling (no modulo bias).
+//! - Policy passwords contain at least one character of every required class.
+//! - Generated values are never logged or audited.
+
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Minimum length of a generated value.
+pub const MIN_LENGTH: usize = 8;
+
+/// Maximum length of a generated value (well below the 25KB Azure limit).
+pub const MAX_LENGTH: usize = 1024;
+
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!#$%&()*+,-./:;<=>?@[]^_{|}~";
+
+/// Characters easily confused with one another when read or typed.
+const AMBIGUOUS: &str = "Il1O0o|";
+
+/// Character set used for a generated value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    /// `A-Z`, `a-z`, `0-9`.
+    Alphanumeric,
+    /// Lowercase hexadecimal digits.
+    Hex,
+    /// URL-safe base64 alphabet (`A-Z`, `a-z`, `0-9`, `-`, `_`).
+    Base64Url,
+    /// Alphanumerics plus punctuation symbols.
+    Ascii,
+}
+
+impl Charset {
+    /// Parses a charset name as sent by the UI.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "alphanumeric" => Ok(Self::Alphanumeric),
+            "hex" => Ok(Self::Hex),
+            "base64url" => Ok(Self::Base64Url),
+            "ascii" => Ok(Self::Ascii),
+            other => Err(format!(
+                "Unknown charset '{}'. Use alphanumeric, hex, base64url, or ascii.",
+                other
+            )),
+        }
+    }
+
+    /// Returns the characters this charset draws from.
+    fn alphabet(self) -> Vec<char> {
+        let parts: &[&str] = match self {
+            Self::Alphanumeric => &[UPPER, LOWER, DIGITS],
+            Self::Hex => &["0123456789abcdef"],
+            Self::Base64Url => &[UPPER, LOWER, DIGITS, "-_"],
+            Self::Ascii => &[UPPER, LOWER, DIGITS, SYMBOLS],
+        };
+        parts.iter().flat_map(|p| p.chars()).collect()
+    }
+}
+
+/// Checks that `length` is within `MIN_LENGTH..=MAX_LENGTH`.
+fn validate_length(length: usize) -> Result<(), String> {
+    if !(MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+        return Err(format!(
+            "Length must be between {} and {} characters.",
+            MIN_LENGTH, MAX_LENGTH
+        ));
+    }
+    Ok(())
+}
+
+/// Generates a random value of `length` characters from `charset`.
+pub fn random_value(length: usize, charset: Charset) -> Result<String, String> {
+    validate_length(length)?;
+    let alphabet = charset.alphabet();
+    Ok((0..length)
+        .map(|_| alphabet[OsRng.gen_range(0..alphabet.len())])
+        .collect())
+}
+
+/// Generates a password of `length` characters containing lowercase and
+/// uppercase letters, plus digits and symbols when requested, with at least
+/// one character from every included class.
+pub fn generate_password(
+    length: usize,
+    include_symbols: bool,
+    include_digits: bool,
+    exclude_ambiguous: bool,
+) -> Result<String, String> {
+    validate_length(length)?;
+
+    let mut classes = vec![LOWER, UPPER];
+    if include_digits {
+        classes.push(DIGITS);
+    }
+    if include_symbols {
+        classes.push(SYMBOLS);
+    }
+    let classes: Vec<Vec<char>> = classes
+        .into_iter()
+        .map(|class| {
+            class
+                .chars()
+                .filter(|c| !(exclude_ambiguous && AMBIGUOUS.contains(*c)))
+                .collect()
+        })
+        .collect();
+    let all: Vec<char> = classes.iter().flatten().copied().collect();
+
+    // One character from each required class, the rest from the union.
+    let mut password: Vec<char> = classes
+        .iter()
+        .map(|class| class[OsRng.gen_range(0..class.len())])
+        .collect();
+    while password.len() < length {
+        password.push(all[OsRng.gen_range(0..all.len())]);
+    }
+    password.shuffle(&mut OsRng);
+
+    Ok(password.into_iter().collect())
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_charsets() {
+        assert_eq!(Charset::parse("hex").unwrap(), Charset::Hex);
+        assert_eq!(
+            Charset::parse("alphanumeric").unwrap(),
+            Charset::Alphanumeric
+        );
+        assert!(Charset::parse("emoji").is_err());
+    }
+
+    #[test]
+    fn random_value_has_requested_length_and_charset() {
+        let value = random_value(64, Charset::Hex).unwrap();
+        assert_eq!(value.len(), 64);
+        assert!(value.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn random_value_rejects_out_of_range_length() {
+        assert!(random_value(MIN_LENGTH - 1, Charset::Alphanumeric).is_err());
+        assert!(random_value(MAX_LENGTH + 1, Charset::Alphanumeric).is_err());
+    }
+
+    #[test]
+    fn password_contains_every_required_class() {
+        for _ in 0..50 {
+            let password = generate_password(MIN_LENGTH, true, true, false).unwrap();
+            assert_eq!(password.chars().count(), MIN_LENGTH);
+            assert!(password.chars().any(|c| c.is_ascii_lowercase()));
+            assert!(password.chars().any(|c| c.is_ascii_uppercase()));
+            assert!(password.chars().any(|c| c.is_ascii_digit()));
+            assert!(password.chars().any(|c| SYMBOLS.contains(c)));
+        }
+    }
+
+    #[test]
+    fn password_respects_excluded_classes() {
+        let password = generate_password(64, false, false, false).unwrap();
+        assert!(password.chars().all(|c| c.is_ascii_alphabetic()));
+    }
+
+    #[test]
+    fn password_excludes_ambiguous_characters() {
+        let password = generate_password(MAX_LENGTH, true, true, true).unwrap();
+        assert!(!password.chars().any(|c| AMBIGUOUS.contains(c)));
+    }
+
+    #[test]
+    fn random_values_differ() {
+        let a = random_value(32, Charset::Base64Url).unwrap();
+        let b = random_value(32, Charset::Base64Url).unwrap();
+        assert_ne!(a, b);
+    }
+}
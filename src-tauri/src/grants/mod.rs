@@ -0,0 +1,126 @@
+//! Short-lived, single-use grants bound to one vault item.
+//!
+//! Used for secret reveals (`get_secret_value` → `reveal_secret_value`) and
+//! purge confirmations (`request_purge` → `purge_secret`). Reveals hand out
+//! a masked preview (`mask_value`) next to the token.
+//!
+//! Security guarantees:
+//! - Grant tokens are random and bound to one vault URI and item name.
+//! - A token is valid only until its TTL passes and is consumed on first use.
+//! - Tokens are held in memory only and never audited.
+//! - Tokens are matched in constant time (see `crate::ct`).
+
+use crate::ct::constant_time_eq;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Values shorter than this are fully masked in previews.
+const MIN_PREVIEW_LEN: usize = 8;
+
+/// Item a grant token was issued for.
+struct Grant {
+    vault_uri: String,
+    name: String,
+    expires_at: Instant,
+}
+
+/// Outstanding grant tokens with a fixed time-to-live.
+pub struct ItemGrants {
+    ttl: Duration,
+    grants: Mutex<HashMap<String, Grant>>,
+}
+
+impl ItemGrants {
+    /// Creates an empty store whose tokens expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            grants: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Issues a token for one use on `name` in `vault_uri`.
+    /// Expired tokens are pruned on the way.
+    pub fn issue(&self, vault_uri: &str, name: &str) -> String {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        if let Ok(mut grants) = self.grants.lock() {
+            let now = Instant::now();
+            grants.retain(|_, grant| grant.expires_at > now);
+            grants.insert(
+                token.clone(),
+                Grant {
+                    vault_uri: vault_uri.to_string(),
+                    name: name.to_string(),
+                    expires_at: now + self.ttl,
+                },
+            );
+        }
+        token
+    }
+
+    /// Consumes `token`, returning `true` only if it was issued for this
+    /// vault and item and has not expired.
+    pub fn redeem(&self, token: &str, vault_uri: &str, name: &str) -> bool {
+        // Scanned rather than hashed so matching never short-circuits on
+        // the contents of the presented token.
+        let grant = self.grants.lock().ok().and_then(|mut grants| {
+            let key = grants
+                .keys()
+                .find(|key| constant_time_eq(key.as_bytes(), token.as_bytes()))
+                .cloned()?;
+            grants.remove(&key)
+        });
+        matches!(
+            grant,
+            Some(grant)
+                if grant.expires_at > Instant::now()
+                    && grant.vault_uri == vault_uri
+                    && grant.name == name
+        )
+    }
+}
+
+/// Masks `value` as `ab…yz`, or with `•` only when it is short.
+pub fn mask_value(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < MIN_PREVIEW_LEN {
+        return "•".repeat(chars.len());
+    }
+    let head: String = chars[..2].iter().collect();
+    let tail: String = chars[chars.len() - 2..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grant_is_single_use_and_bound_to_item() {
+        let grants = ItemGrants::new(Duration::from_secs(60));
+        let token = grants.issue("https://kv.vault.azure.net", "db");
+        assert!(!grants.redeem(&token, "https://kv.vault.azure.net", "other"));
+
+        let token = grants.issue("https://kv.vault.azure.net", "db");
+        assert!(grants.redeem(&token, "https://kv.vault.azure.net", "db"));
+        assert!(!grants.redeem(&token, "https://kv.vault.azure.net", "db"));
+    }
+
+    #[test]
+    fn expired_grant_is_rejected() {
+        let grants = ItemGrants::new(Duration::ZERO);
+        let token = grants.issue("https://kv.vault.azure.net", "db");
+        assert!(!grants.redeem(&token, "https://kv.vault.azure.net", "db"));
+    }
+
+    // ── Masked previews ──
+
+    #[test]
+    fn masks_all_but_edges() {
+        assert_eq!(mask_value("abcdefghij"), "ab…ij");
+        assert_eq!(mask_value("short"), "•••••");
+    }
+}
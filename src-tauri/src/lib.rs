@@ -7,13 +7,22 @@
 mod audit;
 mod auth;
 mod azure;
+mod backup;
+mod cache;
+mod cloud;
 mod commands;
+mod correlation;
+mod ct;
+mod favorites;
+mod generate;
+mod grants;
 mod models;
 
 use commands::AppState;
+use std::sync::atomic::AtomicBool;
 use tauri::{
     menu::{AboutMetadataBuilder, MenuBuilder, SubmenuBuilder},
-    Manager, Runtime,
+    Emitter, Manager, Runtime,
 };
 
 const APP_AUTHORS: &str = env!("CARGO_PKG_AUTHORS");
@@ -104,7 +113,7 @@ fn build_app_menu<R: Runtime>(app: &tauri::App<R>) -> tauri::Result<tauri::menu:
 /// Initialises and runs the Tauri application.
 ///
 /// Sets up plugins (store, logging), constructs the shared `AppState`
-/// (auth manager, Azure client, audit logger), and registers all
+/// (auth manager, Azure client, audit logger, metadata cache), and registers all
 /// IPC command handlers.
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -128,11 +137,47 @@ pub fn run() {
                 .app_data_dir()
                 .unwrap_or_else(|_| std::path::PathBuf::from("."));
 
+            // Resolve the target national cloud (AZVAULT_CLOUD)
+            let cloud = cloud::CloudConfig::from_env();
+            log::info!(
+                "Using {} cloud (authority {})",
+                cloud.cloud.cli_name(),
+                cloud.cloud.authority_host()
+            );
+
+            // Optional append-only JSONL audit mirror (AZVAULT_AUDIT_JSONL=1)
+            let mut audit = audit::AuditLogger::new(app_data_dir.clone());
+            if std::env::var("AZVAULT_AUDIT_JSONL").is_ok_and(|v| v == "1") {
+                let sink = audit.log_dir().join("audit.log.jsonl");
+                audit = audit.with_jsonl_sink(sink);
+            }
+
+            // Tell the UI whenever a request backs off before retrying
+            let handle = app.handle().clone();
+            let azure = azure::AzureClient::new(azure::AzureClientConfig::from_env(cloud.cloud))
+                .with_retry_hook(Box::new(move |notice| {
+                    if let Err(e) = handle.emit("request-retrying", notice) {
+                        log::debug!("Failed to emit request-retrying: {}", e);
+                    }
+                }));
+
+            // Restore the persisted metadata cache opt-in
+            let cache = cache::MetadataCache::new(app_data_dir.clone());
+            if commands::stored_metadata_cache_enabled(app.handle()) {
+                cache.set_enabled(true);
+            }
+
             // Build shared application state
             let state = AppState {
-                auth: auth::AuthManager::new(),
-                azure: azure::AzureClient::new(),
-                audit: audit::AuditLogger::new(app_data_dir),
+                auth: auth::AuthManager::new(cloud.clone()),
+                azure,
+                cache,
+                audit,
+                offline: AtomicBool::new(false),
+                vault_index: Default::default(),
+                favorites: favorites::FavoritesStore::new(app_data_dir.clone()),
+                reveal_grants: grants::ItemGrants::new(commands::REVEAL_GRANT_TTL),
+                purge_grants: grants::ItemGrants::new(commands::PURGE_GRANT_TTL),
             };
 
             app.manage(state);
@@ -144,26 +189,89 @@ pub fn run() {
             commands::auth_status,
             commands::auth_sign_out,
             commands::set_tenant,
+            commands::inspect_token,
+            commands::warm_tokens,
+            commands::whoami,
             // Resource discovery
             commands::list_tenants,
             commands::list_subscriptions,
             commands::list_keyvaults,
+            commands::list_keyvaults_grouped,
+            commands::list_deleted_vaults,
+            commands::recover_vault,
+            commands::search_items_by_tag,
+            commands::resolve_vault,
+            // Favorites
+            commands::list_favorite_vaults,
+            commands::add_favorite_vault,
+            commands::remove_favorite_vault,
+            commands::save_context,
+            commands::load_context,
+            commands::get_required_tag_keys,
+            commands::set_required_tag_keys,
+            commands::validate_item_tags,
             // Vault items
             commands::list_secrets,
             commands::list_keys,
             commands::list_certificates,
+            commands::list_all_items,
             commands::get_secret_metadata,
+            commands::secret_exists,
             commands::get_secret_value,
+            commands::reveal_secret_value,
+            commands::get_secret_value_by_id,
             commands::set_secret,
+            commands::ensure_secret,
+            commands::rotate_secret,
+            commands::generate_password,
             commands::delete_secret,
+            commands::get_deleted_secret,
             commands::recover_secret,
+            commands::request_purge,
             commands::purge_secret,
+            commands::set_secrets_tags,
+            commands::set_secrets_content_type,
+            commands::import_secrets,
+            commands::set_secrets_enabled,
+            commands::find_duplicate_secret_values,
+            commands::vault_value_size,
+            commands::export_secret_values,
+            commands::compare_vaults,
+            commands::vault_item_counts,
+            commands::expiring_certificates,
+            commands::not_yet_active_secrets,
+            commands::check_permission,
+            commands::list_deleted_secrets,
+            commands::list_deleted_keys,
+            commands::list_deleted_certificates,
+            commands::purge_all_deleted_secrets,
+            commands::recover_all_deleted_secrets,
+            commands::import_certificate,
+            commands::get_key,
+            commands::get_key_rotation_policy,
+            commands::set_key_rotation_policy,
+            commands::roundtrip_key_test,
+            commands::get_certificate_as_secret,
+            commands::reveal_certificate_as_secret,
+            // Offline
+            commands::set_offline_mode,
+            commands::set_metadata_cache,
             // Audit
             commands::get_audit_log,
+            commands::get_vault_audit,
             commands::read_audit_log,
             commands::write_audit_log,
             commands::export_audit_log,
+            commands::export_audit_log_to_path,
             commands::clear_audit_log,
+            commands::set_audit_level,
+            // Diagnostics
+            commands::get_diagnostics,
+            commands::last_rate_limit_status,
+            commands::capabilities,
+            commands::validate_azure_url,
+            commands::detect_cloud,
+            commands::export_config,
             // Export
             commands::export_items,
         ])
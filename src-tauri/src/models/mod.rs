@@ -3,8 +3,10 @@
 //! All types implement `Serialize`/`Deserialize` for Tauri IPC and
 //! use `camelCase` field naming to match the React frontend expectations.
 
+use crate::auth::CliError;
+use crate::cloud::Cloud;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ── Auth ──
 
@@ -16,6 +18,28 @@ pub struct AuthState {
     pub tenant_id: Option<String>,
 }
 
+/// Decoded (unverified) access token contents for RBAC debugging.
+/// The raw token and its signature are never included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInspection {
+    pub scope: String,
+    pub header: serde_json::Value,
+    pub claims: serde_json::Value,
+}
+
+/// Identity claims of the signed-in principal, for RBAC debugging.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Identity {
+    /// `oid` claim: the principal id used in role assignments.
+    pub object_id: Option<String>,
+    pub tenant_id: Option<String>,
+    pub preferred_username: Option<String>,
+    /// `appid` (v1) or `azp` (v2) claim: the client that requested the token.
+    pub app_id: Option<String>,
+}
+
 // ── Azure Resources ──
 
 /// Azure AD tenant descriptor.
@@ -47,6 +71,36 @@ pub struct KeyVaultInfo {
     pub vault_uri: String,
     pub tags: Option<HashMap<String, String>>,
     pub soft_delete_enabled: Option<bool>,
+    /// ARM `publicNetworkAccess` (`Enabled`/`Disabled`), if reported.
+    pub public_network_access: Option<String>,
+    /// `true` when `networkAcls.defaultAction` is `Deny` (only allow-listed
+    /// networks can reach the data plane).
+    pub network_default_deny: Option<bool>,
+}
+
+/// A soft-deleted vault from ARM `deletedVaults` (recoverable until
+/// `scheduled_purge_date`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedVaultInfo {
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    /// ARM id the vault had before deletion (recovery recreates it there).
+    pub vault_id: String,
+    pub resource_group: String,
+    pub deletion_date: Option<String>,
+    pub scheduled_purge_date: Option<String>,
+    pub purge_protection_enabled: Option<bool>,
+    pub tags: Option<HashMap<String, String>>,
+}
+
+/// Vaults sharing an Azure region.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRegionGroup {
+    pub location: String,
+    pub vaults: Vec<KeyVaultInfo>,
 }
 
 // ── Vault Items ──
@@ -57,11 +111,19 @@ pub struct KeyVaultInfo {
 pub struct SecretItem {
     pub id: String,
     pub name: String,
+    /// Version segment of `id`, present when the id is version-qualified.
+    pub version: Option<String>,
     pub enabled: bool,
     pub created: Option<String>,
     pub updated: Option<String>,
     pub expires: Option<String>,
     pub not_before: Option<String>,
+    /// Epoch seconds of `created`/`updated`/`expires`, for numeric sorting.
+    pub created_epoch: Option<i64>,
+    pub updated_epoch: Option<i64>,
+    pub expires_epoch: Option<i64>,
+    /// `attributes.recoveryLevel`, e.g. `Recoverable+Purgeable`.
+    pub recovery_level: Option<String>,
     pub content_type: Option<String>,
     pub tags: Option<HashMap<String, String>>,
     pub managed: Option<bool>,
@@ -76,6 +138,64 @@ pub struct SecretValue {
     pub name: String,
 }
 
+/// Exportable material of a certificate, read from its managed backing
+/// secret (PKCS#12 base64 or PEM, including the private key).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateSecret {
+    pub id: String,
+    pub name: String,
+    /// `application/x-pkcs12` or `application/x-pem-file`.
+    pub content_type: Option<String>,
+    pub value: String,
+}
+
+/// Masked preview returned by `get_certificate_as_secret`; the PFX/PEM
+/// material requires `reveal_certificate_as_secret` with `reveal_token`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaskedCertificateSecret {
+    pub id: String,
+    pub name: String,
+    pub content_type: Option<String>,
+    pub preview: String,
+    pub length: usize,
+    pub reveal_token: String,
+}
+
+/// Masked preview returned by `get_secret_value`; the full value requires
+/// `reveal_secret_value` with `reveal_token`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaskedSecretValue {
+    pub id: String,
+    pub name: String,
+    /// First and last characters only (fully masked for short values).
+    pub preview: String,
+    /// Length of the value in characters.
+    pub length: usize,
+    pub reveal_token: String,
+}
+
+/// Soft-deleted secret metadata (recoverable until `scheduled_purge_date`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedSecretItem {
+    pub id: String,
+    pub name: String,
+    pub recovery_id: Option<String>,
+    pub deleted_date: Option<String>,
+    pub scheduled_purge_date: Option<String>,
+    pub recovery_level: Option<String>,
+    pub content_type: Option<String>,
+    pub tags: Option<HashMap<String, String>>,
+    /// Attributes the secret had when it was deleted.
+    pub enabled: Option<bool>,
+    pub created: Option<String>,
+    pub updated: Option<String>,
+    pub expires: Option<String>,
+}
+
 /// Cryptographic key metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -87,12 +207,52 @@ pub struct KeyItem {
     pub updated: Option<String>,
     pub expires: Option<String>,
     pub not_before: Option<String>,
+    /// Epoch seconds of `created`/`updated`/`expires`, for numeric sorting.
+    pub created_epoch: Option<i64>,
+    pub updated_epoch: Option<i64>,
+    pub expires_epoch: Option<i64>,
+    /// `attributes.recoveryLevel`, e.g. `Recoverable+Purgeable`.
+    pub recovery_level: Option<String>,
     pub key_type: Option<String>,
     pub key_ops: Option<Vec<String>>,
     pub tags: Option<HashMap<String, String>>,
     pub managed: Option<bool>,
 }
 
+/// A key's metadata plus its public JWK material, from `get_key`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyDetails {
+    pub metadata: KeyItem,
+    pub public_key: PublicJwk,
+}
+
+/// Public JSON Web Key members (base64url). RSA keys carry `n`/`e`, EC keys
+/// `crv`/`x`/`y`. Private members are never included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicJwk {
+    pub kid: Option<String>,
+    pub kty: Option<String>,
+    pub n: Option<String>,
+    pub e: Option<String>,
+    pub crv: Option<String>,
+    pub x: Option<String>,
+    pub y: Option<String>,
+}
+
+/// Soft-deleted key metadata (recoverable until `scheduled_purge_date`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedKeyItem {
+    pub id: String,
+    pub name: String,
+    pub recovery_id: Option<String>,
+    pub deleted_date: Option<String>,
+    pub scheduled_purge_date: Option<String>,
+    pub recovery_level: Option<String>,
+    pub tags: Option<HashMap<String, String>>,
+}
+
 /// X.509 certificate metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -104,13 +264,69 @@ pub struct CertificateItem {
     pub updated: Option<String>,
     pub expires: Option<String>,
     pub not_before: Option<String>,
+    /// Epoch seconds of `created`/`updated`/`expires`, for numeric sorting.
+    pub created_epoch: Option<i64>,
+    pub updated_epoch: Option<i64>,
+    pub expires_epoch: Option<i64>,
+    /// `attributes.recoveryLevel`, e.g. `Recoverable+Purgeable`.
+    pub recovery_level: Option<String>,
     pub subject: Option<String>,
     pub thumbprint: Option<String>,
     pub tags: Option<HashMap<String, String>>,
 }
 
+/// Result of a paginated listing. If a later page fails, the items fetched
+/// so far are returned with `truncated: true` and the failure in `error`.
+/// `stale` marks a listing served from the offline metadata cache;
+/// `from_cache` marks one whose every page was revalidated via `304`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResult<T> {
+    pub items: Vec<T>,
+    pub truncated: bool,
+    pub error: Option<String>,
+    pub stale: bool,
+    pub from_cache: bool,
+}
+
+/// Every item type of a vault from `list_all_items`. A type that failed to
+/// list is `None` with its error alongside.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultItems {
+    pub secrets: Option<ListResult<SecretItem>>,
+    pub secrets_error: Option<String>,
+    pub keys: Option<ListResult<KeyItem>>,
+    pub keys_error: Option<String>,
+    pub certificates: Option<ListResult<CertificateItem>>,
+    pub certificates_error: Option<String>,
+}
+
+/// Soft-deleted certificate metadata (recoverable until `scheduled_purge_date`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedCertificateItem {
+    pub id: String,
+    pub name: String,
+    pub recovery_id: Option<String>,
+    pub deleted_date: Option<String>,
+    pub scheduled_purge_date: Option<String>,
+    pub recovery_level: Option<String>,
+    pub thumbprint: Option<String>,
+    pub tags: Option<HashMap<String, String>>,
+}
+
 // ── Create/Update ──
 
+/// Whether a secret name is taken, as reported by `secret_exists`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretExistence {
+    pub exists: bool,
+    /// Enabled state of the current version; `None` when it does not exist.
+    pub enabled: Option<bool>,
+}
+
 /// Payload for creating or versioning a secret.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -124,12 +340,289 @@ pub struct CreateSecretRequest {
     pub not_before: Option<String>,
 }
 
+/// Item counts of one vault for the dashboard. Counts are `None` when
+/// `error` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultItemCounts {
+    pub vault_uri: String,
+    pub secrets: Option<usize>,
+    pub keys: Option<usize>,
+    pub certificates: Option<usize>,
+    pub error: Option<String>,
+}
+
+/// Certificate flagged by `expiring_certificates`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringCertificate {
+    pub id: String,
+    pub name: String,
+    pub subject: Option<String>,
+    pub thumbprint: Option<String>,
+    pub expires: Option<String>,
+    /// Whole days until expiry (negative once expired).
+    pub days_remaining: i64,
+    pub expired: bool,
+}
+
+/// Outcome of `ensure_secret`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnsureSecretResult {
+    /// `true` when a new version was written.
+    pub new_version: bool,
+    pub item: SecretItem,
+}
+
+// ── Cloud Detection ──
+
+/// Result of probing every cloud's authority for a tenant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudDetection {
+    pub tenant_id: String,
+    /// The cloud the tenant belongs to, if any authority recognised it.
+    pub detected: Option<Cloud>,
+    pub probes: Vec<CloudProbe>,
+}
+
+/// One authority's answer for a tenant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudProbe {
+    pub cloud: Cloud,
+    pub recognized: bool,
+    /// `cloud_instance_name` reported by the authority, e.g. `microsoftonline.us`.
+    pub cloud_instance: Option<String>,
+    pub error: Option<String>,
+}
+
+// ── URL Validation ──
+
+/// Outcome of checking a URL against the Azure allowlist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlValidation {
+    pub allowed: bool,
+    /// Why the URL was rejected (`None` when allowed).
+    pub reason: Option<String>,
+}
+
+// ── Value Backup ──
+
+/// Result of `export_secret_values`: where the encrypted backup was written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretValueExport {
+    pub path: String,
+    pub count: usize,
+}
+
+/// Result of `export_audit_log_to_path`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditExportFile {
+    pub path: String,
+    pub bytes: usize,
+}
+
+// ── Duplicate Values ──
+
+/// Secrets sharing a value, as found by `find_duplicate_secret_values`.
+/// Contains names only; values and hashes are never returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateValueReport {
+    /// Sorted groups of two or more secret names with identical values.
+    pub groups: Vec<Vec<String>>,
+    /// Number of values read and compared.
+    pub scanned: usize,
+    /// `true` when the secret listing was incomplete.
+    pub truncated: bool,
+    /// Per-secret read failures (`name: error`).
+    pub errors: Vec<String>,
+}
+
+/// Aggregate secret value sizes, as measured by `vault_value_size`.
+/// Contains sizes and names only; values are never returned.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueSizeReport {
+    /// Number of values read and measured.
+    pub measured: usize,
+    /// Sum of value lengths in bytes (UTF-8).
+    pub total_bytes: u64,
+    /// `total_bytes / measured`, rounded down; `0` when nothing was measured.
+    pub average_bytes: u64,
+    pub max_bytes: u64,
+    /// Name of the secret with the largest value.
+    pub largest: Option<String>,
+    /// `true` when the secret listing was incomplete.
+    pub truncated: bool,
+    /// Per-secret read failures (`name: error`).
+    pub errors: Vec<String>,
+}
+
+// ── UI Context ──
+
+/// Last-used tenant/subscription/vault, restored on launch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiContext {
+    pub tenant_id: Option<String>,
+    pub subscription_id: Option<String>,
+    pub vault_uri: Option<String>,
+}
+
+// ── Tag Policy ──
+
+/// Outcome of checking tags against required tag keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagValidation {
+    pub valid: bool,
+    /// Required keys that are absent or have a blank value, in policy order.
+    pub missing: Vec<String>,
+}
+
+// ── Tag Search ──
+
+/// Item found by `search_items_by_tag` (metadata only).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagMatch {
+    pub vault_name: String,
+    pub vault_uri: String,
+    /// `secret`, `key`, or `certificate`.
+    pub item_type: String,
+    pub name: String,
+    pub id: String,
+}
+
+/// Result of a cross-vault tag search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagSearchResult {
+    pub matches: Vec<TagMatch>,
+    /// `true` when matches were capped or some listing was incomplete.
+    pub truncated: bool,
+    /// Per-vault listing failures (`vault: error`).
+    pub errors: Vec<String>,
+}
+
+// ── Key Rotation ──
+
+/// Automated rotation policy of a key (`/keys/{name}/rotationpolicy`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRotationPolicy {
+    pub id: Option<String>,
+    pub lifetime_actions: Vec<LifetimeAction>,
+    /// ISO 8601 duration applied as the expiry of newly rotated versions.
+    pub expiry_time: Option<String>,
+}
+
+/// A rotation policy action and its trigger (exactly one trigger is set).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifetimeAction {
+    /// `Rotate` or `Notify`.
+    pub action: String,
+    /// ISO 8601 duration after key creation, e.g. `P90D`.
+    pub time_after_create: Option<String>,
+    /// ISO 8601 duration before key expiry, e.g. `P30D`.
+    pub time_before_expiry: Option<String>,
+}
+
+// ── Bulk Operations ──
+
+/// Outcome of a single item within a bulk operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    pub name: String,
+    pub success: bool,
+    /// `true` when the item was intentionally not processed (see `error`).
+    pub skipped: bool,
+    pub error: Option<String>,
+}
+
+/// Payload of the `bulk-progress` event, emitted as each item finishes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkProgressEvent {
+    pub operation: String,
+    pub completed: usize,
+    pub total: usize,
+    pub current_item: String,
+}
+
+/// Payload of the terminal `bulk-complete` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkCompleteEvent {
+    pub operation: String,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+/// Payload of the `request-retrying` event, emitted when a request is about
+/// to sleep before retrying (e.g. Azure is rate-limiting).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryNotice {
+    /// Number of the retry about to be made (1 for the first retry).
+    pub attempt: usize,
+    pub delay_secs: u64,
+    /// e.g. `rate limited (429)`, `server error (503)`, `network error`.
+    pub reason: String,
+}
+
+// ── Access Review ──
+
+/// Outcome of a permission probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionAccess {
+    Allowed,
+    Denied,
+    Unknown,
+}
+
+/// Whether the current identity can perform `action` on a vault.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionCheck {
+    pub action: String,
+    pub access: PermissionAccess,
+    /// The probe's error message when access is not `Allowed`.
+    pub detail: Option<String>,
+}
+
+// ── Vault Comparison ──
+
+/// Secret names present in one or both of two compared vaults (sorted).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultComparison {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub in_both: Vec<String>,
+}
+
 // ── Audit ──
 
 /// A single audit log entry persisted to disk.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuditEntry {
+    /// Monotonic sequence number; orders entries regardless of clock jumps.
+    #[serde(default)]
+    pub seq: u64,
+    /// RFC 3339 UTC time with millisecond precision.
     pub timestamp: String,
     pub vault_name: String,
     pub action: String,
@@ -137,6 +630,148 @@ pub struct AuditEntry {
     pub item_name: String,
     pub result: String,
     pub details: Option<String>,
+    /// `x-ms-client-request-id` sent for the operation, for finding it in
+    /// Azure's server-side logs. Absent for local-only actions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// Location and permission state of the persisted audit file, as detected
+/// at startup.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFileStatus {
+    /// Directory the audit log is actually written to.
+    pub directory: String,
+    /// Set when the app data directory was not writable, so the audit log
+    /// moved to a temp directory (or cannot be persisted at all).
+    pub directory_warning: Option<String>,
+    pub exists: bool,
+    /// Octal permission bits seen at startup (Unix only), e.g. `"644"`.
+    pub mode: Option<String>,
+    /// `true` when overly broad permissions were tightened to `0o600`.
+    pub repaired: bool,
+    pub warning: Option<String>,
+}
+
+// ── Diagnostics ──
+
+/// Throttling headers from the most recent response of one host (see
+/// `last_rate_limit_status`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitStatus {
+    pub host: String,
+    /// RFC 3339 UTC time the response was received.
+    pub observed_at: String,
+    pub status: u16,
+    /// `x-ms-ratelimit-*`, `x-ms-keyvault-*`, and `Retry-After` headers,
+    /// keyed by lowercase name.
+    pub headers: BTreeMap<String, String>,
+}
+
+/// Non-sensitive runtime state for troubleshooting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostics {
+    pub version: String,
+    pub cloud: String,
+    pub offline_mode: bool,
+    pub metadata_cache_enabled: bool,
+    pub audit_file: AuditFileStatus,
+}
+
+/// Features supported by this backend build, for UI feature detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    pub export_formats: Vec<String>,
+    /// Item types with list commands (`secret`, `key`, `certificate`).
+    pub item_types: Vec<String>,
+    /// Mutating vault commands available in this build.
+    pub mutations: Vec<String>,
+    /// Offline mode serves listings from cache; mutations still go online.
+    pub offline_mode: bool,
+}
+
+/// Effective, non-secret configuration the app is running with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigExport {
+    /// Azure CLI name of the selected cloud.
+    pub cloud: String,
+    pub tenant_id: String,
+    pub authority_host: String,
+    pub arm_host: String,
+    /// Tokens are always obtained through the Azure CLI (no app client id).
+    pub auth_provider: String,
+    /// Exact hosts and suffixes (leading dot) of the outbound allowlist.
+    pub allowed_hosts: Vec<String>,
+    /// Names of `AZVAULT_EXTRA_HEADERS` headers (values are never exported).
+    pub extra_header_names: Vec<String>,
+    pub connect_timeout_secs: u64,
+    pub request_timeout_secs: u64,
+    pub max_retries: usize,
+    /// Pages followed per listing before it is returned as truncated.
+    pub max_pages: usize,
+    /// Outbound requests allowed in flight at once across all commands.
+    pub max_concurrent_requests: usize,
+    /// Key Vault data-plane `api-version` (default or override).
+    pub keyvault_api_version: String,
+    /// `Microsoft.KeyVault` ARM `api-version` (default or override).
+    pub keyvault_mgmt_api_version: String,
+}
+
+// ── Errors ──
+
+/// Error returned by vault commands. `status` is the HTTP status when the
+/// failure came from an Azure response (`None` for validation/network errors).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandError {
+    pub status: Option<u16>,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+/// Parses the `"[status] code: message | Hint: hint"` format produced by
+/// the Azure client; other strings become a status-less message.
+impl From<String> for CommandError {
+    fn from(error: String) -> Self {
+        let (status, rest) = match error
+            .strip_prefix('[')
+            .and_then(|s| s.split_once("] "))
+            .and_then(|(code, rest)| code.parse::<u16>().ok().map(|c| (c, rest)))
+        {
+            Some((code, rest)) => (Some(code), rest),
+            None => (None, error.as_str()),
+        };
+        let (message, hint) = match rest.split_once(" | Hint: ") {
+            Some((message, hint)) => (message, Some(hint.to_string())),
+            None => (rest, None),
+        };
+        Self {
+            status,
+            message: message.to_string(),
+            hint,
+        }
+    }
+}
+
+impl From<CliError> for CommandError {
+    fn from(error: CliError) -> Self {
+        Self {
+            status: None,
+            message: error.message(),
+            hint: error.hint(),
+        }
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(error: &str) -> Self {
+        Self::from(error.to_string())
+    }
 }
 
 // ── Tests ──
@@ -168,11 +803,16 @@ mod tests {
         let secret = SecretItem {
             id: "id".to_string(),
             name: "db-conn".to_string(),
+            version: None,
             enabled: true,
             created: None,
             updated: None,
             expires: None,
             not_before: None,
+            created_epoch: None,
+            updated_epoch: None,
+            expires_epoch: None,
+            recovery_level: None,
             content_type: Some("text/plain".to_string()),
             tags: None,
             managed: None,
@@ -195,11 +835,16 @@ mod tests {
         let original = SecretItem {
             id: "https://vault.azure.net/secrets/test".to_string(),
             name: "test".to_string(),
+            version: None,
             enabled: false,
             created: Some("2024-01-01T00:00:00Z".to_string()),
             updated: None,
             expires: Some("2025-12-31T23:59:59Z".to_string()),
             not_before: None,
+            created_epoch: None,
+            updated_epoch: None,
+            expires_epoch: None,
+            recovery_level: None,
             content_type: Some("application/json".to_string()),
             tags: Some(HashMap::from([("env".to_string(), "prod".to_string())])),
             managed: Some(true),
@@ -221,6 +866,10 @@ mod tests {
             updated: None,
             expires: None,
             not_before: None,
+            created_epoch: None,
+            updated_epoch: None,
+            expires_epoch: None,
+            recovery_level: None,
             key_type: Some("RSA".to_string()),
             key_ops: Some(vec!["sign".to_string(), "verify".to_string()]),
             tags: None,
@@ -252,17 +901,54 @@ mod tests {
     #[test]
     fn audit_entry_serialization() {
         let entry = AuditEntry {
-            timestamp: "2024-06-15T10:00:00Z".to_string(),
+            seq: 1,
+            timestamp: "2024-06-15T10:00:00.000Z".to_string(),
             vault_name: "my-vault".to_string(),
             action: "get_secret_value".to_string(),
             item_type: "secret".to_string(),
             item_name: "db-conn".to_string(),
             result: "success".to_string(),
             details: Some("[REDACTED]".to_string()),
+            request_id: Some("req-1".to_string()),
         };
         let json = serde_json::to_string(&entry).expect("serialize");
         assert!(json.contains("vaultName"));
         assert!(json.contains("itemType"));
         assert!(json.contains("[REDACTED]"));
+        assert!(json.contains("\"requestId\":\"req-1\""));
+
+        let local = AuditEntry {
+            request_id: None,
+            ..entry
+        };
+        let json = serde_json::to_string(&local).expect("serialize");
+        assert!(!json.contains("requestId"));
+    }
+
+    #[test]
+    fn command_error_parses_azure_error_format() {
+        let err = CommandError::from(
+            "[403] Forbidden: Access denied | Hint: Check your Azure RBAC role.".to_string(),
+        );
+        assert_eq!(err.status, Some(403));
+        assert_eq!(err.message, "Forbidden: Access denied");
+        assert_eq!(err.hint.as_deref(), Some("Check your Azure RBAC role."));
+    }
+
+    #[test]
+    fn command_error_without_status_keeps_message() {
+        let err = CommandError::from("Network error: connection refused");
+        assert_eq!(err.status, None);
+        assert_eq!(err.message, "Network error: connection refused");
+        assert!(err.hint.is_none());
+    }
+
+    #[test]
+    fn command_error_serializes_in_camel_case() {
+        let err = CommandError::from("[404] NotFound: gone".to_string());
+        let json = serde_json::to_value(&err).expect("should serialize");
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["message"], "NotFound: gone");
+        assert!(json["hint"].is_null());
     }
 }
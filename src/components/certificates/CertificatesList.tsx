@@ -9,6 +9,7 @@ import { EmptyState } from '../common/EmptyState';
 import { ErrorMessage } from '../common/ErrorMessage';
 import type { Column } from '../common/ItemTable';
 import { ItemTable, renderDate, renderEnabled } from '../common/ItemTable';
+import { ListStatusNotice } from '../common/ListStatusNotice';
 import { LoadingSkeleton } from '../common/LoadingSkeleton';
 import { SplitPane } from '../common/SplitPane';
 import { CertificateDetails } from './CertificateDetails';
@@ -84,7 +85,7 @@ export function CertificatesList() {
   });
 
   const filterText = localFilter || searchQuery;
-  const allCerts = certsQuery.data || [];
+  const allCerts = certsQuery.data?.items || [];
   const filteredCerts = allCerts.filter((c) =>
     c.name.toLowerCase().includes(filterText.toLowerCase()),
   );
@@ -186,6 +187,8 @@ export function CertificatesList() {
         />
       </div>
 
+      <ListStatusNotice result={certsQuery.data} />
+
       <div className={classes.tableWrap}>
         {certsQuery.isLoading ? (
           <LoadingSkeleton rows={8} columns={[20, 10, 20, 15, 15, 15]} />
@@ -0,0 +1,45 @@
+import { makeStyles, Text, tokens } from '@fluentui/react-components';
+import { Warning24Regular } from '@fluentui/react-icons';
+import type { ListResult } from '../../types';
+
+interface ListStatusNoticeProps {
+  result: ListResult<unknown> | undefined;
+}
+
+const useStyles = makeStyles({
+  root: {
+    display: 'flex',
+    alignItems: 'center',
+    gap: '6px',
+    padding: '4px 12px',
+    background: tokens.colorPaletteYellowBackground1,
+    borderBottom: `1px solid ${tokens.colorNeutralStroke2}`,
+  },
+  icon: {
+    fontSize: '13px',
+    flexShrink: 0,
+    color: tokens.colorPaletteYellowForeground1,
+  },
+  text: {
+    color: tokens.colorPaletteYellowForeground1,
+  },
+});
+
+/** Flags a listing that is incomplete or served from the offline cache. */
+export function ListStatusNotice({ result }: ListStatusNoticeProps) {
+  const classes = useStyles();
+  if (!result || (!result.truncated && !result.stale)) return null;
+
+  const message = result.truncated
+    ? `Showing a partial list of ${result.items.length} items: ${result.error ?? 'the listing stopped early.'}`
+    : 'Offline: showing the last cached listing, which may be out of date.';
+
+  return (
+    <div className={classes.root}>
+      <Warning24Regular className={classes.icon} />
+      <Text size={200} className={classes.text}>
+        {message}
+      </Text>
+    </div>
+  );
+}
@@ -9,6 +9,7 @@ import { EmptyState } from '../common/EmptyState';
 import { ErrorMessage } from '../common/ErrorMessage';
 import type { Column } from '../common/ItemTable';
 import { ItemTable, renderDate, renderEnabled } from '../common/ItemTable';
+import { ListStatusNotice } from '../common/ListStatusNotice';
 import { LoadingSkeleton } from '../common/LoadingSkeleton';
 import { SplitPane } from '../common/SplitPane';
 import { KeyDetails } from './KeyDetails';
@@ -153,7 +154,7 @@ export function KeysList() {
   });
 
   const filterText = localFilter || searchQuery;
-  const allKeys = keysQuery.data || [];
+  const allKeys = keysQuery.data?.items || [];
   const filteredKeys = allKeys.filter((k) =>
     k.name.toLowerCase().includes(filterText.toLowerCase()),
   );
@@ -181,6 +182,8 @@ export function KeysList() {
         />
       </div>
 
+      <ListStatusNotice result={keysQuery.data} />
+
       <div className={classes.tableWrap}>
         {keysQuery.isLoading ? (
           <LoadingSkeleton rows={8} columns={[25, 10, 10, 25, 15, 15]} />
@@ -209,9 +209,9 @@ export function Sidebar() {
   });
 
   const counts: Record<string, number | undefined> = {
-    secrets: secretsQuery.data?.length,
-    keys: keysQuery.data?.length,
-    certificates: certsQuery.data?.length,
+    secrets: secretsQuery.data?.items.length,
+    keys: keysQuery.data?.items.length,
+    certificates: certsQuery.data?.items.length,
   };
 
   const isPinned = pinnedVaults.some((v) => v.uri === selectedVaultUri);
@@ -173,8 +173,7 @@ export function AuditLog() {
   });
 
   const entries = useMemo(() => {
-    const all = [...(logQuery.data || [])].reverse();
-    return all.filter((e) => {
+    return (logQuery.data || []).filter((e) => {
       if (filterAction !== 'All' && !e.action.includes(filterAction)) return false;
       if (filterResult !== 'All' && e.result !== filterResult) return false;
       if (filterType !== 'All' && e.itemType !== filterType) return false;
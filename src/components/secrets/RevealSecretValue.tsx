@@ -23,9 +23,9 @@ import {
 } from '@fluentui/react-icons';
 import { useCallback, useEffect, useRef, useState } from 'react';
 import { useAutoHide } from '../../hooks/useAutoHide';
-import { getSecretValue } from '../../services/tauri';
+import { getSecretValue, revealSecretValue } from '../../services/tauri';
 import { useAppStore } from '../../stores/appStore';
-import type { SecretValue } from '../../types';
+import type { MaskedSecretValue, SecretValue } from '../../types';
 
 const useStyles = makeStyles({
   sectionTitle: {
@@ -113,6 +113,7 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
   const { requireReauthForReveal, autoHideSeconds, clipboardClearSeconds, disableClipboardCopy } =
     useAppStore();
 
+  const [masked, setMasked] = useState<MaskedSecretValue | null>(null);
   const [secretValue, setSecretValue] = useState<SecretValue | null>(null);
   const [fetching, setFetching] = useState(false);
   const [fetchError, setFetchError] = useState<string | null>(null);
@@ -124,7 +125,11 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
 
   const { isRevealed, secondsLeft, reveal, hide } = useAutoHide({
     timeoutSeconds: autoHideSeconds,
-    onHide: () => setSecretValue(null),
+    onHide: () => {
+      // The reveal token is single-use, so hiding drops the preview as well
+      setSecretValue(null);
+      setMasked(null);
+    },
   });
 
   const handleFetchValue = useCallback(async () => {
@@ -134,7 +139,7 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
     try {
       const val = await getSecretValue(vaultUri, secretName);
       if (requestId !== fetchRequestIdRef.current) return;
-      setSecretValue(val);
+      setMasked(val);
     } catch (e) {
       if (requestId !== fetchRequestIdRef.current) return;
       setFetchError(String(e));
@@ -148,6 +153,7 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
   useEffect(() => {
     // Switching selected secret should always clear prior sensitive UI state
     fetchRequestIdRef.current += 1;
+    setMasked(null);
     setSecretValue(null);
     setFetching(false);
     setFetchError(null);
@@ -167,9 +173,35 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
     await handleFetchValue();
   }, [handleFetchValue, requireReauthForReveal, reauthConfirmed]);
 
-  const handleCopy = useCallback(() => {
-    if (!secretValue?.value || disableClipboardCopy) return;
-    navigator.clipboard.writeText(secretValue.value);
+  // Redeems the reveal token the first time the full value is actually needed
+  const loadValue = useCallback(async (): Promise<SecretValue | null> => {
+    if (secretValue) return secretValue;
+    if (!masked) return null;
+    const requestId = fetchRequestIdRef.current;
+    setFetching(true);
+    setFetchError(null);
+    try {
+      const val = await revealSecretValue(vaultUri, secretName, masked.revealToken);
+      if (requestId !== fetchRequestIdRef.current) return null;
+      setSecretValue(val);
+      return val;
+    } catch (e) {
+      if (requestId !== fetchRequestIdRef.current) return null;
+      setMasked(null);
+      setFetchError(String(e));
+      return null;
+    } finally {
+      if (requestId === fetchRequestIdRef.current) {
+        setFetching(false);
+      }
+    }
+  }, [masked, secretValue, secretName, vaultUri]);
+
+  const handleCopy = useCallback(async () => {
+    if (!masked || disableClipboardCopy) return;
+    const val = await loadValue();
+    if (!val?.value) return;
+    navigator.clipboard.writeText(val.value);
     setCopied(true);
     setClipboardWarning(true);
     setTimeout(() => setCopied(false), 2000);
@@ -177,14 +209,18 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
       navigator.clipboard.writeText('').catch(() => {});
       setClipboardWarning(false);
     }, clipboardClearSeconds * 1000);
-  }, [secretValue, clipboardClearSeconds, disableClipboardCopy]);
+  }, [masked, loadValue, clipboardClearSeconds, disableClipboardCopy]);
 
-  const handleRevealToggle = useCallback(() => {
-    if (!isRevealed) reveal();
-    else hide();
-  }, [isRevealed, reveal, hide]);
+  const handleRevealToggle = useCallback(async () => {
+    if (isRevealed) {
+      hide();
+      return;
+    }
+    if (await loadValue()) reveal();
+  }, [isRevealed, loadValue, reveal, hide]);
 
   const clearValue = useCallback(() => {
+    setMasked(null);
     setSecretValue(null);
     hide();
     setFetchError(null);
@@ -199,11 +235,11 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
         Secret Value
       </Text>
 
-      {!secretValue ? (
+      {!masked ? (
         <div className={styles.box}>
           <Text block size={200} className={styles.hintText}>
-            Values are never loaded automatically. Fetching will retrieve the value from Azure Key
-            Vault and hold it in memory only.
+            Values are never loaded automatically. Fetching shows a masked preview; the full value
+            is only retrieved when you reveal or copy it, and is held in memory only.
           </Text>
           <Button
             appearance="primary"
@@ -219,8 +255,8 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
         <div className={styles.box}>
           <div className={styles.row}>
             <Input
-              type={isRevealed ? 'text' : 'password'}
-              value={secretValue.value}
+              type="text"
+              value={isRevealed && secretValue ? secretValue.value : masked.preview}
               readOnly
               className={styles.input}
             />
@@ -230,6 +266,7 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
                 appearance="subtle"
                 size="small"
                 onClick={handleRevealToggle}
+                disabled={fetching}
               />
             </Tooltip>
             {!disableClipboardCopy && (
@@ -239,6 +276,7 @@ export function RevealSecretValue({ secretName, vaultUri }: RevealSecretValuePro
                   appearance="subtle"
                   size="small"
                   onClick={handleCopy}
+                  disabled={fetching}
                 />
               </Tooltip>
             )}
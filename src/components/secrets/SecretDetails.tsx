@@ -17,7 +17,7 @@ import {
 } from '@fluentui/react-icons';
 import { format } from 'date-fns';
 import { useCallback, useState } from 'react';
-import { deleteSecret, purgeSecret, recoverSecret } from '../../services/tauri';
+import { deleteSecret, purgeSecret, recoverSecret, requestPurge } from '../../services/tauri';
 import type { SecretItem } from '../../types';
 import { DangerConfirmDialog } from '../common/DangerConfirmDialog';
 import { CreateSecretDialog } from './CreateSecretDialog';
@@ -159,7 +159,9 @@ export function SecretDetails({ item, vaultUri, onClose, onRefresh }: SecretDeta
     setActionLoading(true);
     setActionError(null);
     try {
-      await purgeSecret(vaultUri, item.name);
+      // The token is only issued after the user has confirmed in the dialog
+      const confirmationToken = await requestPurge(vaultUri, item.name);
+      await purgeSecret(vaultUri, item.name, confirmationToken);
       setShowPurgeDialog(false);
       onRefresh();
       onClose();
@@ -28,12 +28,13 @@ import { useQuery, useQueryClient } from '@tanstack/react-query';
 import { type ChangeEvent, useEffect, useMemo, useRef, useState } from 'react';
 import { deleteSecret, exportItems, listSecrets, setSecret } from '../../services/tauri';
 import { useAppStore } from '../../stores/appStore';
-import type { SecretItem } from '../../types';
+import type { ListResult, SecretItem } from '../../types';
 import { DangerConfirmDialog } from '../common/DangerConfirmDialog';
 import { EmptyState } from '../common/EmptyState';
 import { ErrorMessage } from '../common/ErrorMessage';
 import type { Column } from '../common/ItemTable';
 import { ItemTable, renderDate, renderEnabled, renderTags } from '../common/ItemTable';
+import { ListStatusNotice } from '../common/ListStatusNotice';
 import { LoadingSkeleton } from '../common/LoadingSkeleton';
 import { SplitPane } from '../common/SplitPane';
 import { CreateSecretDialog } from './CreateSecretDialog';
@@ -310,7 +311,7 @@ export function SecretsList() {
     enabled: !!selectedVaultUri,
   });
 
-  const allSecrets = useMemo(() => secretsQuery.data ?? [], [secretsQuery.data]);
+  const allSecrets = useMemo(() => secretsQuery.data?.items ?? [], [secretsQuery.data]);
   const filterText = localFilter || searchQuery;
   const filteredSecrets = allSecrets.filter((s) =>
     s.name.toLowerCase().includes(filterText.toLowerCase()),
@@ -554,8 +555,13 @@ export function SecretsList() {
       setSelectedIds((prev) => removeSucceededSelection(prev, succeededIds));
 
       if (selectedVaultUri && succeededIds.length > 0) {
-        queryClient.setQueryData<SecretItem[]>(['secrets', selectedVaultUri], (current) =>
-          filterOutDeletedSecrets(current, succeededIds),
+        queryClient.setQueryData<ListResult<SecretItem>>(
+          ['secrets', selectedVaultUri],
+          (current) =>
+            current && {
+              ...current,
+              items: filterOutDeletedSecrets(current.items, succeededIds),
+            },
         );
       }
 
@@ -717,6 +723,8 @@ export function SecretsList() {
         </div>
       )}
 
+      <ListStatusNotice result={secretsQuery.data} />
+
       {/* Table */}
       <div className={classes.tableWrap}>
         {secretsQuery.isLoading ? (
@@ -837,8 +845,13 @@ export function SecretsList() {
         onClose={() => setShowPrefixDeleteDialog(false)}
         onCompleted={(deletedIds) => {
           if (selectedVaultUri && deletedIds.length > 0) {
-            queryClient.setQueryData<SecretItem[]>(['secrets', selectedVaultUri], (current) =>
-              filterOutDeletedSecrets(current, deletedIds),
+            queryClient.setQueryData<ListResult<SecretItem>>(
+              ['secrets', selectedVaultUri],
+              (current) =>
+                current && {
+                  ...current,
+                  items: filterOutDeletedSecrets(current.items, deletedIds),
+                },
             );
           }
           secretsQuery.refetch();
@@ -16,6 +16,7 @@ function makeSecret(id: string, name: string): SecretItem {
   return {
     id,
     name,
+    version: null,
     enabled: true,
     created: null,
     updated: null,
@@ -6,6 +6,7 @@ function makeSecret(overrides?: Partial<SecretItem>): SecretItem {
   return {
     id: 'id-1',
     name: 'secret-a',
+    version: null,
     enabled: true,
     created: '2025-01-01T00:00:00Z',
     updated: '2025-01-02T00:00:00Z',
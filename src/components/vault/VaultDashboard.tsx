@@ -176,21 +176,21 @@ export function VaultDashboard() {
         <CountCard
           icon={<Key24Regular />}
           label="Secrets"
-          count={secretsQuery.data?.length}
+          count={secretsQuery.data?.items.length}
           loading={secretsQuery.isLoading}
           onClick={() => setActiveTab('secrets')}
         />
         <CountCard
           icon={<LockClosed24Regular />}
           label="Keys"
-          count={keysQuery.data?.length}
+          count={keysQuery.data?.items.length}
           loading={keysQuery.isLoading}
           onClick={() => setActiveTab('keys')}
         />
         <CountCard
           icon={<Certificate24Regular />}
           label="Certificates"
-          count={certsQuery.data?.length}
+          count={certsQuery.data?.items.length}
           loading={certsQuery.isLoading}
           onClick={() => setActiveTab('certificates')}
         />
@@ -259,6 +259,17 @@ export function VaultDashboard() {
             </Text>
           </div>
         )}
+
+        {(currentVault?.publicNetworkAccess === 'Disabled' ||
+          currentVault?.networkDefaultDeny) && (
+          <div className={classes.softDeleteWarning}>
+            <Text size={200} className={classes.softDeleteWarningText}>
+              {currentVault?.publicNetworkAccess === 'Disabled'
+                ? 'Public network access is disabled. Only private endpoints can reach this vault.'
+                : 'Network rules deny by default. Only allow-listed networks can reach this vault.'}
+            </Text>
+          </div>
+        )}
       </Card>
 
       {/* Quick actions */}
@@ -308,8 +319,7 @@ export function VaultDashboard() {
           </Text>
         ) : (
           <div className={classes.activityList}>
-            {[...(auditQuery.data || [])]
-              .reverse()
+            {(auditQuery.data || [])
               .slice(0, 5)
               .map((entry, i) => (
                 <div key={i} className={classes.activityRow}>
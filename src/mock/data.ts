@@ -5,6 +5,8 @@ import type {
   CreateSecretRequest,
   KeyItem,
   KeyVaultInfo,
+  ListResult,
+  MaskedSecretValue,
   SecretItem,
   SecretValue,
   Subscription,
@@ -61,6 +63,8 @@ export function mockKeyvaults(): KeyVaultInfo[] {
       vaultUri: 'https://kv-prod-app.vault.azure.net',
       tags: { environment: 'production', team: 'platform' },
       softDeleteEnabled: true,
+      publicNetworkAccess: 'Enabled',
+      networkDefaultDeny: false,
     },
     {
       id: '/subscriptions/sub-001/resourceGroups/rg-prod/providers/Microsoft.KeyVault/vaults/kv-prod-data',
@@ -70,6 +74,8 @@ export function mockKeyvaults(): KeyVaultInfo[] {
       vaultUri: 'https://kv-prod-data.vault.azure.net',
       tags: { environment: 'production', team: 'data' },
       softDeleteEnabled: true,
+      publicNetworkAccess: 'Enabled',
+      networkDefaultDeny: false,
     },
     {
       id: '/subscriptions/sub-001/resourceGroups/rg-dev/providers/Microsoft.KeyVault/vaults/kv-dev-app',
@@ -79,6 +85,8 @@ export function mockKeyvaults(): KeyVaultInfo[] {
       vaultUri: 'https://kv-dev-app.vault.azure.net',
       tags: { environment: 'development' },
       softDeleteEnabled: true,
+      publicNetworkAccess: 'Enabled',
+      networkDefaultDeny: false,
     },
     {
       id: '/subscriptions/sub-001/resourceGroups/rg-shared/providers/Microsoft.KeyVault/vaults/kv-shared-certs',
@@ -88,6 +96,8 @@ export function mockKeyvaults(): KeyVaultInfo[] {
       vaultUri: 'https://kv-shared-certs.vault.azure.net',
       tags: { environment: 'shared', purpose: 'certificates' },
       softDeleteEnabled: true,
+      publicNetworkAccess: 'Enabled',
+      networkDefaultDeny: false,
     },
   ];
 }
@@ -102,6 +112,7 @@ export function mockSecrets(): SecretItem[] {
     {
       id: 'https://kv-prod-app.vault.azure.net/secrets/database-connection-string',
       name: 'database-connection-string',
+      version: null,
       enabled: true,
       created: lastWeek,
       updated: yesterday,
@@ -114,6 +125,7 @@ export function mockSecrets(): SecretItem[] {
     {
       id: 'https://kv-prod-app.vault.azure.net/secrets/api-key-external',
       name: 'api-key-external',
+      version: null,
       enabled: true,
       created: lastWeek,
       updated: lastWeek,
@@ -126,6 +138,7 @@ export function mockSecrets(): SecretItem[] {
     {
       id: 'https://kv-prod-app.vault.azure.net/secrets/storage-account-key',
       name: 'storage-account-key',
+      version: null,
       enabled: true,
       created: lastWeek,
       updated: now,
@@ -138,6 +151,7 @@ export function mockSecrets(): SecretItem[] {
     {
       id: 'https://kv-prod-app.vault.azure.net/secrets/redis-password',
       name: 'redis-password',
+      version: null,
       enabled: true,
       created: lastWeek,
       updated: lastWeek,
@@ -150,6 +164,7 @@ export function mockSecrets(): SecretItem[] {
     {
       id: 'https://kv-prod-app.vault.azure.net/secrets/jwt-signing-key',
       name: 'jwt-signing-key',
+      version: null,
       enabled: true,
       created: lastWeek,
       updated: yesterday,
@@ -162,6 +177,7 @@ export function mockSecrets(): SecretItem[] {
     {
       id: 'https://kv-prod-app.vault.azure.net/secrets/deprecated-key',
       name: 'deprecated-key',
+      version: null,
       enabled: false,
       created: lastWeek,
       updated: lastWeek,
@@ -174,6 +190,10 @@ export function mockSecrets(): SecretItem[] {
   ];
 }
 
+export function mockListResult<T>(items: T[]): ListResult<T> {
+  return { items, truncated: false, error: null, stale: false, fromCache: false };
+}
+
 export function mockSecretValue(name: string): SecretValue {
   return {
     value: `mock-secret-value-for-${name}-${Math.random().toString(36).slice(2, 10)}`,
@@ -182,10 +202,21 @@ export function mockSecretValue(name: string): SecretValue {
   };
 }
 
+export function mockMaskedSecretValue(name: string): MaskedSecretValue {
+  return {
+    id: `https://kv-prod-app.vault.azure.net/secrets/${name}/version1`,
+    name,
+    preview: 'mo…01',
+    length: 32,
+    revealToken: `mock-reveal-token-${name}`,
+  };
+}
+
 export function mockSetSecret(req: CreateSecretRequest): SecretItem {
   return {
-    id: `https://kv-prod-app.vault.azure.net/secrets/${req.name}`,
+    id: `https://kv-prod-app.vault.azure.net/secrets/${req.name}/version1`,
     name: req.name,
+    version: 'version1',
     enabled: req.enabled ?? true,
     created: now,
     updated: now,
@@ -273,6 +304,7 @@ export function mockCertificates(): CertificateItem[] {
 export function mockAuditLog(): AuditEntry[] {
   return [
     {
+      seq: 4,
       timestamp: now,
       vaultName: 'kv-prod-app',
       action: 'list_secrets',
@@ -282,6 +314,7 @@ export function mockAuditLog(): AuditEntry[] {
       details: null,
     },
     {
+      seq: 3,
       timestamp: yesterday,
       vaultName: 'kv-prod-app',
       action: 'get_secret_value',
@@ -291,6 +324,7 @@ export function mockAuditLog(): AuditEntry[] {
       details: '[REDACTED]',
     },
     {
+      seq: 2,
       timestamp: yesterday,
       vaultName: 'kv-prod-app',
       action: 'set_secret',
@@ -300,6 +334,7 @@ export function mockAuditLog(): AuditEntry[] {
       details: '[REDACTED]',
     },
     {
+      seq: 1,
       timestamp: lastWeek,
       vaultName: 'system',
       action: 'sign_in',
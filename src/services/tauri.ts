@@ -4,9 +4,12 @@ import type {
   AuditEntry,
   AuthState,
   CertificateItem,
+  CommandError,
   CreateSecretRequest,
   KeyItem,
   KeyVaultInfo,
+  ListResult,
+  MaskedSecretValue,
   SecretItem,
   SecretValue,
   Subscription,
@@ -17,6 +20,36 @@ function isMock(): boolean {
   return useMockStore.getState().mockMode;
 }
 
+/** Vault command failure; `String(error)` keeps the `[status] message` form. */
+export class VaultCommandError extends Error {
+  readonly status: number | null;
+  readonly hint: string | null;
+
+  constructor({ status, message, hint }: CommandError) {
+    const text = status === null ? message : `[${status}] ${message}`;
+    super(hint ? `${text} | Hint: ${hint}` : text);
+    this.name = 'VaultCommandError';
+    this.status = status;
+    this.hint = hint;
+  }
+
+  override toString(): string {
+    return this.message;
+  }
+}
+
+function isCommandError(error: unknown): error is CommandError {
+  return typeof error === 'object' && error !== null && 'message' in error && 'status' in error;
+}
+
+async function invokeVault<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
+  try {
+    return await invoke<T>(cmd, args);
+  } catch (error) {
+    throw isCommandError(error) ? new VaultCommandError(error) : error;
+  }
+}
+
 // ─── Auth ───
 
 export async function authStatus(): Promise<AuthState> {
@@ -47,12 +80,12 @@ export async function listTenants(): Promise<Tenant[]> {
   return invoke<Tenant[]>('list_tenants');
 }
 
-export async function listSubscriptions(): Promise<Subscription[]> {
+export async function listSubscriptions(stateFilter?: string): Promise<Subscription[]> {
   if (isMock()) {
     const { mockSubscriptions } = await import('../mock/data');
     return mockSubscriptions();
   }
-  return invoke<Subscription[]>('list_subscriptions');
+  return invoke<Subscription[]>('list_subscriptions', { stateFilter: stateFilter ?? null });
 }
 
 export async function listKeyvaults(subscriptionId: string): Promise<KeyVaultInfo[]> {
@@ -65,36 +98,53 @@ export async function listKeyvaults(subscriptionId: string): Promise<KeyVaultInf
 
 // ─── Vault Items ───
 
-export async function listSecrets(vaultUri: string): Promise<SecretItem[]> {
+export async function listSecrets(vaultUri: string): Promise<ListResult<SecretItem>> {
   if (isMock()) {
-    const { mockSecrets } = await import('../mock/data');
-    return mockSecrets();
+    const { mockSecrets, mockListResult } = await import('../mock/data');
+    return mockListResult(mockSecrets());
+  }
+  return invokeVault<ListResult<SecretItem>>('list_secrets', { vaultUri });
+}
+
+export async function listKeys(vaultUri: string): Promise<ListResult<KeyItem>> {
+  if (isMock()) {
+    const { mockKeys, mockListResult } = await import('../mock/data');
+    return mockListResult(mockKeys());
   }
-  return invoke<SecretItem[]>('list_secrets', { vaultUri });
+  return invokeVault<ListResult<KeyItem>>('list_keys', { vaultUri });
 }
 
-export async function listKeys(vaultUri: string): Promise<KeyItem[]> {
+export async function listCertificates(vaultUri: string): Promise<ListResult<CertificateItem>> {
   if (isMock()) {
-    const { mockKeys } = await import('../mock/data');
-    return mockKeys();
+    const { mockCertificates, mockListResult } = await import('../mock/data');
+    return mockListResult(mockCertificates());
   }
-  return invoke<KeyItem[]>('list_keys', { vaultUri });
+  return invokeVault<ListResult<CertificateItem>>('list_certificates', { vaultUri });
 }
 
-export async function listCertificates(vaultUri: string): Promise<CertificateItem[]> {
+export async function getSecretValue(vaultUri: string, name: string): Promise<MaskedSecretValue> {
   if (isMock()) {
-    const { mockCertificates } = await import('../mock/data');
-    return mockCertificates();
+    const { mockMaskedSecretValue } = await import('../mock/data');
+    return mockMaskedSecretValue(name);
   }
-  return invoke<CertificateItem[]>('list_certificates', { vaultUri });
+  return invokeVault<MaskedSecretValue>('get_secret_value', { vaultUri, name });
 }
 
-export async function getSecretValue(vaultUri: string, name: string): Promise<SecretValue> {
+export async function revealSecretValue(
+  vaultUri: string,
+  name: string,
+  revealToken: string,
+): Promise<SecretValue> {
   if (isMock()) {
     const { mockSecretValue } = await import('../mock/data');
     return mockSecretValue(name);
   }
-  return invoke<SecretValue>('get_secret_value', { vaultUri, name });
+  return invokeVault<SecretValue>('reveal_secret_value', {
+    vaultUri,
+    name,
+    version: null,
+    revealToken,
+  });
 }
 
 export async function getSecretMetadata(vaultUri: string, name: string): Promise<SecretItem> {
@@ -104,33 +154,47 @@ export async function getSecretMetadata(vaultUri: string, name: string): Promise
     if (!item) throw new Error(`Secret ${name} not found in mock data`);
     return item;
   }
-  return invoke<SecretItem>('get_secret_metadata', { vaultUri, name });
+  return invokeVault<SecretItem>('get_secret_metadata', { vaultUri, name });
 }
 
 export async function setSecret(
   vaultUri: string,
   request: CreateSecretRequest,
+  validateContent?: boolean,
 ): Promise<SecretItem> {
   if (isMock()) {
     const { mockSetSecret } = await import('../mock/data');
     return mockSetSecret(request);
   }
-  return invoke<SecretItem>('set_secret', { vaultUri, request });
+  return invokeVault<SecretItem>('set_secret', {
+    vaultUri,
+    request,
+    validateContent: validateContent ?? null,
+  });
 }
 
 export async function deleteSecret(vaultUri: string, name: string): Promise<void> {
   if (isMock()) return;
-  return invoke<void>('delete_secret', { vaultUri, name });
+  return invokeVault<void>('delete_secret', { vaultUri, name });
 }
 
 export async function recoverSecret(vaultUri: string, name: string): Promise<void> {
   if (isMock()) return;
-  return invoke<void>('recover_secret', { vaultUri, name });
+  return invokeVault<void>('recover_secret', { vaultUri, name });
 }
 
-export async function purgeSecret(vaultUri: string, name: string): Promise<void> {
+export async function requestPurge(vaultUri: string, name: string): Promise<string> {
+  if (isMock()) return `mock-purge-token-${name}`;
+  return invokeVault<string>('request_purge', { vaultUri, name });
+}
+
+export async function purgeSecret(
+  vaultUri: string,
+  name: string,
+  confirmationToken: string,
+): Promise<void> {
   if (isMock()) return;
-  return invoke<void>('purge_secret', { vaultUri, name });
+  return invokeVault<void>('purge_secret', { vaultUri, name, confirmationToken });
 }
 
 // ─── Audit ───
@@ -143,9 +207,9 @@ export async function getAuditLog(limit?: number): Promise<AuditEntry[]> {
   return invoke<AuditEntry[]>('get_audit_log', { limit: limit ?? null });
 }
 
-export async function exportAuditLog(): Promise<string> {
+export async function exportAuditLog(compact?: boolean): Promise<string> {
   if (isMock()) return '[]';
-  return invoke<string>('export_audit_log');
+  return invoke<string>('export_audit_log', { compact: compact ?? null });
 }
 
 export async function clearAuditLog(): Promise<void> {
@@ -155,7 +219,17 @@ export async function clearAuditLog(): Promise<void> {
 
 // ─── Export ───
 
-export async function exportItems(itemsJson: string, format: string): Promise<string> {
+export async function exportItems(
+  itemsJson: string,
+  format: string,
+  fields?: string[],
+  groupByContentType?: boolean,
+): Promise<string> {
   if (isMock()) return itemsJson;
-  return invoke<string>('export_items', { itemsJson, format });
+  return invoke<string>('export_items', {
+    itemsJson,
+    format,
+    fields: fields ?? null,
+    groupByContentType: groupByContentType ?? null,
+  });
 }
@@ -29,6 +29,8 @@ export interface KeyVaultInfo {
   vaultUri: string;
   tags: Record<string, string> | null;
   softDeleteEnabled: boolean | null;
+  publicNetworkAccess: string | null;
+  networkDefaultDeny: boolean | null;
 }
 
 // ── Vault Items ──
@@ -36,11 +38,16 @@ export interface KeyVaultInfo {
 export interface SecretItem {
   id: string;
   name: string;
+  version: string | null;
   enabled: boolean;
   created: string | null;
   updated: string | null;
   expires: string | null;
   notBefore: string | null;
+  createdEpoch?: number | null;
+  updatedEpoch?: number | null;
+  expiresEpoch?: number | null;
+  recoveryLevel?: string | null;
   contentType: string | null;
   tags: Record<string, string> | null;
   managed: boolean | null;
@@ -52,6 +59,14 @@ export interface SecretValue {
   name: string;
 }
 
+export interface MaskedSecretValue {
+  id: string;
+  name: string;
+  preview: string;
+  length: number;
+  revealToken: string;
+}
+
 export interface KeyItem {
   id: string;
   name: string;
@@ -60,6 +75,10 @@ export interface KeyItem {
   updated: string | null;
   expires: string | null;
   notBefore: string | null;
+  createdEpoch?: number | null;
+  updatedEpoch?: number | null;
+  expiresEpoch?: number | null;
+  recoveryLevel?: string | null;
   keyType: string | null;
   keyOps: string[] | null;
   tags: Record<string, string> | null;
@@ -74,11 +93,23 @@ export interface CertificateItem {
   updated: string | null;
   expires: string | null;
   notBefore: string | null;
+  createdEpoch?: number | null;
+  updatedEpoch?: number | null;
+  expiresEpoch?: number | null;
+  recoveryLevel?: string | null;
   subject: string | null;
   thumbprint: string | null;
   tags: Record<string, string> | null;
 }
 
+export interface ListResult<T> {
+  items: T[];
+  truncated: boolean;
+  error: string | null;
+  stale: boolean;
+  fromCache: boolean;
+}
+
 // ── Create/Update ──
 
 export interface CreateSecretRequest {
@@ -94,6 +125,7 @@ export interface CreateSecretRequest {
 // ── Audit ──
 
 export interface AuditEntry {
+  seq: number;
   timestamp: string;
   vaultName: string;
   action: string;
@@ -101,6 +133,8 @@ export interface AuditEntry {
   itemName: string;
   result: string;
   details: string | null;
+  /** `x-ms-client-request-id` of the operation, when it called Azure. */
+  requestId?: string;
 }
 
 // ── UI State ──
@@ -130,6 +164,12 @@ export interface PinnedVault {
 
 // ── Error handling ──
 
+export interface CommandError {
+  status: number | null;
+  message: string;
+  hint: string | null;
+}
+
 export interface UserFacingError {
   title: string;
   description: string;